@@ -0,0 +1,61 @@
+use eth_trie_utils::partial_trie::HashedPartialTrie;
+use ethereum_types::Address;
+use hex_literal::hex;
+use plonky2_evm::generation::mpt::AccountRlp;
+use plonky2_evm::generation::TrieInputs;
+use plonky2_evm::Node;
+use plonky2_sample::{
+    eth_to_wei, prove_inputs, BlockBuilder, BlockMetadataBuilder, HashConfig, SignedTxn,
+    StateTrieBuilder, SAMPLE_TXN,
+};
+
+/// Test that listing the receiver in `addresses` doesn't change the gas of a transfer.
+#[test]
+fn test_accessed_addresses() -> anyhow::Result<()> {
+    let sender = Address::from(hex!("2c7536e3605d9c16a7a3d7b1898e529396a65c23"));
+    let to = Address::from(hex!("a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0"));
+    let mut state_before = StateTrieBuilder::new();
+    state_before.insert(
+        sender,
+        AccountRlp {
+            nonce: 5.into(),
+            balance: eth_to_wei(100_000.into()),
+            ..AccountRlp::default()
+        },
+    );
+    let tries_before = TrieInputs {
+        state_trie: state_before.build(),
+        transactions_trie: HashedPartialTrie::from(Node::Empty),
+        receipts_trie: HashedPartialTrie::from(Node::Empty),
+        storage_tries: vec![],
+    };
+    let txn = SAMPLE_TXN;
+    let block_metadata = BlockMetadataBuilder::new()
+        .beneficiary(Address::from(hex!(
+            "deadbeefdeadbeefdeadbeefdeadbeefdeadbeef"
+        )))
+        .gas_used(21032.into())
+        .build()?;
+
+    let mut builder = BlockBuilder::new(tries_before, block_metadata);
+    builder.push_txn(SignedTxn {
+        sender,
+        bytes: txn.to_vec(),
+        gas_used: 21032.into(),
+    });
+    let cold = builder.clone().build()?.remove(0);
+    builder.with_accessed_addresses(vec![sender, to]);
+    let warm = builder.build()?.remove(0);
+    assert_eq!(warm.addresses, vec![sender, to]);
+
+    // 送信者と受信者は元々ウォームなので、リストに入れてもガスは21032のまま
+    let cold_proof = prove_inputs(cold, HashConfig::Keccak)?;
+    let warm_proof = prove_inputs(warm, HashConfig::Keccak)?;
+    assert_eq!(cold_proof.summary().gas_used, 21032.into());
+    assert_eq!(warm_proof.summary().gas_used, cold_proof.summary().gas_used);
+    assert_eq!(
+        warm_proof.summary().state_root,
+        cold_proof.summary().state_root
+    );
+    warm_proof.verify()
+}
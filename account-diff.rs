@@ -0,0 +1,115 @@
+use ethereum_types::{Address, U256};
+use hex_literal::hex;
+use plonky2_evm::generation::mpt::AccountRlp;
+use plonky2_sample::{
+    apply_simple_transfer, balance_delta, diff_tries, eth_to_wei, format_balance_deltas,
+    StateTrieBuilder,
+};
+
+/// Test that a transfer changes exactly the sender and the receiver.
+#[test]
+fn test_diff_tries_after_transfer() -> anyhow::Result<()> {
+    let sender = Address::from(hex!("2c7536e3605d9c16a7a3d7b1898e529396a65c23"));
+    let to = Address::from(hex!("a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0"));
+    let bystander = Address::from(hex!("deadbeefdeadbeefdeadbeefdeadbeefdeadbeef"));
+    let sender_account = AccountRlp {
+        nonce: 5.into(),
+        balance: eth_to_wei(100_000.into()),
+        ..AccountRlp::default()
+    };
+
+    let mut state_before = StateTrieBuilder::new();
+    state_before.insert(sender, sender_account);
+    state_before.insert(
+        bystander,
+        AccountRlp {
+            balance: eth_to_wei(1.into()),
+            ..AccountRlp::default()
+        },
+    );
+    let before = state_before.build();
+    let after = apply_simple_transfer(&before, sender, to, 100.into(), 21032.into(), 10.into())?;
+
+    let diffs = diff_tries(&before, &after)?;
+    assert_eq!(diffs.len(), 2);
+
+    // 送信者は変更、受信者は追加
+    let sender_diff = diffs.iter().find(|diff| diff.is_for(sender)).unwrap();
+    assert_eq!(sender_diff.before, Some(sender_account));
+    let sender_after = sender_diff.after.unwrap();
+    assert_eq!(sender_after.nonce, 6.into());
+    assert_eq!(
+        sender_after.balance,
+        sender_account.balance - 100 - 21032 * 10
+    );
+
+    let to_diff = diffs.iter().find(|diff| diff.is_for(to)).unwrap();
+    assert_eq!(to_diff.before, None);
+    assert_eq!(to_diff.after.unwrap().balance, U256::from(100));
+
+    assert!(!diffs.iter().any(|diff| diff.is_for(bystander)));
+
+    // 逆向きなら受信者は削除になる
+    let reversed = diff_tries(&after, &before)?;
+    let to_diff = reversed.iter().find(|diff| diff.is_for(to)).unwrap();
+    assert!(to_diff.before.is_some() && to_diff.after.is_none());
+    Ok(())
+}
+
+fn account_with_balance(balance: u64) -> AccountRlp {
+    AccountRlp {
+        balance: balance.into(),
+        ..AccountRlp::default()
+    }
+}
+
+/// Test the delta of a balance that increased.
+#[test]
+fn test_balance_delta_increase() {
+    let delta = balance_delta(&account_with_balance(100), &account_with_balance(250));
+    assert_eq!(delta, (false, U256::from(150)));
+}
+
+/// Test the delta of a balance that decreased, whose magnitude is still positive.
+#[test]
+fn test_balance_delta_decrease() {
+    let delta = balance_delta(&account_with_balance(250), &account_with_balance(100));
+    assert_eq!(delta, (true, U256::from(150)));
+}
+
+/// Test the delta of a balance that didn't change.
+#[test]
+fn test_balance_delta_no_change() {
+    let delta = balance_delta(&account_with_balance(100), &account_with_balance(100));
+    assert_eq!(delta, (false, U256::zero()));
+}
+
+/// Test the table of balance changes of the sample transfer.
+#[test]
+fn test_format_balance_deltas() -> anyhow::Result<()> {
+    let sender = Address::from(hex!("2c7536e3605d9c16a7a3d7b1898e529396a65c23"));
+    let to = Address::from(hex!("a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0"));
+    let bystander = Address::from(hex!("deadbeefdeadbeefdeadbeefdeadbeefdeadbeef"));
+    let mut state_before = StateTrieBuilder::new();
+    state_before.insert(sender, account_with_balance(1_000_000));
+    let before = state_before.build();
+    let after = apply_simple_transfer(&before, sender, to, 100.into(), 21032.into(), 10.into())?;
+
+    let table = format_balance_deltas(
+        &before,
+        &after,
+        &[("sender", sender), ("to", to), ("other", bystander)],
+    )?;
+    println!("{table}");
+    // 送信者は送金額とガス代を失う
+    let lines: Vec<_> = table.lines().collect();
+    assert_eq!(
+        lines,
+        vec![
+            format!("sender {sender:?} -210420 wei"),
+            format!("to     {to:?} +100 wei"),
+            format!("other  {bystander:?} 0 wei"),
+        ]
+    );
+    Ok(())
+}
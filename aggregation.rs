@@ -0,0 +1,88 @@
+use eth_trie_utils::partial_trie::{HashedPartialTrie, PartialTrie};
+use ethereum_types::Address;
+use hex_literal::hex;
+use keccak_hash::keccak;
+use plonky2::util::timing::TimingTree;
+use plonky2_evm::all_stark::AllStark;
+use plonky2_evm::config::StarkConfig;
+use plonky2_evm::generation::mpt::AccountRlp;
+use plonky2_evm::generation::TrieInputs;
+use plonky2_evm::Node;
+use plonky2_sample::{
+    aggregate_proofs, block_inputs, eth_to_wei, recursive_circuits, BlockMetadataBuilder,
+    SignedTxn, StateTrieBuilder, D, F, SAMPLE_TXN,
+};
+
+/// Test aggregating the proofs of two consecutive transfers into a single proof.
+#[test]
+fn test_aggregate_two_transfers() -> anyhow::Result<()> {
+    let sender = Address::from(hex!("2c7536e3605d9c16a7a3d7b1898e529396a65c23"));
+    let sender_account_before = AccountRlp {
+        nonce: 5.into(),
+        balance: eth_to_wei(100_000.into()),
+        storage_root: HashedPartialTrie::from(Node::Empty).hash(),
+        code_hash: keccak([]),
+    };
+    let mut state_before = StateTrieBuilder::new();
+    state_before.insert(sender, sender_account_before);
+    let tries_before = TrieInputs {
+        state_trie: state_before.build(),
+        transactions_trie: HashedPartialTrie::from(Node::Empty),
+        receipts_trie: HashedPartialTrie::from(Node::Empty),
+        storage_tries: vec![],
+    };
+
+    // multi-transfer.rs と同じ2つの送金
+    let txn_0 = SAMPLE_TXN;
+    let txn_1 = hex!("f869060a82520894a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a08a152d02c7e14af67995bc801ba00213ef373c5fd6736d9dafd04d45482b038438e5cfd0e9a7cc366454b18bcd8da042c663ec738e79b82dddb2a3819df510a461383b67da01a2928dcbc294de34a2");
+    let block_metadata = BlockMetadataBuilder::new()
+        .beneficiary(Address::from(hex!(
+            "deadbeefdeadbeefdeadbeefdeadbeefdeadbeef"
+        )))
+        .gas_used((21032 + 21000).into())
+        .build()?;
+    let txns = vec![
+        SignedTxn {
+            sender,
+            bytes: txn_0.to_vec(),
+            gas_used: 21032.into(),
+        },
+        SignedTxn {
+            sender,
+            bytes: txn_1.to_vec(),
+            gas_used: 21000.into(),
+        },
+    ];
+    let mut inputs = block_inputs(txns, tries_before, block_metadata)?;
+    let inputs_1 = inputs.pop().unwrap();
+    let inputs_0 = inputs.pop().unwrap();
+
+    let all_stark = AllStark::<F, D>::default();
+    let config = StarkConfig::standard_fast_config();
+    let circuits = recursive_circuits(&all_stark, &config);
+    let mut timing = TimingTree::new("prove root", log::Level::Debug);
+    let root_0 = circuits.prove_root(&all_stark, &config, inputs_0, &mut timing)?;
+    let root_1 = circuits.prove_root(&all_stark, &config, inputs_1, &mut timing)?;
+    let trie_roots_before = root_0.1.trie_roots_before.clone();
+    let trie_roots_after = root_1.1.trie_roots_after.clone();
+
+    let aggregated = aggregate_proofs(&circuits, root_0, root_1)?;
+
+    // 1つの証明が、1つ目の前から2つ目の後までの遷移を表している
+    assert_eq!(
+        aggregated.public_values.trie_roots_before,
+        trie_roots_before
+    );
+    assert_eq!(aggregated.public_values.trie_roots_after, trie_roots_after);
+    assert_eq!(
+        aggregated.public_values.extra_block_data.gas_used_before,
+        0.into()
+    );
+    assert_eq!(
+        aggregated.public_values.extra_block_data.gas_used_after,
+        (21032 + 21000).into()
+    );
+
+    aggregated.verify(&circuits)?;
+    Ok(())
+}
@@ -0,0 +1,84 @@
+use eth_trie_utils::partial_trie::PartialTrie;
+use ethereum_types::{Address, Bloom, H256, U256};
+use hex_literal::hex;
+use plonky2_evm::generation::mpt::{AccountRlp, LegacyReceiptRlp};
+use plonky2_evm::proof::TrieRoots;
+use plonky2_sample::{
+    apply_simple_transfer, assert_roots, build_receipts_trie, build_transactions_trie, eth_to_wei,
+    prove_transfer, BlockMetadataBuilder, ConfigPreset, HashConfig, ProveError, StateTrieBuilder,
+    SAMPLE_TXN,
+};
+
+/// Test checking the roots of the sample transfer against roots computed outside of the prover.
+#[test]
+fn test_assert_roots() -> anyhow::Result<()> {
+    let sender = Address::from(hex!("2c7536e3605d9c16a7a3d7b1898e529396a65c23"));
+    let to = Address::from(hex!("a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0"));
+    let value = U256::from(100u32);
+    let sender_account_before = AccountRlp {
+        nonce: 5.into(),
+        balance: eth_to_wei(100_000.into()),
+        ..AccountRlp::default()
+    };
+    let txn = SAMPLE_TXN;
+    let block_metadata = BlockMetadataBuilder::new()
+        .beneficiary(Address::from(hex!(
+            "deadbeefdeadbeefdeadbeefdeadbeefdeadbeef"
+        )))
+        .gas_used(21032.into())
+        .build()?;
+    let proof = prove_transfer(
+        sender,
+        to,
+        value,
+        sender_account_before,
+        &txn,
+        block_metadata,
+        HashConfig::Keccak,
+        ConfigPreset::Fast,
+    )?;
+
+    // 実行クライアントから得られるのと同じルートを、証明とは別に計算する
+    let mut state_before = StateTrieBuilder::new();
+    state_before.insert(sender, sender_account_before);
+    let state_after = apply_simple_transfer(
+        &state_before.build(),
+        sender,
+        to,
+        value,
+        21032.into(),
+        10.into(),
+    )?;
+    let receipts_trie = build_receipts_trie(&[LegacyReceiptRlp {
+        status: true,
+        cum_gas_used: 21032.into(),
+        bloom: Bloom::zero().as_bytes().to_vec().into(),
+        logs: vec![],
+    }]);
+    let expected = TrieRoots {
+        state_root: state_after.hash(),
+        transactions_root: build_transactions_trie(&[txn.to_vec()]).hash(),
+        receipts_root: receipts_trie.hash(),
+    };
+    let summary = proof.summary();
+    assert_roots(&summary, expected.clone())?;
+
+    // レシートのルートだけが違えば、エラーはreceipts_rootを指す
+    let err = assert_roots(
+        &summary,
+        TrieRoots {
+            receipts_root: H256::repeat_byte(0x42),
+            ..expected
+        },
+    )
+    .unwrap_err();
+    assert!(matches!(
+        err,
+        ProveError::TrieRootMismatch {
+            root: "receipts_root",
+            ..
+        }
+    ));
+    assert!(err.to_string().contains("receipts_root"));
+    Ok(())
+}
@@ -0,0 +1,104 @@
+use ethereum_types::{Address, U256};
+use hex_literal::hex;
+use plonky2_evm::generation::mpt::AccountRlp;
+use plonky2_sample::{apply_simple_transfer, apply_withdrawals, ProveError, StateTrieBuilder};
+
+const SENDER: Address = Address::repeat_byte(0x2c);
+const TO: Address = Address::repeat_byte(0xa0);
+
+fn state_with_sender(sender_account: AccountRlp) -> StateTrieBuilder {
+    let mut state = StateTrieBuilder::new();
+    state.insert(SENDER, sender_account);
+    state
+}
+
+/// Test that a gas cost that overflows is reported as an unaffordable cost instead of panicking.
+#[test]
+fn test_cost_overflow() {
+    let state = state_with_sender(AccountRlp {
+        balance: U256::MAX,
+        ..AccountRlp::default()
+    });
+    let err = apply_simple_transfer(
+        &state.build(),
+        SENDER,
+        TO,
+        100.into(),
+        21032.into(),
+        U256::MAX,
+    )
+    .unwrap_err();
+    assert!(matches!(
+        err,
+        ProveError::InsufficientBalance { cost, .. } if cost == U256::MAX
+    ));
+}
+
+/// Test that the balance doesn't underflow when the sender can't pay for the gas.
+#[test]
+fn test_balance_underflow() {
+    let state = state_with_sender(AccountRlp {
+        balance: 21031.into(),
+        ..AccountRlp::default()
+    });
+    // 送金額が0でも、ガス代を払えなければエラーになる
+    let err = apply_simple_transfer(&state.build(), SENDER, TO, 0.into(), 21032.into(), 1.into())
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        ProveError::InsufficientBalance { balance, cost, .. }
+            if balance == 21031.into() && cost == 21032.into()
+    ));
+}
+
+/// Test that a sender at the maximum nonce can't send another transaction.
+#[test]
+fn test_max_nonce() {
+    let state = state_with_sender(AccountRlp {
+        nonce: u64::MAX.into(),
+        balance: 1_000_000.into(),
+        ..AccountRlp::default()
+    });
+    let err = apply_simple_transfer(
+        &state.build(),
+        SENDER,
+        TO,
+        100.into(),
+        21000.into(),
+        1.into(),
+    )
+    .unwrap_err();
+    assert!(matches!(err, ProveError::InvalidTransaction(_)));
+
+    // 1つ手前のnonceなら増やせる
+    let state = state_with_sender(AccountRlp {
+        nonce: (u64::MAX - 1).into(),
+        balance: 1_000_000.into(),
+        ..AccountRlp::default()
+    });
+    assert!(apply_simple_transfer(
+        &state.build(),
+        SENDER,
+        TO,
+        100.into(),
+        21000.into(),
+        1.into()
+    )
+    .is_ok());
+}
+
+/// Test that a credit overflowing the balance fails.
+#[test]
+fn test_credit_overflow() {
+    let rich = Address::from(hex!("deadbeefdeadbeefdeadbeefdeadbeefdeadbeef"));
+    let mut state = StateTrieBuilder::new();
+    state.insert(
+        rich,
+        AccountRlp {
+            balance: U256::MAX,
+            ..AccountRlp::default()
+        },
+    );
+    let err = apply_withdrawals(&state.build(), &[(rich, 1.into())]).unwrap_err();
+    assert!(matches!(err, ProveError::InvalidInputs(_)));
+}
@@ -0,0 +1,83 @@
+use eth_trie_utils::partial_trie::HashedPartialTrie;
+use ethereum_types::{Address, U256};
+use hex_literal::hex;
+use plonky2_evm::generation::mpt::AccountRlp;
+use plonky2_sample::{
+    apply_transfer_txn, eth_to_wei, gas_fee, get_account, BlockMetadataBuilder, GasFee, SignedTxn,
+    StateTrieBuilder,
+};
+
+// eip1559.rs と同じEIP-1559の送金
+// (nonce 5, max_priority_fee_per_gas 2, max_fee_per_gas 20, to 0xa0..a0, value 100)
+const TXN: [u8; 101] = hex!("02f8620105021482520894a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a06480c080a0dcd94a10b7e0bdcdbe47d6cb91dcba8b0c6edc2e80bc1272973b979edbd64a9da027383b59211cf3eef7abc9caea416137cf8bb487e0549c13d7015c32136a7ab3");
+
+/// Returns the sum of the balances of `addrs` in `trie`.
+fn total_balance(trie: &HashedPartialTrie, addrs: &[Address]) -> anyhow::Result<U256> {
+    let mut total = U256::zero();
+    for &addr in addrs {
+        if let Some(account) = get_account(trie, addr)? {
+            total += account.balance;
+        }
+    }
+    Ok(total)
+}
+
+/// Test splitting a fee between the beneficiary and the burn.
+#[test]
+fn test_gas_fee() {
+    assert_eq!(
+        gas_fee(21000.into(), 10.into(), 2.into()),
+        GasFee {
+            paid: (21000 * 12).into(),
+            tip: (21000 * 2).into(),
+            burnt: (21000 * 10).into(),
+        }
+    );
+}
+
+/// Test that the base fee of a transfer leaves the total supply.
+#[test]
+fn test_base_fee_burn() -> anyhow::Result<()> {
+    let sender = Address::from(hex!("2c7536e3605d9c16a7a3d7b1898e529396a65c23"));
+    let to = Address::from(hex!("a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0"));
+    let beneficiary = Address::from(hex!("deadbeefdeadbeefdeadbeefdeadbeefdeadbeef"));
+    let mut state_before = StateTrieBuilder::new();
+    state_before.insert(
+        sender,
+        AccountRlp {
+            nonce: 5.into(),
+            balance: eth_to_wei(100_000.into()),
+            ..AccountRlp::default()
+        },
+    );
+    let state_before = state_before.build();
+    let block_metadata = BlockMetadataBuilder::new()
+        .beneficiary(beneficiary)
+        .gas_used(21000.into())
+        .build()?;
+    let signed_txn = SignedTxn {
+        sender,
+        bytes: TXN.to_vec(),
+        gas_used: 21000.into(),
+    };
+    let state_after = apply_transfer_txn(&state_before, &signed_txn, &block_metadata)?;
+
+    // 実効ガス価格は min(20, 10 + 2) = 12
+    let fee = gas_fee(21000.into(), block_metadata.block_base_fee, 2.into());
+    let sender_after = get_account(&state_after, sender)?.expect("the sender exists");
+    assert_eq!(
+        sender_after.balance,
+        eth_to_wei(100_000.into()) - 100 - fee.paid
+    );
+    let beneficiary_after =
+        get_account(&state_after, beneficiary)?.expect("the beneficiary is paid");
+    assert_eq!(beneficiary_after.balance, fee.tip);
+
+    // 送金額は移るだけなので、総量はベースフィーの分だけ減る
+    let addrs = [sender, to, beneficiary];
+    assert_eq!(
+        total_balance(&state_before, &addrs)? - total_balance(&state_after, &addrs)?,
+        fee.burnt
+    );
+    Ok(())
+}
@@ -0,0 +1,46 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use plonky2_evm::all_stark::AllStark;
+use plonky2_sample::{prove_inputs_with, sample_transfer_inputs, ConfigPreset, HashConfig, D, F};
+
+fn bench_setup(c: &mut Criterion) {
+    // ProverContextを使い回すことで、証明ごとに節約できる時間
+    c.bench_function("all_stark_setup", |b| b.iter(AllStark::<F, D>::default));
+}
+
+fn bench_transfer(c: &mut Criterion) {
+    // AllStarkの構築は計測に含めない
+    let all_stark = AllStark::<F, D>::default();
+    let inputs = sample_transfer_inputs();
+    // 速さと安全性のトレードオフを比べる
+    let presets = [ConfigPreset::Fast, ConfigPreset::Secure];
+
+    let mut group = c.benchmark_group("prove_transfer");
+    group.sample_size(10);
+    for preset in presets {
+        group.bench_with_input(
+            BenchmarkId::from_parameter(format!("{preset:?}")),
+            &preset,
+            |b, &preset| {
+                b.iter(|| {
+                    prove_inputs_with(&all_stark, preset, inputs.clone(), HashConfig::Keccak)
+                        .unwrap()
+                })
+            },
+        );
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("verify_proof");
+    group.sample_size(10);
+    for preset in presets {
+        let proof =
+            prove_inputs_with(&all_stark, preset, inputs.clone(), HashConfig::Keccak).unwrap();
+        group.bench_function(BenchmarkId::from_parameter(format!("{preset:?}")), |b| {
+            b.iter(|| proof.verify_with(&all_stark).unwrap())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_setup, bench_transfer);
+criterion_main!(benches);
@@ -0,0 +1,90 @@
+use eth_trie_utils::partial_trie::{HashedPartialTrie, PartialTrie};
+use ethereum_types::{Address, U256};
+use hex_literal::hex;
+use plonky2_evm::generation::mpt::AccountRlp;
+use plonky2_evm::generation::TrieInputs;
+use plonky2_evm::Node;
+use plonky2_sample::{
+    apply_transfer_txn, eth_to_wei, prove_block, BlockMetadataBuilder, HashConfig, SignedTxn,
+    StateTrieBuilder, SAMPLE_TXN,
+};
+
+/// Test that the beneficiary accumulates the priority fees of every transaction of a block.
+#[test]
+fn test_beneficiary_fees() -> anyhow::Result<()> {
+    let sender = Address::from(hex!("2c7536e3605d9c16a7a3d7b1898e529396a65c23"));
+    let to = Address::from(hex!("a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0"));
+    let beneficiary = Address::from(hex!("deadbeefdeadbeefdeadbeefdeadbeefdeadbeef"));
+    let sender_account_before = AccountRlp {
+        nonce: 5.into(),
+        balance: eth_to_wei(100_000.into()),
+        ..AccountRlp::default()
+    };
+    let mut state_before = StateTrieBuilder::new();
+    state_before.insert(sender, sender_account_before);
+    let tries_before = TrieInputs {
+        state_trie: state_before.build(),
+        transactions_trie: HashedPartialTrie::from(Node::Empty),
+        receipts_trie: HashedPartialTrie::from(Node::Empty),
+        storage_tries: vec![],
+    };
+
+    // どちらもgas_price 10。1つ目はサンプルの100weiの送金(nonce 5, 21032 gas)、
+    // 2つ目は1000weiの送金(nonce 6, 21000 gas)
+    let txns = vec![
+        SignedTxn {
+            sender,
+            bytes: SAMPLE_TXN.to_vec(),
+            gas_used: 21032.into(),
+        },
+        SignedTxn {
+            sender,
+            bytes: hex!("f861060a82520894a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a08203e8801ca0ca96224bf09f80556a5f7d05e79ab80e8e42fd2c9af8bcda2a7949c5280ba5eea00571380a38c4a1909aab730adcdeac337a89736ea131994573a893945ad66dff").to_vec(),
+            gas_used: 21000.into(),
+        },
+    ];
+    // ベースフィーは4なので、1ガスあたり6がbeneficiaryに支払われる
+    let block_metadata = BlockMetadataBuilder::new()
+        .beneficiary(beneficiary)
+        .base_fee(4.into())
+        .gas_used((21032 + 21000).into())
+        .build()?;
+
+    // 期待する状態は、トランザクションを順に適用して求める
+    let mut expected_state_after = tries_before.state_trie.clone();
+    for txn in &txns {
+        expected_state_after = apply_transfer_txn(&expected_state_after, txn, &block_metadata)?;
+    }
+    let mut state_after = StateTrieBuilder::new();
+    state_after.insert(
+        sender,
+        AccountRlp {
+            nonce: 7.into(),
+            balance: sender_account_before.balance - 100 - 1000 - (21032 + 21000) * 10,
+            ..sender_account_before
+        },
+    );
+    state_after.insert(
+        to,
+        AccountRlp {
+            balance: 1100.into(),
+            ..AccountRlp::default()
+        },
+    );
+    state_after.insert(
+        beneficiary,
+        AccountRlp {
+            balance: U256::from(21032 + 21000) * 6,
+            ..AccountRlp::default()
+        },
+    );
+    assert_eq!(expected_state_after.hash(), state_after.build().hash());
+
+    let proofs = prove_block(txns, tries_before, block_metadata, HashConfig::Keccak)?;
+    let last = proofs.last().unwrap();
+    assert_eq!(last.summary().state_root, expected_state_after.hash());
+    for proof in &proofs {
+        proof.verify()?;
+    }
+    Ok(())
+}
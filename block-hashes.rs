@@ -0,0 +1,59 @@
+use eth_trie_utils::partial_trie::{HashedPartialTrie, PartialTrie};
+use ethereum_types::{H256, U256};
+use plonky2_evm::generation::TrieInputs;
+use plonky2_evm::Node;
+use plonky2_sample::{BlockBuilder, BlockHashBuilder, BlockMetadataBuilder};
+
+/// Test that the parent hash lands where `BLOCKHASH(block_number - 1)` reads it.
+#[test]
+fn test_block_hash_builder() -> anyhow::Result<()> {
+    let block_number = U256::from(1000);
+    let parent_hash = H256::repeat_byte(0x11);
+    let oldest_hash = H256::repeat_byte(0x22);
+    let block_hashes = BlockHashBuilder::new(block_number)
+        .parent_hash(block_number - 1, parent_hash)
+        .parent_hash(block_number - 256, oldest_hash)
+        .cur_hash(H256::repeat_byte(0x33))
+        .build()?;
+
+    // prev_hashesの最後が親ブロック、先頭が256個前のブロック
+    assert_eq!(block_hashes.prev_hashes.len(), 256);
+    assert_eq!(block_hashes.prev_hashes[255], parent_hash);
+    assert_eq!(block_hashes.prev_hashes[0], oldest_hash);
+    assert_eq!(block_hashes.cur_hash, H256::repeat_byte(0x33));
+
+    // ブロックの全ての証明に同じハッシュが入る
+    let block_metadata = BlockMetadataBuilder::new().number(block_number).build()?;
+    let tries = TrieInputs {
+        state_trie: HashedPartialTrie::from(Node::Empty),
+        transactions_trie: HashedPartialTrie::from(Node::Empty),
+        receipts_trie: HashedPartialTrie::from(Node::Empty),
+        storage_tries: vec![],
+    };
+    let mut builder = BlockBuilder::new(tries, block_metadata);
+    builder.with_block_hashes(block_hashes.clone());
+    let inputs = builder.build()?;
+    assert_eq!(inputs[0].block_hashes.prev_hashes[255], parent_hash);
+    Ok(())
+}
+
+/// Test that invalid block hashes are rejected.
+#[test]
+fn test_block_hash_builder_errors() {
+    let block_number = U256::from(1000);
+    // 256個ではない
+    assert!(BlockHashBuilder::new(block_number)
+        .prev_hashes(vec![H256::zero(); 255])
+        .build()
+        .is_err());
+    // 257個前は読めない
+    assert!(BlockHashBuilder::new(block_number)
+        .parent_hash(block_number - 257, H256::repeat_byte(0x11))
+        .build()
+        .is_err());
+    // 自分自身や未来のブロックは前のブロックではない
+    assert!(BlockHashBuilder::new(block_number)
+        .parent_hash(block_number, H256::repeat_byte(0x11))
+        .build()
+        .is_err());
+}
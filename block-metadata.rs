@@ -0,0 +1,34 @@
+use ethereum_types::{Address, BigEndianHash, H256};
+use hex_literal::hex;
+use plonky2_sample::BlockMetadataBuilder;
+
+/// Test a block built with only the beneficiary and the block number set.
+#[test]
+fn test_block_metadata_builder() -> anyhow::Result<()> {
+    let beneficiary = Address::from(hex!("deadbeefdeadbeefdeadbeefdeadbeefdeadbeef"));
+    let block_metadata = BlockMetadataBuilder::new()
+        .beneficiary(beneficiary)
+        .number(7.into())
+        .build()?;
+
+    assert_eq!(block_metadata.block_beneficiary, beneficiary);
+    assert_eq!(block_metadata.block_number, 7.into());
+    // 指定していないフィールドはデフォルト値のまま
+    assert_eq!(block_metadata.block_timestamp, 0x03e8.into());
+    assert_eq!(
+        block_metadata.block_random,
+        H256::from_uint(&0x020000.into())
+    );
+    assert_eq!(block_metadata.block_gaslimit, 0xff112233u32.into());
+    assert_eq!(block_metadata.block_chain_id, 1.into());
+    assert_eq!(block_metadata.block_base_fee, 0xa.into());
+    assert_eq!(block_metadata.block_gas_used, 0.into());
+
+    // ガス使用量がガスリミットを超えるブロックは作れない
+    assert!(BlockMetadataBuilder::new()
+        .gas_limit(21000.into())
+        .gas_used(21032.into())
+        .build()
+        .is_err());
+    Ok(())
+}
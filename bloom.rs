@@ -0,0 +1,139 @@
+use ethereum_types::{Address, H256, U256};
+use hex_literal::hex;
+use plonky2_evm::generation::mpt::AccountRlp;
+use plonky2_evm::generation::mpt::{LegacyReceiptRlp, LogRlp};
+use plonky2_sample::{
+    accumulate_block_bloom, build_receipts_trie, build_transactions_trie, compute_bloom,
+    eth_to_wei, receipt_bloom, tries_from_accounts, BlockBuilder, BlockMetadataBuilder,
+    ReceiptBuilder, SignedTxn, SAMPLE_TXN,
+};
+
+/// Returns whether bit `bit` of the 2048-bit bloom is set, counting from the least significant.
+fn bloom_bit(bloom: &[U256; 8], bit: usize) -> bool {
+    // 先頭のワードが上位ビットになる
+    bloom[7 - bit / 256].bit(bit % 256)
+}
+
+/// Test the bloom of a log with one topic.
+#[test]
+fn test_bloom_of_log_with_one_topic() {
+    let log = LogRlp {
+        address: Address::from(hex!("a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0")),
+        topics: vec![H256::from_low_u64_be(1)],
+        data: vec![0x42].into(),
+    };
+    let bloom = compute_bloom(&[log.clone()]);
+
+    // keccak(address) の先頭6バイトから 1699, 379, 930
+    // keccak(topic) の先頭6バイトから 270, 1362, 1554
+    let expected_bits = [1699, 379, 930, 270, 1362, 1554];
+    for bit in expected_bits {
+        assert!(bloom_bit(&bloom, bit), "bit {bit} isn't set");
+    }
+    let num_bits: u32 = bloom
+        .iter()
+        .map(|word| word.0.iter().map(|limb| limb.count_ones()).sum::<u32>())
+        .sum();
+    assert_eq!(num_bits, expected_bits.len() as u32);
+
+    // レシートのブルームも同じビットになる
+    let receipt = LegacyReceiptRlp {
+        status: true,
+        cum_gas_used: 21000.into(),
+        bloom: vec![].into(),
+        logs: vec![log],
+    };
+    let receipt_bloom = receipt_bloom(&receipt);
+    assert_eq!(
+        compute_bloom(&receipt.logs)
+            .map(|word| {
+                let mut bytes = [0; 32];
+                word.to_big_endian(&mut bytes);
+                bytes
+            })
+            .concat(),
+        receipt_bloom.as_bytes()
+    );
+
+    assert_eq!(compute_bloom(&[]), [U256::zero(); 8]);
+}
+
+/// Returns whether every bit set in `subset` is also set in `superset`.
+fn is_superset(superset: &[U256; 8], subset: &[U256; 8]) -> bool {
+    superset
+        .iter()
+        .zip(subset)
+        .all(|(&superset, &subset)| superset & subset == subset)
+}
+
+/// Test accumulating the blooms of two receipts on top of a non-zero block bloom.
+#[test]
+fn test_accumulate_block_bloom() {
+    let mut before = [U256::zero(); 8];
+    before[7] = 1.into();
+    before[0] = U256::one() << 255;
+    let receipts = [
+        ReceiptBuilder::new()
+            .cumulative_gas(21000.into())
+            .add_log(
+                Address::from(hex!("a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0")),
+                vec![H256::from_low_u64_be(1)],
+                vec![],
+            )
+            .build(),
+        ReceiptBuilder::new()
+            .cumulative_gas(42000.into())
+            .add_log(
+                Address::from(hex!("b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0")),
+                vec![],
+                vec![],
+            )
+            .build(),
+    ];
+
+    let after = accumulate_block_bloom(before, &receipts);
+    assert!(is_superset(&after, &before));
+    for receipt in &receipts {
+        assert!(is_superset(&after, &compute_bloom(&receipt.logs)));
+    }
+    // 何も足さなければ変わらない
+    assert_eq!(accumulate_block_bloom(before, &[]), before);
+}
+
+/// Test that the block builder carries a non-zero block bloom over to the next transaction.
+#[test]
+fn test_block_builder_keeps_bloom_before() -> anyhow::Result<()> {
+    let sender = Address::from(hex!("2c7536e3605d9c16a7a3d7b1898e529396a65c23"));
+    let mut tries = tries_from_accounts(&[(
+        sender,
+        AccountRlp {
+            nonce: 5.into(),
+            balance: eth_to_wei(100_000.into()),
+            ..AccountRlp::default()
+        },
+    )]);
+    let block_metadata = BlockMetadataBuilder::new()
+        .gas_used((21000 + 21032).into())
+        .build()?;
+    let mut before = [U256::zero(); 8];
+    before[3] = 0xff.into();
+
+    // 前のトランザクションは0番にあるものとする
+    tries.transactions_trie = build_transactions_trie(&[vec![0x01]]);
+    tries.receipts_trie =
+        build_receipts_trie(&[ReceiptBuilder::new().cumulative_gas(21000.into()).build()]);
+    let mut builder = BlockBuilder::new(tries, block_metadata);
+    builder.with_previous_txns(1, 21000.into(), before);
+    builder.push_txn(SignedTxn {
+        sender,
+        bytes: SAMPLE_TXN.to_vec(),
+        gas_used: 21032.into(),
+    });
+    let inputs = builder.build()?.remove(0);
+
+    assert_eq!(inputs.block_bloom_before, before);
+    assert!(is_superset(&inputs.block_bloom_after, &before));
+    // 送金のレシートにはログが無いので、ブルームは増えない
+    assert_eq!(inputs.block_bloom_after, before);
+    Ok(())
+}
@@ -0,0 +1,57 @@
+use ethereum_types::{Address, Bloom, U256};
+use hex_literal::hex;
+use plonky2_evm::generation::mpt::{AccountRlp, LegacyReceiptRlp};
+use plonky2_sample::{
+    build_receipts_trie, check_receipt, eth_to_wei, prove_transfer, BlockMetadataBuilder,
+    ConfigPreset, HashConfig, ProveError, SAMPLE_TXN,
+};
+
+/// Test the receipt of the sample transfer after proving it.
+#[test]
+fn test_check_receipt() -> anyhow::Result<()> {
+    let sender = Address::from(hex!("2c7536e3605d9c16a7a3d7b1898e529396a65c23"));
+    let to = Address::from(hex!("a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0"));
+    let sender_account_before = AccountRlp {
+        nonce: 5.into(),
+        balance: eth_to_wei(100_000.into()),
+        ..AccountRlp::default()
+    };
+    let txn = SAMPLE_TXN;
+    let block_metadata = BlockMetadataBuilder::new()
+        .beneficiary(Address::from(hex!(
+            "deadbeefdeadbeefdeadbeefdeadbeefdeadbeef"
+        )))
+        .gas_used(21032.into())
+        .build()?;
+    let proof = prove_transfer(
+        sender,
+        to,
+        U256::from(100u32),
+        sender_account_before,
+        &txn,
+        block_metadata,
+        HashConfig::Keccak,
+        ConfigPreset::Fast,
+    )?;
+    proof.verify()?;
+
+    // ログの無い成功したトランザクションのレシート
+    let receipts_trie = build_receipts_trie(&[LegacyReceiptRlp {
+        status: true,
+        cum_gas_used: 21032.into(),
+        bloom: Bloom::zero().as_bytes().to_vec().into(),
+        logs: vec![],
+    }]);
+    let summary = proof.summary();
+    check_receipt(&summary, &receipts_trie, 0, true, 21032.into())?;
+
+    assert!(matches!(
+        check_receipt(&summary, &receipts_trie, 0, false, 21032.into()),
+        Err(ProveError::UnexpectedReceipt(_))
+    ));
+    assert!(matches!(
+        check_receipt(&summary, &receipts_trie, 1, true, 21032.into()),
+        Err(ProveError::UnexpectedReceipt(_))
+    ));
+    Ok(())
+}
@@ -0,0 +1,45 @@
+use assert_cmd::Command;
+use predicates::str::contains;
+
+/// Test proving the example transfer with the CLI binary.
+#[test]
+fn test_cli_proves_example_transfer() -> anyhow::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let output = dir.path().join("proof.json");
+
+    Command::cargo_bin("plonky2-sample")?
+        .arg("examples/transfer.json")
+        .arg("--output")
+        .arg(&output)
+        .arg("--verify")
+        .assert()
+        .success()
+        .stdout(contains("verified, state root: 0x"));
+
+    assert!(output.exists());
+    Ok(())
+}
+
+/// Test verifying a proof written by the CLI in a separate invocation.
+#[test]
+fn test_cli_verifies_proof_file() -> anyhow::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let output = dir.path().join("proof.json");
+
+    Command::cargo_bin("plonky2-sample")?
+        .arg("examples/transfer.json")
+        .arg("--output")
+        .arg(&output)
+        .assert()
+        .success();
+
+    // 証明し直さずに、ファイルだけから検証する
+    Command::cargo_bin("plonky2-sample")?
+        .arg("verify")
+        .arg(&output)
+        .assert()
+        .success()
+        .stdout(contains("state root: 0x"))
+        .stdout(contains("receipts root: 0x"));
+    Ok(())
+}
@@ -0,0 +1,76 @@
+use std::str::FromStr;
+
+use eth_trie_utils::nibbles::Nibbles;
+use eth_trie_utils::partial_trie::PartialTrie;
+use ethereum_types::{Address, U256};
+use hex_literal::hex;
+use keccak_hash::keccak;
+use plonky2_evm::generation::mpt::AccountRlp;
+use plonky2_sample::{
+    apply_transfer_txn, collect_accounts, eth_to_wei, prune_to_touched, sample_transfer_inputs,
+    ProveError, SignedTxn, StateTrieBuilder, SAMPLE_SENDER,
+};
+
+/// Test collecting the accounts of the state after the sample transfer.
+#[test]
+fn test_collect_sample_post_state() -> anyhow::Result<()> {
+    let sender = Address::from(hex!("2c7536e3605d9c16a7a3d7b1898e529396a65c23"));
+    let to = Address::from(hex!("a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0"));
+    let inputs = sample_transfer_inputs();
+    let signed_txn = SignedTxn {
+        sender,
+        bytes: inputs
+            .signed_txn
+            .clone()
+            .expect("the sample has a transaction"),
+        gas_used: inputs.gas_used_after,
+    };
+    let state_after = apply_transfer_txn(
+        &inputs.tries.state_trie,
+        &signed_txn,
+        &inputs.block_metadata,
+    )?;
+    assert_eq!(state_after.hash(), inputs.trie_roots_after.state_root);
+
+    // ガス価格はベースフィーと同じなので、beneficiaryのアカウントは作られない
+    let accounts = collect_accounts(&state_after)?;
+    assert_eq!(accounts.len(), 2);
+    let sender_account = accounts[&keccak(sender)];
+    assert_eq!(sender_account.nonce, 6.into());
+    assert_eq!(
+        sender_account.balance,
+        eth_to_wei(100_000.into()) - 100 - 21032 * 10
+    );
+    assert_eq!(accounts[&keccak(to)].balance, U256::from(100));
+
+    // ハッシュノードの下のアカウントは読めないので、黙って飛ばさずにエラーにする
+    let pruned = prune_to_touched(&state_after, &[sender]);
+    assert!(matches!(
+        collect_accounts(&pruned),
+        Err(ProveError::HashedSubtrie(_))
+    ));
+    Ok(())
+}
+
+/// Test that collecting the accounts of a trie with a hashed subtrie reports its prefix.
+#[test]
+fn test_collect_accounts_with_hashed_subtrie() -> anyhow::Result<()> {
+    let mut builder = StateTrieBuilder::new();
+    builder.insert(
+        SAMPLE_SENDER,
+        AccountRlp {
+            nonce: 5.into(),
+            ..AccountRlp::default()
+        },
+    );
+    // 送信者のステートキー(0x2f..)と重ならない位置に、中身の分からない部分木を置く
+    let prefix = Nibbles::from_str("0x7").unwrap();
+    builder.insert_hash(prefix, keccak("untouched accounts"));
+    let trie = builder.build();
+
+    match collect_accounts(&trie) {
+        Err(ProveError::HashedSubtrie(got)) => assert_eq!(got, prefix),
+        other => panic!("expected a hashed subtrie error, got {other:?}"),
+    }
+    Ok(())
+}
@@ -0,0 +1,68 @@
+use eth_trie_utils::partial_trie::{HashedPartialTrie, PartialTrie};
+use ethereum_types::Address;
+use hex_literal::hex;
+use keccak_hash::keccak;
+use plonky2_evm::generation::mpt::AccountRlp;
+use plonky2_evm::Node;
+use plonky2_sample::{
+    eth_to_wei, prove_transfer, secure_config, BlockMetadataBuilder, ConfigPreset, HashConfig,
+    SAMPLE_TXN,
+};
+
+/// Test that each preset's FRI parameters reach the security it claims.
+#[test]
+fn test_config_presets_security() {
+    for preset in [
+        ConfigPreset::Testing,
+        ConfigPreset::Fast,
+        ConfigPreset::Secure,
+    ] {
+        let config = preset.stark_config();
+        let fri = &config.fri_config;
+        // 推測セキュリティ = rate_bits * num_query_rounds + proof_of_work_bits
+        let conjectured_bits =
+            fri.rate_bits * fri.num_query_rounds + fri.proof_of_work_bits as usize;
+        assert!(
+            conjectured_bits >= config.security_bits,
+            "{preset:?} has {conjectured_bits} bits, less than {}",
+            config.security_bits
+        );
+    }
+    assert_eq!(secure_config().security_bits, 128);
+}
+
+/// Test proving and verifying the sample transfer under `secure_config`.
+#[test]
+fn test_prove_transfer_with_secure_config() -> anyhow::Result<()> {
+    let sender = Address::from(hex!("2c7536e3605d9c16a7a3d7b1898e529396a65c23"));
+    let to = Address::from(hex!("a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0"));
+    let sender_account_before = AccountRlp {
+        nonce: 5.into(),
+        balance: eth_to_wei(100_000.into()),
+        storage_root: HashedPartialTrie::from(Node::Empty).hash(),
+        code_hash: keccak([]),
+    };
+    let txn = SAMPLE_TXN;
+    let block_metadata = BlockMetadataBuilder::new()
+        .beneficiary(Address::from(hex!(
+            "deadbeefdeadbeefdeadbeefdeadbeefdeadbeef"
+        )))
+        .gas_used(21032.into())
+        .build()?;
+
+    let proof = prove_transfer(
+        sender,
+        to,
+        100.into(),
+        sender_account_before,
+        &txn,
+        block_metadata,
+        HashConfig::Keccak,
+        ConfigPreset::Secure,
+    )?;
+    assert_eq!(proof.preset, ConfigPreset::Secure);
+
+    // 証明したときと同じ設定で検証される
+    proof.verify()?;
+    Ok(())
+}
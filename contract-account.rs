@@ -0,0 +1,38 @@
+use eth_trie_utils::partial_trie::PartialTrie;
+use ethereum_types::{H256, U256};
+use hex_literal::hex;
+use keccak_hash::keccak;
+use plonky2_sample::{contract_account, eth_to_wei, ProveError, StorageTrieBuilder};
+
+/// Test that the account, code and storage trie of a contract agree with each other.
+#[test]
+fn test_contract_account() -> anyhow::Result<()> {
+    // PUSH1 0x2a PUSH1 0x00 SSTORE STOP
+    let code = hex!("602a60005500").to_vec();
+    let storage = [
+        (H256::zero(), U256::from(0x2a)),
+        (H256::from_low_u64_be(1), U256::from(7)),
+    ];
+    let (account, account_code, storage_trie) =
+        contract_account(code.clone(), &storage, eth_to_wei(1.into()), 1.into())?;
+
+    assert_eq!(account.code_hash, keccak(&code));
+    assert_eq!(account_code, code);
+    assert_eq!(account.balance, eth_to_wei(1.into()));
+    assert_eq!(account.nonce, 1.into());
+
+    let (expected_trie, expected_root) = StorageTrieBuilder::with_slots(storage)?.build();
+    assert_eq!(account.storage_root, expected_root);
+    assert_eq!(storage_trie.hash(), expected_trie.hash());
+    Ok(())
+}
+
+/// Test that a contract with the same slot set twice is rejected.
+#[test]
+fn test_contract_account_duplicate_slot() {
+    let storage = [(H256::zero(), U256::from(1)), (H256::zero(), U256::from(2))];
+    assert!(matches!(
+        contract_account(vec![0x00], &storage, U256::zero(), 1.into()),
+        Err(ProveError::InvalidInputs(_))
+    ));
+}
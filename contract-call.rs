@@ -0,0 +1,99 @@
+use eth_trie_utils::partial_trie::PartialTrie;
+use ethereum_types::{Address, H256, U256};
+use hex_literal::hex;
+use plonky2_evm::generation::mpt::AccountRlp;
+use plonky2_sample::{
+    call_contract, eth_to_wei, get_account, prove_inputs, BlockMetadataBuilder, HashConfig,
+    ProveError, SignedTxn, StorageTrieBuilder, WorldState,
+};
+
+// PUSH1 0x00 CALLDATALOAD PUSH1 0x00 SSTORE STOP: calldataの先頭32バイトをスロット0に書き込む
+const CODE: [u8; 7] = hex!("60003560005500");
+// 0xc0..c0 のコントラクトを、32バイトの0x2aを渡して呼ぶ
+// (nonce 5, gas_price 10, gas_limit 50000, value 0)
+const TXN: [u8; 129] = hex!("f87f050a82c35094c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c080a0000000000000000000000000000000000000000000000000000000000000002a1ba0b0769c0c90d230382b6e5af02e10379218a614ea7c507243825c702102a3f5ada06b8020ed5ec1289dc3d1f4ad4b6c32372dae8af5fb3dfc1800087c901f966d96");
+// 21000 + calldata(0が31バイトで4 * 31、0x2aで16) = 21140 に、
+// PUSH1, CALLDATALOAD, PUSH1 の3 * 3と、コールドなスロットを0から書き換えるSSTOREの22100を足す
+const GAS_USED: u64 = 21140 + 9 + 22100;
+
+/// Returns a world with the sender of `TXN`, and `code` at its receiver.
+fn world_before(code: &[u8]) -> WorldState {
+    let sender = Address::from(hex!("2c7536e3605d9c16a7a3d7b1898e529396a65c23"));
+    let contract = Address::from(hex!("c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0"));
+    let mut world = WorldState::new();
+    world.set_account(
+        sender,
+        AccountRlp {
+            nonce: 5.into(),
+            balance: eth_to_wei(100_000.into()),
+            ..AccountRlp::default()
+        },
+    );
+    world.set_code(contract, code.to_vec());
+    world
+}
+
+/// Test proving a call that stores its calldata in the contract's storage.
+#[test]
+fn test_call_sstore_contract() -> anyhow::Result<()> {
+    let sender = Address::from(hex!("2c7536e3605d9c16a7a3d7b1898e529396a65c23"));
+    let contract = Address::from(hex!("c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0"));
+    let world = world_before(&CODE);
+    let signed_txn = SignedTxn {
+        sender,
+        bytes: TXN.to_vec(),
+        gas_used: GAS_USED.into(),
+    };
+    let block_metadata = BlockMetadataBuilder::new()
+        .beneficiary(Address::from(hex!(
+            "deadbeefdeadbeefdeadbeefdeadbeefdeadbeef"
+        )))
+        .gas_used(GAS_USED.into())
+        .build()?;
+
+    let (inputs, world_after) = call_contract(
+        &world,
+        &signed_txn,
+        &[(H256::zero(), 0x2a.into())],
+        block_metadata,
+    )?;
+    assert_eq!(inputs.contract_code.len(), 2);
+
+    // スロット0に0x2aが入り、コントラクトのstorage_rootが変わる
+    let (_, storage_root) = StorageTrieBuilder::with_slots([(H256::zero(), 0x2a.into())])?.build();
+    let state_after = world_after.state_trie();
+    let contract_after = get_account(&state_after, contract)?.expect("the contract exists");
+    assert_eq!(contract_after.storage_root, storage_root);
+    assert_eq!(
+        get_account(&state_after, sender)?
+            .expect("the sender exists")
+            .balance,
+        eth_to_wei(100_000.into()) - U256::from(GAS_USED) * 10
+    );
+    assert_eq!(inputs.trie_roots_after.state_root, state_after.hash());
+
+    let proof = prove_inputs(inputs, HashConfig::Keccak)?;
+    assert_eq!(proof.summary().state_root, state_after.hash());
+    proof.verify()
+}
+
+/// Test that calling an address without code is rejected.
+#[test]
+fn test_call_account_without_code() -> anyhow::Result<()> {
+    let sender = Address::from(hex!("2c7536e3605d9c16a7a3d7b1898e529396a65c23"));
+    // 受信者のアカウントはあるが、コードが空
+    let world = world_before(&[]);
+    let signed_txn = SignedTxn {
+        sender,
+        bytes: TXN.to_vec(),
+        gas_used: GAS_USED.into(),
+    };
+    let block_metadata = BlockMetadataBuilder::new()
+        .gas_used(GAS_USED.into())
+        .build()?;
+    assert!(matches!(
+        call_contract(&world, &signed_txn, &[], block_metadata),
+        Err(ProveError::InvalidTransaction(_))
+    ));
+    Ok(())
+}
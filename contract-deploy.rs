@@ -0,0 +1,162 @@
+use eth_trie_utils::partial_trie::{HashedPartialTrie, PartialTrie};
+use ethereum_types::{Address, U256};
+use hex_literal::hex;
+use keccak_hash::keccak;
+use plonky2_evm::generation::mpt::AccountRlp;
+use plonky2_evm::Node;
+use plonky2_sample::{
+    contract_address, deploy_contract, eth_to_wei, prove_inputs, BlockMetadataBuilder, HashConfig,
+    ProveError, StateTrieBuilder,
+};
+
+// イニットコード 600060005360016000f3 は
+// PUSH1 0, PUSH1 0, MSTORE8, PUSH1 1, PUSH1 0, RETURN でメモリの1バイト(0x00)を返す
+// nonce 5, gas_price 10, gas_limit 100000, to 空, value 0
+const DEPLOY_TXN: [u8; 88] = hex!("f856050a830186a080808a600060005360016000f31ca06832dc56cc1f67f69fc84240ad2abe967101ac47d8ed92be89369a4c48567f19a07094990f647c05e3227771146786dffe1a28dec01cc4369e2b96ba124c682818");
+// 21000 + 32000(作成) + 124(calldata) + 2(イニットコードのワード) + 18(実行) + 200(コードの保存)
+const DEPLOY_GAS_USED: u64 = 53344;
+
+/// Test deploying a contract whose code is a single STOP opcode.
+#[test]
+fn test_deploy_stop_contract() -> anyhow::Result<()> {
+    let deployer = Address::from(hex!("2c7536e3605d9c16a7a3d7b1898e529396a65c23"));
+    let deployer_account_before = AccountRlp {
+        nonce: 5.into(),
+        balance: eth_to_wei(100_000.into()),
+        storage_root: HashedPartialTrie::from(Node::Empty).hash(),
+        code_hash: keccak([]),
+    };
+
+    // デプロイされるコードはSTOP(0x00)だけ
+    let code = vec![0x00];
+    let txn = DEPLOY_TXN;
+    let gas_used = DEPLOY_GAS_USED.into();
+
+    let block_metadata = BlockMetadataBuilder::new()
+        .beneficiary(Address::from(hex!(
+            "deadbeefdeadbeefdeadbeefdeadbeefdeadbeef"
+        )))
+        .gas_used(gas_used)
+        .build()?;
+    let inputs = deploy_contract(
+        code.clone(),
+        deployer,
+        deployer_account_before,
+        &txn,
+        gas_used,
+        block_metadata,
+    )?;
+    assert_eq!(inputs.contract_code.get(&keccak(&code)), Some(&code));
+
+    let proof = prove_inputs(inputs, HashConfig::Keccak)?;
+
+    // 証明された後のステートで、コントラクトがSTOPのコードハッシュを持っている
+    let contract = contract_address(deployer, 5.into());
+    assert_eq!(
+        contract,
+        Address::from(hex!("e9f5929393175ab32f14ea167ddec1ca1fbe738f"))
+    );
+    let expected_state_trie_after = {
+        let mut state_after = StateTrieBuilder::new();
+        state_after.insert(
+            deployer,
+            AccountRlp {
+                nonce: 6.into(),
+                balance: deployer_account_before.balance - 53344 * 10,
+                ..deployer_account_before
+            },
+        );
+        state_after.insert(
+            contract,
+            AccountRlp {
+                nonce: 1.into(),
+                code_hash: keccak([0x00]),
+                ..AccountRlp::default()
+            },
+        );
+        state_after.build()
+    };
+    assert_eq!(proof.summary().state_root, expected_state_trie_after.hash());
+
+    proof.verify()
+}
+
+/// Test that a deployment paying above the base fee credits the tip to the beneficiary, like a
+/// transfer, and that one paying below it is rejected.
+#[test]
+fn test_deploy_with_priority_fee() -> anyhow::Result<()> {
+    let deployer = Address::from(hex!("2c7536e3605d9c16a7a3d7b1898e529396a65c23"));
+    let beneficiary = Address::from(hex!("deadbeefdeadbeefdeadbeefdeadbeefdeadbeef"));
+    let deployer_account_before = AccountRlp {
+        nonce: 5.into(),
+        balance: eth_to_wei(100_000.into()),
+        ..AccountRlp::default()
+    };
+    let gas_used = U256::from(DEPLOY_GAS_USED);
+
+    // ガス価格10のうち、ベースフィー7を超える3がbeneficiaryに入る
+    let block_metadata = BlockMetadataBuilder::new()
+        .beneficiary(beneficiary)
+        .base_fee(7.into())
+        .gas_used(gas_used)
+        .build()?;
+    let inputs = deploy_contract(
+        vec![0x00],
+        deployer,
+        deployer_account_before,
+        &DEPLOY_TXN,
+        gas_used,
+        block_metadata,
+    )?;
+    let expected_state_trie_after = {
+        let mut state_after = StateTrieBuilder::new();
+        state_after.insert(
+            deployer,
+            AccountRlp {
+                nonce: 6.into(),
+                balance: deployer_account_before.balance - gas_used * 10,
+                ..deployer_account_before
+            },
+        );
+        state_after.insert(
+            contract_address(deployer, 5.into()),
+            AccountRlp {
+                nonce: 1.into(),
+                code_hash: keccak([0x00]),
+                ..AccountRlp::default()
+            },
+        );
+        state_after.insert(
+            beneficiary,
+            AccountRlp {
+                balance: gas_used * 3,
+                ..AccountRlp::default()
+            },
+        );
+        state_after.build()
+    };
+    assert_eq!(
+        inputs.trie_roots_after.state_root,
+        expected_state_trie_after.hash()
+    );
+    let proof = prove_inputs(inputs, HashConfig::Keccak)?;
+    assert_eq!(proof.summary().state_root, expected_state_trie_after.hash());
+    proof.verify()?;
+
+    let block_metadata = BlockMetadataBuilder::new()
+        .base_fee(11.into())
+        .gas_used(gas_used)
+        .build()?;
+    assert!(matches!(
+        deploy_contract(
+            vec![0x00],
+            deployer,
+            deployer_account_before,
+            &DEPLOY_TXN,
+            gas_used,
+            block_metadata,
+        ),
+        Err(ProveError::InvalidTransaction(_))
+    ));
+    Ok(())
+}
@@ -0,0 +1,105 @@
+use eth_trie_utils::partial_trie::PartialTrie;
+use ethereum_types::{Address, H256};
+use hex_literal::hex;
+use keccak_hash::keccak;
+use plonky2_evm::generation::mpt::AccountRlp;
+use plonky2_sample::{
+    eth_to_wei, prove_inputs, prove_transfer, BlockBuilder, BlockMetadataBuilder, ConfigPreset,
+    HashConfig, ProveError, SignedTxn, WorldState, SAMPLE_TXN,
+};
+
+// サンプルと同じ100weiの送金(nonce 5, gas_price 10, 21032 gas)
+const TXN: [u8; 99] = SAMPLE_TXN;
+
+/// Returns a world where the sender of `TXN` has code and one storage slot.
+fn contract_sender_world(sender: Address, code: &[u8]) -> WorldState {
+    let mut world = WorldState::new();
+    world.set_account(
+        sender,
+        AccountRlp {
+            nonce: 5.into(),
+            balance: eth_to_wei(100_000.into()),
+            ..AccountRlp::default()
+        },
+    );
+    world.set_code(sender, code.to_vec());
+    world.set_storage(sender, H256::zero(), 0x2a.into());
+    world
+}
+
+/// Test that a contract sending value keeps its code and storage.
+#[test]
+fn test_transfer_from_contract() -> anyhow::Result<()> {
+    let sender = Address::from(hex!("2c7536e3605d9c16a7a3d7b1898e529396a65c23"));
+    let to = Address::from(hex!("a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0"));
+    // PUSH1 0x2a PUSH1 0x00 SSTORE STOP
+    let code = hex!("602a60005500");
+    let world_before = contract_sender_world(sender, &code);
+    let sender_account_before = world_before.account(sender).expect("the sender exists");
+    assert_eq!(sender_account_before.code_hash, keccak(code));
+
+    let block_metadata = BlockMetadataBuilder::new()
+        .beneficiary(Address::from(hex!(
+            "deadbeefdeadbeefdeadbeefdeadbeefdeadbeef"
+        )))
+        .gas_used(21032.into())
+        .build()?;
+    let signed_txn = SignedTxn {
+        sender,
+        bytes: TXN.to_vec(),
+        gas_used: 21032.into(),
+    };
+    let mut world_after = world_before.clone();
+    world_after.apply_transfer(&signed_txn, &block_metadata)?;
+    let sender_account_after = world_after.account(sender).expect("the sender exists");
+    assert_eq!(sender_account_after.nonce, 6.into());
+    assert_eq!(
+        sender_account_after.code_hash,
+        sender_account_before.code_hash
+    );
+    assert_eq!(
+        sender_account_after.storage_root,
+        sender_account_before.storage_root
+    );
+    assert_eq!(
+        world_after
+            .account(to)
+            .expect("the receiver exists")
+            .balance,
+        100.into()
+    );
+
+    // ストレージトライとコードも入力に含める
+    let mut builder = BlockBuilder::new(world_before.to_trie_inputs(), block_metadata);
+    builder.with_contract_code(code.to_vec());
+    builder.push_txn(signed_txn);
+    let inputs = builder.build()?.remove(0);
+    assert_eq!(inputs.contract_code[&keccak(code)], code.to_vec());
+
+    let proof = prove_inputs(inputs, HashConfig::Keccak)?;
+    assert_eq!(proof.summary().state_root, world_after.state_trie().hash());
+    proof.verify()
+}
+
+/// Test that a sender with storage but without its storage trie is rejected before proving.
+#[test]
+fn test_transfer_from_contract_without_storage_trie() -> anyhow::Result<()> {
+    let sender = Address::from(hex!("2c7536e3605d9c16a7a3d7b1898e529396a65c23"));
+    let to = Address::from(hex!("a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0"));
+    let world = contract_sender_world(sender, &hex!("602a60005500"));
+    let sender_account = world.account(sender).expect("the sender exists");
+
+    // prove_transferはステートトライしか作らないので、送信者のストレージが分からない
+    let result = prove_transfer(
+        sender,
+        to,
+        100.into(),
+        sender_account,
+        &TXN,
+        BlockMetadataBuilder::new().gas_used(21032.into()).build()?,
+        HashConfig::Keccak,
+        ConfigPreset::Fast,
+    );
+    assert!(matches!(result, Err(ProveError::InvalidInputs(_))));
+    Ok(())
+}
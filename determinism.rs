@@ -0,0 +1,14 @@
+#![cfg(feature = "test-utils")]
+
+use plonky2_sample::sample_transfer_inputs;
+use plonky2_sample::test_utils::assert_deterministic_public_values;
+
+/// Test that proving the sample transfer twice commits to the same public values.
+#[test]
+fn test_sample_transfer_is_deterministic() -> anyhow::Result<()> {
+    let inputs = sample_transfer_inputs();
+    let summary = assert_deterministic_public_values(inputs.clone())?;
+    assert_eq!(summary.state_root, inputs.trie_roots_after.state_root);
+    assert_eq!(summary.gas_used, inputs.gas_used_after);
+    Ok(())
+}
@@ -0,0 +1,36 @@
+use ethereum_types::U256;
+use plonky2_sample::{
+    assert_roots, dry_run, prove_inputs, sample_transfer_inputs, HashConfig, ProveError,
+};
+
+/// Test that the roots of a dry run of the sample transfer are those of its proof.
+#[test]
+fn test_dry_run() -> anyhow::Result<()> {
+    let inputs = sample_transfer_inputs();
+
+    // 証明の前に、シナリオの整合性を確かめる
+    let roots = dry_run(&inputs)?;
+    assert_eq!(roots, inputs.trie_roots_after);
+
+    let summary = prove_inputs(inputs, HashConfig::Keccak)?.summary();
+    assert_roots(&summary, roots)?;
+    Ok(())
+}
+
+/// Test that a dry run of inconsistent inputs reports them rather than panicking.
+#[test]
+fn test_dry_run_inconsistent_inputs() {
+    let mut inputs = sample_transfer_inputs();
+    inputs.gas_used_before = inputs.gas_used_after + 1;
+    assert!(matches!(
+        dry_run(&inputs),
+        Err(ProveError::InvalidInputs(_))
+    ));
+
+    let mut inputs = sample_transfer_inputs();
+    inputs.txn_number_before = U256::MAX;
+    assert!(matches!(
+        dry_run(&inputs),
+        Err(ProveError::InvalidInputs(_))
+    ));
+}
@@ -0,0 +1,47 @@
+use eth_trie_utils::partial_trie::HashedPartialTrie;
+use ethereum_types::{Address, H256, U256};
+use hex_literal::hex;
+use plonky2_evm::generation::mpt::AccountRlp;
+use plonky2_evm::Node;
+use plonky2_sample::{apply_simple_transfer, dump_trie, eth_to_wei, StateTrieBuilder};
+
+/// Test dumping the state trie after the sample transfer.
+#[test]
+fn test_dump_trie() -> anyhow::Result<()> {
+    let sender = Address::from(hex!("2c7536e3605d9c16a7a3d7b1898e529396a65c23"));
+    let to = Address::from(hex!("a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0"));
+    let mut state_before = StateTrieBuilder::new();
+    state_before.insert(
+        sender,
+        AccountRlp {
+            nonce: 5.into(),
+            balance: eth_to_wei(100_000.into()),
+            ..AccountRlp::default()
+        },
+    );
+    let state_after = apply_simple_transfer(
+        &state_before.build(),
+        sender,
+        to,
+        U256::from(100u32),
+        21032.into(),
+        10.into(),
+    )?;
+
+    let dump = dump_trie(&state_after);
+    // 2つのステートキーは先頭のニブル(2とc)が違うので、ルートはブランチになる
+    assert!(dump.starts_with("Branch"));
+    // keccak(sender) と keccak(to)
+    assert!(dump.contains("2f93d0dfb1562c03c825a33eec4438e468c17fff649ae844c004065985ae2945"));
+    assert!(dump.contains("cea3717b23a29f65b207f6582dfe5ae3a6edfbc8187412789b115d6448464982"));
+    assert!(dump.contains("nonce=6"));
+    assert!(dump.contains("balance=100"));
+    Ok(())
+}
+
+/// Test dumping a partial trie that is only known by its hash.
+#[test]
+fn test_dump_hash_node() {
+    let trie = HashedPartialTrie::from(Node::Hash(H256::repeat_byte(0x42)));
+    assert!(dump_trie(&trie).starts_with("Hash 0x4242"));
+}
@@ -0,0 +1,122 @@
+use eth_trie_utils::partial_trie::{HashedPartialTrie, PartialTrie};
+use ethereum_types::{Address, H256, U256};
+use hex_literal::hex;
+use keccak_hash::keccak;
+use plonky2_evm::generation::mpt::AccountRlp;
+use plonky2_evm::Node;
+use plonky2_sample::{
+    eth_to_wei, prove_transfer, BlockMetadataBuilder, ConfigPreset, Eip1559Txn, HashConfig,
+    StateTrieBuilder, Txn,
+};
+
+// nonce 5, max_priority_fee_per_gas 2, max_fee_per_gas 20, gas_limit 21000, to 0xa0..a0, value 100
+const TXN: [u8; 101] = hex!("02f8620105021482520894a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a06480c080a0dcd94a10b7e0bdcdbe47d6cb91dcba8b0c6edc2e80bc1272973b979edbd64a9da027383b59211cf3eef7abc9caea416137cf8bb487e0549c13d7015c32136a7ab3");
+
+/// Test decoding and re-encoding EIP-1559 transactions with and without an access list.
+#[test]
+fn test_eip1559_round_trip() -> anyhow::Result<()> {
+    let txn = Eip1559Txn::decode(&TXN)?;
+    assert_eq!(txn.chain_id, 1.into());
+    assert_eq!(txn.nonce, 5.into());
+    assert_eq!(txn.max_priority_fee_per_gas, 2.into());
+    assert_eq!(txn.max_fee_per_gas, 20.into());
+    assert_eq!(
+        txn.to,
+        Some(Address::from(hex!(
+            "a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0"
+        )))
+    );
+    // 空のアクセスリストは0xc0
+    assert!(txn.access_list.is_empty());
+    assert_eq!(txn.encode(), TXN);
+
+    // アドレス1つとスロット1つのアクセスリスト(gas_limit 30000)
+    let with_access_list = hex!("02f89b0105021482753094a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a06480f838f794a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0e1a0000000000000000000000000000000000000000000000000000000000000000001a05f73a62d0489a2dbba9b82780b38ec112eb0dfb8fced23b52e04489adaa9a1f1a063d795071ad3dfff7df5eca63f69562ec015af355be40888d91e9044ce7afa44");
+    let txn = Txn::decode(&with_access_list)?;
+    let Txn::Eip1559(inner) = &txn else {
+        panic!("expected an EIP-1559 transaction");
+    };
+    assert_eq!(
+        inner.access_list,
+        vec![(
+            Address::from(hex!("a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0")),
+            vec![H256::zero()]
+        )]
+    );
+    assert_eq!(txn.encode(), with_access_list);
+
+    // ベースフィー10なら 10 + 2、ベースフィー19なら上限の20
+    assert_eq!(txn.effective_gas_price(10.into()), 12.into());
+    assert_eq!(txn.effective_gas_price(19.into()), 20.into());
+
+    // ベースフィーと優先手数料の和がオーバーフローしても上限で止まる
+    let huge_tip = Eip1559Txn {
+        max_priority_fee_per_gas: U256::MAX,
+        max_fee_per_gas: U256::MAX,
+        ..inner.clone()
+    };
+    assert_eq!(huge_tip.effective_gas_price(10.into()), U256::MAX);
+    Ok(())
+}
+
+/// Test proving an EIP-1559 transfer, whose priority fee goes to the beneficiary.
+#[test]
+fn test_eip1559_transfer() -> anyhow::Result<()> {
+    let sender = Address::from(hex!("2c7536e3605d9c16a7a3d7b1898e529396a65c23"));
+    let to = Address::from(hex!("a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0"));
+    let beneficiary = Address::from(hex!("deadbeefdeadbeefdeadbeefdeadbeefdeadbeef"));
+    let sender_account_before = AccountRlp {
+        nonce: 5.into(),
+        balance: eth_to_wei(100_000.into()),
+        storage_root: HashedPartialTrie::from(Node::Empty).hash(),
+        code_hash: keccak([]),
+    };
+
+    // ベースフィーは0xa
+    let block_metadata = BlockMetadataBuilder::new()
+        .beneficiary(beneficiary)
+        .gas_used(21000.into())
+        .build()?;
+    let proof = prove_transfer(
+        sender,
+        to,
+        100.into(),
+        sender_account_before,
+        &TXN,
+        block_metadata,
+        HashConfig::Keccak,
+        ConfigPreset::Fast,
+    )?;
+
+    // 実効ガス価格は min(20, 10 + 2) = 12 で、そのうち2がbeneficiaryに支払われる
+    let expected_state_trie_after = {
+        let mut state_after = StateTrieBuilder::new();
+        state_after.insert(
+            sender,
+            AccountRlp {
+                balance: sender_account_before.balance - 100 - 21000 * 12,
+                nonce: 6.into(),
+                ..sender_account_before
+            },
+        );
+        state_after.insert(
+            to,
+            AccountRlp {
+                balance: 100.into(),
+                ..AccountRlp::default()
+            },
+        );
+        state_after.insert(
+            beneficiary,
+            AccountRlp {
+                balance: U256::from(21000 * 2),
+                ..AccountRlp::default()
+            },
+        );
+        state_after.build()
+    };
+    assert_eq!(proof.summary().state_root, expected_state_trie_after.hash());
+
+    proof.verify()?;
+    Ok(())
+}
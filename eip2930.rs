@@ -0,0 +1,183 @@
+use eth_trie_utils::partial_trie::{HashedPartialTrie, PartialTrie};
+use ethereum_types::{Address, H256, U256};
+use hex_literal::hex;
+use plonky2_evm::generation::mpt::AccountRlp;
+use plonky2_evm::generation::TrieInputs;
+use plonky2_evm::Node;
+use plonky2_sample::{
+    access_list_gas, block_inputs, eth_to_wei, prove_transfer, BlockMetadataBuilder, ConfigPreset,
+    Eip2930Txn, HashConfig, ProveError, SignedTxn, StateTrieBuilder, Txn,
+};
+use rlp::RlpStream;
+
+// nonce 5, gas_price 10, gas_limit 30000, to 0xa0..a0, value 100,
+// アクセスリストは 0xa0..a0 のスロット0と1
+const TXN: [u8; 192] = hex!("01f8bd01050a82753094a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a06480f85bf85994a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0f842a00000000000000000000000000000000000000000000000000000000000000000a0000000000000000000000000000000000000000000000000000000000000000101a09d4a80b3b50c905949fb476810e9ae559edc58dccb56e789b4eb556a358c20c8a05a15e61b34552f486f07737c3fd201b881fba4f7d37d5f43888bcfebb82aa716");
+
+/// Test decoding and re-encoding an EIP-2930 transaction with two storage keys.
+#[test]
+fn test_eip2930_round_trip() -> anyhow::Result<()> {
+    let to = Address::from(hex!("a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0"));
+    let txn = Eip2930Txn::decode(&TXN)?;
+    assert_eq!(txn.chain_id, 1.into());
+    assert_eq!(txn.nonce, 5.into());
+    assert_eq!(txn.gas_price, 10.into());
+    assert_eq!(txn.gas_limit, 30000.into());
+    assert_eq!(txn.to, Some(to));
+    assert_eq!(txn.value, 100.into());
+    assert_eq!(
+        txn.access_list,
+        vec![(to, vec![H256::zero(), H256::from_low_u64_be(1)])]
+    );
+    assert_eq!(txn.encode(), TXN);
+
+    // Txnからもタイプ1として読める
+    let txn = Txn::decode(&TXN)?;
+    assert_eq!(txn.txn_type(), Some(Eip2930Txn::TYPE));
+    assert_eq!(txn.chain_id(), Some(1.into()));
+    assert_eq!(txn.access_list().len(), 1);
+    assert_eq!(txn.encode(), TXN);
+
+    // EIP-1559のバイト列はタイプ1としては読めない
+    assert!(Eip2930Txn::decode(&[&[0x02], &TXN[1..]].concat()).is_err());
+    Ok(())
+}
+
+/// Test that access lists whose entries aren't `[address, [slot, ..]]` are rejected.
+#[test]
+fn test_eip2930_malformed_access_list() -> anyhow::Result<()> {
+    let to = Address::from(hex!("a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0"));
+    let slot = H256::zero();
+    // TXNのアクセスリストだけを差し替えたトランザクション
+    let with_access_list = |entry: &dyn Fn(&mut RlpStream)| -> anyhow::Result<Vec<u8>> {
+        let txn = Eip2930Txn::decode(&TXN)?;
+        let mut stream = RlpStream::new_list(11);
+        stream
+            .append(&txn.chain_id)
+            .append(&txn.nonce)
+            .append(&txn.gas_price)
+            .append(&txn.gas_limit)
+            .append(&to)
+            .append(&txn.value)
+            .append(&txn.data);
+        stream.begin_list(1);
+        entry(&mut stream);
+        stream.append(&txn.y_parity).append(&txn.r).append(&txn.s);
+        Ok([&[Eip2930Txn::TYPE], stream.out().as_ref()].concat())
+    };
+
+    // 正しい形なら読める
+    let valid = with_access_list(&|stream| {
+        stream.begin_list(2).append(&to).begin_list(1).append(&slot);
+    })?;
+    assert_eq!(
+        Eip2930Txn::decode(&valid)?.access_list,
+        vec![(to, vec![slot])]
+    );
+
+    let malformed: [&dyn Fn(&mut RlpStream); 5] = [
+        // エントリーがリストでない
+        &|stream| {
+            stream.append(&to);
+        },
+        // 余分な要素がある
+        &|stream| {
+            stream.begin_list(3).append(&to).begin_list(0).append(&slot);
+        },
+        // アドレスが19バイト
+        &|stream| {
+            stream.begin_list(2).append(&to[1..].to_vec()).begin_list(0);
+        },
+        // スロットがリストでない
+        &|stream| {
+            stream.begin_list(2).append(&to).append(&slot);
+        },
+        // スロットが31バイト
+        &|stream| {
+            stream
+                .begin_list(2)
+                .append(&to)
+                .begin_list(1)
+                .append(&slot[1..].to_vec());
+        },
+    ];
+    for entry in malformed {
+        assert!(matches!(
+            Eip2930Txn::decode(&with_access_list(entry)?),
+            Err(ProveError::InvalidTransaction(_))
+        ));
+    }
+    Ok(())
+}
+
+/// Test proving an EIP-2930 transfer, which pays upfront for the addresses and slots it warms.
+#[test]
+fn test_eip2930_transfer() -> anyhow::Result<()> {
+    let sender = Address::from(hex!("2c7536e3605d9c16a7a3d7b1898e529396a65c23"));
+    let to = Address::from(hex!("a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0"));
+    let sender_account_before = AccountRlp {
+        nonce: 5.into(),
+        balance: eth_to_wei(100_000.into()),
+        ..AccountRlp::default()
+    };
+
+    // 21000 + アドレス1つで2400 + スロット2つで1900 * 2
+    let txn = Eip2930Txn::decode(&TXN)?;
+    let gas_used = U256::from(21000) + access_list_gas(&txn.access_list);
+    assert_eq!(gas_used, 27200.into());
+
+    // アクセスリストのアドレスはGenerationInputsのaddressesに入る
+    let mut state_before = StateTrieBuilder::new();
+    state_before.insert(sender, sender_account_before);
+    let tries_before = TrieInputs {
+        state_trie: state_before.build(),
+        transactions_trie: HashedPartialTrie::from(Node::Empty),
+        receipts_trie: HashedPartialTrie::from(Node::Empty),
+        storage_tries: vec![],
+    };
+    let block_metadata = BlockMetadataBuilder::new().gas_used(gas_used).build()?;
+    let signed_txn = SignedTxn {
+        sender,
+        bytes: TXN.to_vec(),
+        gas_used,
+    };
+    let inputs = block_inputs(vec![signed_txn], tries_before, block_metadata.clone())?;
+    assert_eq!(inputs[0].addresses, vec![to]);
+
+    // ガス価格はベースフィーと同じ10なので、beneficiaryには何も支払われない
+    let proof = prove_transfer(
+        sender,
+        to,
+        100.into(),
+        sender_account_before,
+        &TXN,
+        block_metadata,
+        HashConfig::Keccak,
+        ConfigPreset::Fast,
+    )?;
+    assert_eq!(proof.summary().gas_used, gas_used);
+
+    let expected_state_trie_after = {
+        let mut state_after = StateTrieBuilder::new();
+        state_after.insert(
+            sender,
+            AccountRlp {
+                balance: sender_account_before.balance - 100 - 27200 * 10,
+                nonce: 6.into(),
+                ..sender_account_before
+            },
+        );
+        state_after.insert(
+            to,
+            AccountRlp {
+                balance: 100.into(),
+                ..AccountRlp::default()
+            },
+        );
+        state_after.build()
+    };
+    assert_eq!(proof.summary().state_root, expected_state_trie_after.hash());
+
+    proof.verify()?;
+    Ok(())
+}
@@ -0,0 +1,64 @@
+use eth_trie_utils::partial_trie::{HashedPartialTrie, PartialTrie};
+use ethereum_types::Address;
+use hex_literal::hex;
+use plonky2_evm::generation::mpt::AccountRlp;
+use plonky2_evm::generation::TrieInputs;
+use plonky2_evm::Node;
+use plonky2_sample::{
+    eth_to_wei, prove_empty_block, BlockMetadataBuilder, ProveError, StateTrieBuilder,
+};
+
+fn sample_tries() -> TrieInputs {
+    let mut state = StateTrieBuilder::new();
+    state.insert(
+        Address::from(hex!("2c7536e3605d9c16a7a3d7b1898e529396a65c23")),
+        AccountRlp {
+            nonce: 5.into(),
+            balance: eth_to_wei(100_000.into()),
+            ..AccountRlp::default()
+        },
+    );
+    TrieInputs {
+        state_trie: state.build(),
+        transactions_trie: HashedPartialTrie::from(Node::Empty),
+        receipts_trie: HashedPartialTrie::from(Node::Empty),
+        storage_tries: vec![],
+    }
+}
+
+/// Test proving a block without transactions nor withdrawals.
+#[test]
+fn test_prove_empty_block() -> anyhow::Result<()> {
+    let tries = sample_tries();
+    let state_root = tries.state_trie.hash();
+    let block_metadata = BlockMetadataBuilder::new()
+        .beneficiary(Address::from(hex!(
+            "deadbeefdeadbeefdeadbeefdeadbeefdeadbeef"
+        )))
+        .build()?;
+
+    let summary = prove_empty_block(tries, block_metadata)?;
+    // 何も実行されないので、ルートはどれも変わらない
+    assert_eq!(summary.state_root, state_root);
+    assert_eq!(
+        summary.transactions_root,
+        HashedPartialTrie::from(Node::Empty).hash()
+    );
+    assert_eq!(
+        summary.receipts_root,
+        HashedPartialTrie::from(Node::Empty).hash()
+    );
+    assert_eq!(summary.gas_used, 0.into());
+    Ok(())
+}
+
+/// Test that an empty block claiming to use gas is rejected before proving.
+#[test]
+fn test_empty_block_with_gas() -> anyhow::Result<()> {
+    let block_metadata = BlockMetadataBuilder::new().gas_used(21000.into()).build()?;
+    assert!(matches!(
+        prove_empty_block(sample_tries(), block_metadata),
+        Err(ProveError::InvalidBlock(_))
+    ));
+    Ok(())
+}
@@ -0,0 +1,99 @@
+#![cfg(feature = "slow-tests")]
+
+use eth_trie_utils::partial_trie::{HashedPartialTrie, PartialTrie};
+use ethereum_types::Address;
+use hex_literal::hex;
+use keccak_hash::keccak;
+use plonky2::plonk::proof::ProofWithPublicInputs;
+use plonky2::util::timing::TimingTree;
+use plonky2_evm::all_stark::AllStark;
+use plonky2_evm::config::StarkConfig;
+use plonky2_evm::generation::mpt::AccountRlp;
+use plonky2_evm::generation::TrieInputs;
+use plonky2_evm::proof::PublicValues;
+use plonky2_evm::Node;
+use plonky2_sample::{
+    aggregate_proofs, block_inputs, eth_to_wei, load_proof, recursive_circuits, save_proof,
+    BlockMetadataBuilder, RecursiveConfig, SignedTxn, StateTrieBuilder, D, F, SAMPLE_TXN,
+};
+
+/// Test proving two transfers, saving and reloading their proofs, then aggregating and verifying
+/// them.
+#[test]
+fn test_prove_save_load_aggregate() -> anyhow::Result<()> {
+    let sender = Address::from(hex!("2c7536e3605d9c16a7a3d7b1898e529396a65c23"));
+    let mut state_before = StateTrieBuilder::new();
+    state_before.insert(
+        sender,
+        AccountRlp {
+            nonce: 5.into(),
+            balance: eth_to_wei(100_000.into()),
+            storage_root: HashedPartialTrie::from(Node::Empty).hash(),
+            code_hash: keccak([]),
+        },
+    );
+    let tries_before = TrieInputs {
+        state_trie: state_before.build(),
+        transactions_trie: HashedPartialTrie::from(Node::Empty),
+        receipts_trie: HashedPartialTrie::from(Node::Empty),
+        storage_tries: vec![],
+    };
+
+    // aggregation.rs と同じ2つの送金
+    let txn_0 = SAMPLE_TXN;
+    let txn_1 = hex!("f869060a82520894a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a08a152d02c7e14af67995bc801ba00213ef373c5fd6736d9dafd04d45482b038438e5cfd0e9a7cc366454b18bcd8da042c663ec738e79b82dddb2a3819df510a461383b67da01a2928dcbc294de34a2");
+    let block_metadata = BlockMetadataBuilder::new()
+        .beneficiary(Address::from(hex!(
+            "deadbeefdeadbeefdeadbeefdeadbeefdeadbeef"
+        )))
+        .gas_used((21032 + 21000).into())
+        .build()?;
+    let txns = vec![
+        SignedTxn {
+            sender,
+            bytes: txn_0.to_vec(),
+            gas_used: 21032.into(),
+        },
+        SignedTxn {
+            sender,
+            bytes: txn_1.to_vec(),
+            gas_used: 21000.into(),
+        },
+    ];
+    let inputs = block_inputs(txns, tries_before, block_metadata)?;
+
+    let all_stark = AllStark::<F, D>::default();
+    let config = StarkConfig::standard_fast_config();
+    let circuits = recursive_circuits(&all_stark, &config);
+    let dir = tempfile::tempdir()?;
+
+    // 1. 証明して 2. ファイルに保存する
+    let mut paths = vec![];
+    for (i, inputs) in inputs.into_iter().enumerate() {
+        let mut timing = TimingTree::new("prove root", log::Level::Debug);
+        let (proof, _public_values) =
+            circuits.prove_root(&all_stark, &config, inputs, &mut timing)?;
+        let path = dir.path().join(format!("proof_{i}.json"));
+        save_proof(&path, &proof)?;
+        paths.push(path);
+    }
+
+    // 3. 読み込み直す。公開値は証明の公開入力から復元する
+    let mut roots = vec![];
+    for path in &paths {
+        let proof: ProofWithPublicInputs<F, RecursiveConfig, D> = load_proof(path)?;
+        let public_values = PublicValues::from_public_inputs(&proof.public_inputs);
+        roots.push((proof, public_values));
+    }
+    let root_1 = roots.pop().unwrap();
+    let root_0 = roots.pop().unwrap();
+
+    // 4. 集約して 5. 検証する
+    let aggregated = aggregate_proofs(&circuits, root_0, root_1)?;
+    assert_eq!(
+        aggregated.public_values.extra_block_data.gas_used_after,
+        (21032 + 21000).into()
+    );
+    aggregated.verify(&circuits)?;
+    Ok(())
+}
@@ -0,0 +1,33 @@
+use eth_trie_utils::partial_trie::{HashedPartialTrie, PartialTrie};
+use ethereum_types::{Address, U256};
+use plonky2_evm::generation::mpt::AccountRlp;
+use plonky2_evm::Node;
+use plonky2_sample::{apply_simple_transfer, StateTrieBuilder};
+
+/// Test that the expected-state helper rejects transfers it can't apply.
+#[test]
+fn test_apply_simple_transfer_errors() {
+    let sender = Address::from_low_u64_be(1);
+    let to = Address::from_low_u64_be(2);
+
+    // 送信者がState Trieにいない
+    let empty = HashedPartialTrie::from(Node::Empty);
+    assert!(
+        apply_simple_transfer(&empty, sender, to, 100.into(), 21000.into(), 10.into()).is_err()
+    );
+
+    // 残高が value + gas_used * gas_price に足りない
+    let mut builder = StateTrieBuilder::new();
+    builder.insert(
+        sender,
+        AccountRlp {
+            balance: U256::from(21000 * 10 + 99),
+            ..AccountRlp::default()
+        },
+    );
+    let before = builder.build();
+    assert!(
+        apply_simple_transfer(&before, sender, to, 100.into(), 21000.into(), 10.into()).is_err()
+    );
+    assert!(apply_simple_transfer(&before, sender, to, 99.into(), 21000.into(), 10.into()).is_ok());
+}
@@ -0,0 +1,33 @@
+use plonky2::field::goldilocks_field::GoldilocksField;
+use plonky2::plonk::config::KeccakGoldilocksConfig;
+use plonky2_evm::all_stark::AllStark;
+use plonky2_evm::config::StarkConfig;
+use plonky2_sample::{
+    prove_generic, prove_inputs, sample_transfer_inputs, verify_generic, HashConfig,
+};
+
+/// Test proving the sample transfer with the extension degree given explicitly.
+#[test]
+fn test_prove_generic_degree_2() -> anyhow::Result<()> {
+    let inputs = sample_transfer_inputs();
+
+    // GoldilocksFieldの拡大次数は2, 4, 5があるが、plonky2の既存のconfigはD = 2のみ
+    const D: usize = 2;
+    let all_stark = AllStark::<GoldilocksField, D>::default();
+    let config = StarkConfig::standard_fast_config();
+    let proof = prove_generic::<GoldilocksField, KeccakGoldilocksConfig, D>(
+        &all_stark,
+        &config,
+        inputs.clone(),
+    )?;
+
+    // 既定の経路と同じ公開値になる
+    let expected = prove_inputs(inputs, HashConfig::Keccak)?;
+    assert_eq!(
+        proof.public_values.trie_roots_after,
+        expected.public_values.trie_roots_after
+    );
+
+    verify_generic(&all_stark, proof, &config)?;
+    Ok(())
+}
@@ -0,0 +1,68 @@
+use plonky2::fri::reduction_strategies::FriReductionStrategy;
+use plonky2::fri::FriConfig;
+use plonky2_sample::{fri_stark_config, prove_with_fri, sample_transfer_inputs, ProveError};
+
+/// Test proving and verifying the sample transfer with FRI parameters of no preset.
+#[test]
+fn test_prove_with_custom_fri() -> anyhow::Result<()> {
+    // アリティ8で畳み、ブローアップ4で20回クエリする: 2 * 20 + 10 = 50ビット
+    let fri = FriConfig {
+        rate_bits: 2,
+        cap_height: 3,
+        proof_of_work_bits: 10,
+        reduction_strategy: FriReductionStrategy::ConstantArityBits(3, 5),
+        num_query_rounds: 20,
+    };
+    assert_eq!(fri_stark_config(fri.clone())?.security_bits, 50);
+
+    let inputs = sample_transfer_inputs();
+    let summary = prove_with_fri(inputs.clone(), fri)?;
+    assert_eq!(summary.state_root, inputs.trie_roots_after.state_root);
+    assert_eq!(summary.gas_used, inputs.gas_used_after);
+    Ok(())
+}
+
+/// Test that inconsistent FRI parameters are rejected before proving.
+#[test]
+fn test_invalid_fri_config() {
+    let valid = FriConfig {
+        rate_bits: 1,
+        cap_height: 4,
+        proof_of_work_bits: 16,
+        reduction_strategy: FriReductionStrategy::ConstantArityBits(4, 5),
+        num_query_rounds: 12,
+    };
+    let invalid_configs = [
+        FriConfig {
+            rate_bits: 0,
+            ..valid.clone()
+        },
+        FriConfig {
+            num_query_rounds: 0,
+            ..valid.clone()
+        },
+        FriConfig {
+            proof_of_work_bits: 64,
+            ..valid.clone()
+        },
+        FriConfig {
+            reduction_strategy: FriReductionStrategy::ConstantArityBits(0, 5),
+            ..valid.clone()
+        },
+        FriConfig {
+            reduction_strategy: FriReductionStrategy::Fixed(vec![4, 0]),
+            ..valid.clone()
+        },
+    ];
+    for fri in invalid_configs {
+        assert!(matches!(
+            fri_stark_config(fri.clone()),
+            Err(ProveError::InvalidFriConfig(_))
+        ));
+        // 証明を始める前に失敗する
+        assert!(matches!(
+            prove_with_fri(sample_transfer_inputs(), fri),
+            Err(ProveError::InvalidFriConfig(_))
+        ));
+    }
+}
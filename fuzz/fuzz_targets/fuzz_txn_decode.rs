@@ -0,0 +1,10 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use plonky2_sample::{TransferTxn, Txn};
+
+// 不正なバイト列でもパニックせず、Errを返すことを確かめる
+fuzz_target!(|data: &[u8]| {
+    let _ = TransferTxn::decode(data);
+    let _ = Txn::decode(data);
+});
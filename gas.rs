@@ -0,0 +1,22 @@
+use plonky2_sample::{intrinsic_gas, total_gas_cost, TransferTxn, SAMPLE_TXN};
+
+/// Test the intrinsic gas of the sample transfer.
+#[test]
+fn test_intrinsic_gas() -> anyhow::Result<()> {
+    let txn = SAMPLE_TXN;
+    let mut txn = TransferTxn::decode(&txn)?;
+
+    // 21000 + 0x4242の2バイト * 16
+    assert_eq!(intrinsic_gas(&txn), 21032.into());
+    assert_eq!(
+        total_gas_cost(intrinsic_gas(&txn), txn.gas_price),
+        210320.into()
+    );
+
+    // ゼロバイトは4ガス
+    txn.data = vec![0x00, 0x42];
+    assert_eq!(intrinsic_gas(&txn), (21000 + 4 + 16).into());
+    txn.data = vec![];
+    assert_eq!(intrinsic_gas(&txn), 21000.into());
+    Ok(())
+}
@@ -0,0 +1,88 @@
+use std::collections::HashMap;
+
+use eth_trie_utils::partial_trie::{HashedPartialTrie, PartialTrie};
+use ethereum_types::{Address, H256};
+use hex_literal::hex;
+use plonky2_evm::generation::mpt::AccountRlp;
+use plonky2_evm::generation::GenerationInputs;
+use plonky2_evm::Node;
+use plonky2_sample::{
+    eth_to_wei, genesis_tries, prove_inputs, validate_inputs, BlockBuilder, BlockMetadataBuilder,
+    HashConfig, ProveError, SignedTxn, StateTrieBuilder, SAMPLE_TXN,
+};
+
+/// Test the tries built from a genesis allocation of two accounts.
+#[test]
+fn test_genesis_tries() {
+    let sender = Address::from(hex!("2c7536e3605d9c16a7a3d7b1898e529396a65c23"));
+    let to = Address::from(hex!("a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0"));
+    let sender_account = AccountRlp {
+        nonce: 5.into(),
+        balance: eth_to_wei(100_000.into()),
+        ..AccountRlp::default()
+    };
+    let to_account = AccountRlp {
+        balance: eth_to_wei(1.into()),
+        ..AccountRlp::default()
+    };
+
+    let alloc = HashMap::from([(sender, sender_account), (to, to_account)]);
+    let (tries, genesis_root) = genesis_tries(alloc);
+
+    let mut builder = StateTrieBuilder::new();
+    builder.insert(sender, sender_account);
+    builder.insert(to, to_account);
+    assert_eq!(genesis_root, builder.build().hash());
+    assert_eq!(tries.state_trie.hash(), genesis_root);
+
+    // ステート以外のトライは空
+    let empty_root = HashedPartialTrie::from(Node::Empty).hash();
+    assert_eq!(tries.transactions_trie.hash(), empty_root);
+    assert_eq!(tries.receipts_trie.hash(), empty_root);
+    assert!(tries.storage_tries.is_empty());
+}
+
+/// Test proving the sample transfer in block 1 of a chain with a non-empty genesis state.
+#[test]
+fn test_prove_with_genesis_state_root() -> anyhow::Result<()> {
+    let sender = Address::from(hex!("2c7536e3605d9c16a7a3d7b1898e529396a65c23"));
+    let alloc = HashMap::from([(
+        sender,
+        AccountRlp {
+            nonce: 5.into(),
+            balance: eth_to_wei(100_000.into()),
+            ..AccountRlp::default()
+        },
+    )]);
+    let (tries, genesis_root) = genesis_tries(alloc);
+    assert_ne!(genesis_root, HashedPartialTrie::from(Node::Empty).hash());
+
+    let txn = SAMPLE_TXN;
+    let block_metadata = BlockMetadataBuilder::new()
+        .number(1.into())
+        .gas_used(21032.into())
+        .build()?;
+    let mut builder = BlockBuilder::new(tries, block_metadata);
+    builder.push_txn(SignedTxn {
+        sender,
+        bytes: txn.to_vec(),
+        gas_used: 21032.into(),
+    });
+    builder.with_genesis_state_root(genesis_root);
+    let inputs = builder.build()?.remove(0);
+    assert_eq!(inputs.genesis_state_trie_root, genesis_root);
+
+    // ブロック1の前のステートがジェネシスと違えば、証明の前に弾かれる
+    let wrong_genesis = GenerationInputs {
+        genesis_state_trie_root: H256::repeat_byte(0x42),
+        ..inputs.clone()
+    };
+    assert!(matches!(
+        validate_inputs(&wrong_genesis),
+        Err(ProveError::InvalidInputs(_))
+    ));
+
+    let proof = prove_inputs(inputs, HashConfig::Keccak)?;
+    proof.verify()?;
+    Ok(())
+}
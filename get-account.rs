@@ -0,0 +1,50 @@
+use eth_trie_utils::partial_trie::PartialTrie;
+use ethereum_types::Address;
+use hex_literal::hex;
+use plonky2_evm::generation::mpt::AccountRlp;
+use plonky2_sample::{
+    address_to_state_key_nibbles, eth_to_wei, get_account, ProveError, StateTrieBuilder,
+};
+
+fn sample_state() -> (Address, AccountRlp, StateTrieBuilder) {
+    let sender = Address::from(hex!("2c7536e3605d9c16a7a3d7b1898e529396a65c23"));
+    let sender_account = AccountRlp {
+        nonce: 5.into(),
+        balance: eth_to_wei(100_000.into()),
+        ..AccountRlp::default()
+    };
+    let mut builder = StateTrieBuilder::new();
+    builder.insert(sender, sender_account);
+    (sender, sender_account, builder)
+}
+
+/// Test reading an account present in the state trie.
+#[test]
+fn test_get_account_present() -> anyhow::Result<()> {
+    let (sender, sender_account, builder) = sample_state();
+    assert_eq!(get_account(&builder.build(), sender)?, Some(sender_account));
+    Ok(())
+}
+
+/// Test reading an account absent from the state trie.
+#[test]
+fn test_get_account_absent() -> anyhow::Result<()> {
+    let (_, _, builder) = sample_state();
+    let to = Address::from(hex!("a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0"));
+    assert_eq!(get_account(&builder.build(), to)?, None);
+    Ok(())
+}
+
+/// Test that a leaf that isn't an RLP-encoded account is reported as an error.
+#[test]
+fn test_get_account_corrupt() {
+    let (_, _, builder) = sample_state();
+    let mut trie = builder.build();
+    let corrupt = Address::from(hex!("a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0"));
+    // アカウントではない値をリーフに入れる
+    trie.insert(address_to_state_key_nibbles(corrupt), vec![0x42]);
+    assert!(matches!(
+        get_account(&trie, corrupt),
+        Err(ProveError::Rlp(_))
+    ));
+}
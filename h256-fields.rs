@@ -0,0 +1,59 @@
+use ethereum_types::H256;
+use hex_literal::hex;
+use plonky2::field::types::Field;
+use plonky2_sample::{fields_to_h256, h256_to_fields, ProveError, F};
+
+/// Test that the limbs are the 32-bit words of the hash, least significant first.
+#[test]
+fn test_h256_to_fields_limb_order() {
+    let h = H256(hex!(
+        "0000000100000002000000030000000400000005000000060000000700000008"
+    ));
+    let limbs = h256_to_fields(h);
+    assert_eq!(
+        limbs,
+        [8u32, 7, 6, 5, 4, 3, 2, 1].map(F::from_canonical_u32)
+    );
+    assert_eq!(h256_to_fields(H256::zero()), [F::ZERO; 8]);
+}
+
+/// Test converting hashes to limbs and back.
+#[test]
+fn test_h256_fields_round_trip() -> anyhow::Result<()> {
+    for h in [
+        H256::zero(),
+        H256::repeat_byte(0xff),
+        H256::from_low_u64_be(0x0123_4567_89ab_cdef),
+        H256(hex!(
+            "8000000000000000000000000000000000000000000000000000000000000001"
+        )),
+    ] {
+        assert_eq!(fields_to_h256(h256_to_fields(h))?, h);
+    }
+    Ok(())
+}
+
+/// Test that the state root of the sample transfer survives the conversion.
+#[test]
+fn test_sample_state_root_round_trip() -> anyhow::Result<()> {
+    // snapshot.rs と同じ、サンプルの送金後のステートルート
+    let state_root = H256(hex!(
+        "52587c85ce3a0e4039fdf42e6f42107564b212ae83a974459232bb93a76bd689"
+    ));
+    let limbs = h256_to_fields(state_root);
+    assert_eq!(limbs[0], F::from_canonical_u32(0xa76bd689));
+    assert_eq!(limbs[7], F::from_canonical_u32(0x52587c85));
+    assert_eq!(fields_to_h256(limbs)?, state_root);
+    Ok(())
+}
+
+/// Test that a limb above 32 bits is rejected.
+#[test]
+fn test_fields_to_h256_rejects_wide_limb() {
+    let mut limbs = [F::ZERO; 8];
+    limbs[3] = F::from_canonical_u64(1 << 32);
+    assert!(matches!(
+        fields_to_h256(limbs),
+        Err(ProveError::InvalidInputs(_))
+    ));
+}
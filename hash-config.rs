@@ -0,0 +1,62 @@
+use eth_trie_utils::partial_trie::{HashedPartialTrie, PartialTrie};
+use ethereum_types::{Address, U256};
+use hex_literal::hex;
+use keccak_hash::keccak;
+use plonky2_evm::generation::mpt::AccountRlp;
+use plonky2_evm::Node;
+use plonky2_sample::{
+    eth_to_wei, prove_transfer, BlockMetadataBuilder, ConfigPreset, EvmProof, HashConfig,
+    SAMPLE_TXN,
+};
+
+/// Test proving the same transfer under both hash configs.
+#[test]
+fn test_prove_transfer_with_both_hash_configs() -> anyhow::Result<()> {
+    let sender = Address::from(hex!("2c7536e3605d9c16a7a3d7b1898e529396a65c23"));
+    let to = Address::from(hex!("a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0"));
+    let sender_account_before = AccountRlp {
+        nonce: 5.into(),
+        balance: eth_to_wei(100_000.into()),
+        storage_root: HashedPartialTrie::from(Node::Empty).hash(),
+        code_hash: keccak([]),
+    };
+    let txn = SAMPLE_TXN;
+    let block_metadata = BlockMetadataBuilder::new()
+        .beneficiary(Address::from(hex!(
+            "deadbeefdeadbeefdeadbeefdeadbeefdeadbeef"
+        )))
+        .gas_used(21032.into())
+        .build()?;
+
+    let keccak_proof = prove_transfer(
+        sender,
+        to,
+        U256::from(100u32),
+        sender_account_before,
+        &txn,
+        block_metadata.clone(),
+        HashConfig::Keccak,
+        ConfigPreset::Fast,
+    )?;
+    let poseidon_proof = prove_transfer(
+        sender,
+        to,
+        U256::from(100u32),
+        sender_account_before,
+        &txn,
+        block_metadata,
+        HashConfig::Poseidon,
+        ConfigPreset::Fast,
+    )?;
+    assert!(matches!(keccak_proof.proof, EvmProof::Keccak(_)));
+    assert!(matches!(poseidon_proof.proof, EvmProof::Poseidon(_)));
+
+    // ハッシュ関数が違っても、証明しているステートの遷移は同じ
+    assert_eq!(
+        keccak_proof.public_values.trie_roots_after,
+        poseidon_proof.public_values.trie_roots_after
+    );
+
+    keccak_proof.verify()?;
+    poseidon_proof.verify()
+}
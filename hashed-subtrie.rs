@@ -0,0 +1,64 @@
+use std::str::FromStr;
+
+use eth_trie_utils::nibbles::Nibbles;
+use eth_trie_utils::partial_trie::{HashedPartialTrie, PartialTrie};
+use ethereum_types::{Address, H256};
+use hex_literal::hex;
+use keccak_hash::keccak;
+use plonky2_evm::generation::mpt::AccountRlp;
+use plonky2_evm::generation::TrieInputs;
+use plonky2_evm::Node;
+use plonky2_sample::{
+    apply_transfer_txn, block_inputs, eth_to_wei, prove_inputs, BlockMetadataBuilder, HashConfig,
+    SignedTxn, StateTrieBuilder, SAMPLE_TXN,
+};
+
+/// Test proving a transfer in a state trie where an untouched subtrie is only known by its hash.
+#[test]
+fn test_transfer_with_hashed_subtrie() -> anyhow::Result<()> {
+    let sender = Address::from(hex!("2c7536e3605d9c16a7a3d7b1898e529396a65c23"));
+    // 送信者(0x2f..)、受信者(0xce..)、beneficiary(0x53..)のどれとも重ならない位置
+    let prefix = Nibbles::from_str("0x7").unwrap();
+    let hash = keccak("untouched accounts");
+
+    let mut state_before = StateTrieBuilder::new();
+    state_before.insert(
+        sender,
+        AccountRlp {
+            nonce: 5.into(),
+            balance: eth_to_wei(100_000.into()),
+            storage_root: HashedPartialTrie::from(Node::Empty).hash(),
+            code_hash: keccak([]),
+        },
+    );
+    state_before.insert_hash(prefix, hash);
+    let tries_before = TrieInputs {
+        state_trie: state_before.build(),
+        transactions_trie: HashedPartialTrie::from(Node::Empty),
+        receipts_trie: HashedPartialTrie::from(Node::Empty),
+        storage_tries: vec![],
+    };
+    let txn = SAMPLE_TXN;
+    let block_metadata = BlockMetadataBuilder::new().gas_used(21032.into()).build()?;
+    let signed_txn = SignedTxn {
+        sender,
+        bytes: txn.to_vec(),
+        gas_used: 21032.into(),
+    };
+
+    // 送金後もハッシュノードはそのまま残る
+    let state_after = apply_transfer_txn(&tries_before.state_trie, &signed_txn, &block_metadata)?;
+    match &*state_after {
+        Node::Branch { children, .. } => {
+            assert!(matches!(&***children[0x7], Node::Hash(h) if *h == hash));
+        }
+        node => panic!("expected a branch at the root, got {node:?}"),
+    }
+
+    let inputs = block_inputs(vec![signed_txn], tries_before, block_metadata)?.remove(0);
+    let proof = prove_inputs(inputs, HashConfig::Keccak)?;
+    assert_eq!(proof.summary().state_root, state_after.hash());
+
+    proof.verify()?;
+    Ok(())
+}
@@ -0,0 +1,123 @@
+use eth_trie_utils::partial_trie::{HashedPartialTrie, PartialTrie};
+use ethereum_types::{Address, H256};
+use hex_literal::hex;
+use keccak_hash::keccak;
+use plonky2_evm::generation::TrieInputs;
+use plonky2_evm::proof::TrieRoots;
+use plonky2_evm::Node;
+use plonky2_sample::{BlockMetadataBuilder, GenerationInputsBuilder, ProveError, SAMPLE_TXN};
+
+fn empty_tries() -> TrieInputs {
+    TrieInputs {
+        state_trie: HashedPartialTrie::from(Node::Empty),
+        transactions_trie: HashedPartialTrie::from(Node::Empty),
+        receipts_trie: HashedPartialTrie::from(Node::Empty),
+        storage_tries: vec![],
+    }
+}
+
+fn empty_roots() -> TrieRoots {
+    let empty_root = HashedPartialTrie::from(Node::Empty).hash();
+    TrieRoots {
+        state_root: empty_root,
+        transactions_root: empty_root,
+        receipts_root: empty_root,
+    }
+}
+
+/// Returns the problems listed by `ProveError::IncompleteInputs`.
+fn problems(builder: GenerationInputsBuilder) -> Vec<String> {
+    match builder.build() {
+        Err(ProveError::IncompleteInputs(problems)) => problems,
+        other => panic!("expected incomplete inputs, got {other:?}"),
+    }
+}
+
+/// Test building inputs with every field set.
+#[test]
+fn test_build_complete_inputs() -> anyhow::Result<()> {
+    let txn = SAMPLE_TXN;
+    let code = hex!("60006000fd").to_vec();
+    let addr = Address::from(hex!("a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0"));
+    let block_metadata = BlockMetadataBuilder::new().gas_used(42064.into()).build()?;
+
+    let inputs = GenerationInputsBuilder::new()
+        .signed_txn(txn.to_vec())
+        .withdrawals(vec![(addr, 1.into())])
+        .tries(empty_tries())
+        .trie_roots_after(empty_roots())
+        .contract_code(code.clone())
+        .genesis_state_trie_root(H256::repeat_byte(0x11))
+        .block_metadata(block_metadata.clone())
+        .txn_number_before(1.into())
+        .gas_used_before(21032.into())
+        .gas_used_after(42064.into())
+        .addresses(vec![addr])
+        .build()?;
+
+    assert_eq!(inputs.signed_txn, Some(txn.to_vec()));
+    assert_eq!(inputs.withdrawals, vec![(addr, 1.into())]);
+    assert_eq!(inputs.trie_roots_after, empty_roots());
+    // 空のコードは常に含まれる
+    assert_eq!(inputs.contract_code.len(), 2);
+    assert_eq!(inputs.contract_code[&keccak(&code)], code);
+    assert_eq!(inputs.contract_code[&keccak([])], Vec::<u8>::new());
+    assert_eq!(inputs.genesis_state_trie_root, H256::repeat_byte(0x11));
+    assert_eq!(inputs.block_metadata, block_metadata);
+    assert_eq!(inputs.txn_number_before, 1.into());
+    assert_eq!(inputs.gas_used_before, 21032.into());
+    assert_eq!(inputs.gas_used_after, 42064.into());
+    assert_eq!(inputs.addresses, vec![addr]);
+    Ok(())
+}
+
+/// Test that an empty builder lists every required field.
+#[test]
+fn test_build_empty_inputs() {
+    let problems = problems(GenerationInputsBuilder::new());
+    assert_eq!(problems.len(), 4);
+    for field in [
+        "tries",
+        "trie_roots_after",
+        "block_metadata",
+        "gas_used_after",
+    ] {
+        assert!(problems.iter().any(|p| p.starts_with(&format!("{field} "))));
+    }
+}
+
+/// Test that inputs without the roots after the transaction are rejected.
+#[test]
+fn test_build_without_trie_roots_after() -> anyhow::Result<()> {
+    let builder = GenerationInputsBuilder::new()
+        .tries(empty_tries())
+        .block_metadata(BlockMetadataBuilder::new().gas_used(21032.into()).build()?)
+        .gas_used_after(21032.into());
+    assert_eq!(problems(builder), vec!["trie_roots_after is not set"]);
+    Ok(())
+}
+
+/// Test that the gas used can't decrease, nor exceed the block's.
+#[test]
+fn test_build_inconsistent_gas_used() -> anyhow::Result<()> {
+    let block_metadata = BlockMetadataBuilder::new().gas_used(21032.into()).build()?;
+    let builder = GenerationInputsBuilder::new()
+        .tries(empty_tries())
+        .trie_roots_after(empty_roots())
+        .block_metadata(block_metadata.clone())
+        .gas_used_before(21032.into())
+        .gas_used_after(21000.into());
+    let problems_below = problems(builder);
+    assert_eq!(problems_below.len(), 1);
+    assert!(problems_below[0].contains("below gas_used_before"));
+
+    let builder = GenerationInputsBuilder::new()
+        .tries(empty_tries())
+        .trie_roots_after(empty_roots())
+        .block_metadata(block_metadata)
+        .gas_used_after(42064.into());
+    let problems_above = problems(builder);
+    assert_eq!(problems_above.len(), 1);
+    assert!(problems_above[0].contains("exceeds the block gas used"));
+    Ok(())
+}
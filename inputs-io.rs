@@ -0,0 +1,27 @@
+use eth_trie_utils::partial_trie::PartialTrie;
+use plonky2_sample::{
+    deserialize_inputs, prove_inputs, sample_transfer_inputs, serialize_inputs, HashConfig,
+};
+
+/// Test that inputs reloaded from JSON are identical and can be proven.
+#[test]
+fn test_inputs_round_trip() -> anyhow::Result<()> {
+    let inputs = sample_transfer_inputs();
+
+    let reloaded = deserialize_inputs(&serialize_inputs(&inputs)?)?;
+    assert_eq!(reloaded.signed_txn, inputs.signed_txn);
+    assert_eq!(reloaded.block_metadata, inputs.block_metadata);
+    assert_eq!(reloaded.trie_roots_after, inputs.trie_roots_after);
+    assert_eq!(
+        reloaded.tries.state_trie.hash(),
+        inputs.tries.state_trie.hash()
+    );
+
+    // 読み込み直した入力からも証明できる
+    let proof = prove_inputs(reloaded, HashConfig::Keccak)?;
+    assert_eq!(
+        proof.public_values.trie_roots_after,
+        inputs.trie_roots_after
+    );
+    proof.verify()
+}
@@ -0,0 +1,10 @@
+use log::LevelFilter;
+use plonky2_sample::init_logger;
+
+/// Test that initializing the logger again doesn't panic.
+#[test]
+fn test_init_logger_twice() {
+    init_logger(LevelFilter::Info);
+    init_logger(LevelFilter::Debug);
+    log::info!("logger initialized");
+}
@@ -0,0 +1,38 @@
+#![cfg(feature = "metrics")]
+
+use metrics_util::debugging::{DebugValue, DebuggingRecorder};
+use plonky2_sample::metrics::{PROVING_SECONDS, VERIFICATION_SECONDS};
+use plonky2_sample::{prove_inputs, sample_transfer_inputs, HashConfig};
+
+/// Test that proving and verifying the sample transfer record their durations.
+#[test]
+fn test_proving_metrics() -> anyhow::Result<()> {
+    // レコーダーはプロセスに1つだけなので、このファイルのテストはこれだけにする
+    let recorder = DebuggingRecorder::new();
+    let snapshotter = recorder.snapshotter();
+    recorder.install()?;
+
+    let inputs = sample_transfer_inputs();
+    prove_inputs(inputs, HashConfig::Keccak)?.verify()?;
+
+    let snapshot = snapshotter.snapshot().into_vec();
+    let samples = |name: &str| -> usize {
+        snapshot
+            .iter()
+            .filter(|(key, ..)| {
+                key.key().name() == name
+                    && key
+                        .key()
+                        .labels()
+                        .any(|label| label.key() == "preset" && label.value() == "Fast")
+            })
+            .map(|(.., value)| match value {
+                DebugValue::Histogram(samples) => samples.len(),
+                _ => 0,
+            })
+            .sum()
+    };
+    assert!(samples(PROVING_SECONDS) >= 1);
+    assert!(samples(VERIFICATION_SECONDS) >= 1);
+    Ok(())
+}
@@ -0,0 +1,78 @@
+use eth_trie_utils::partial_trie::PartialTrie;
+use ethereum_types::{Address, U256};
+use hex_literal::hex;
+use plonky2_evm::generation::mpt::AccountRlp;
+use plonky2_evm::generation::TrieInputs;
+use plonky2_sample::{
+    apply_transfer_txn, block_inputs, build_receipts_trie, build_transactions_trie, eth_to_wei,
+    prove_inputs, tries_from_accounts, BlockBuilder, BlockMetadataBuilder, HashConfig,
+    ReceiptBuilder, SignedTxn, SAMPLE_TXN,
+};
+
+/// Test proving the second transaction of a block on its own, after the first one.
+#[test]
+fn test_prove_second_transaction() -> anyhow::Result<()> {
+    let sender = Address::from(hex!("2c7536e3605d9c16a7a3d7b1898e529396a65c23"));
+    let sender_account = AccountRlp {
+        nonce: 5.into(),
+        balance: eth_to_wei(100_000.into()),
+        ..AccountRlp::default()
+    };
+    // multi-transfer.rs と同じ2つの送金。1つ目は21032 gas、2つ目は21000 gas
+    let txn_0 = SignedTxn {
+        sender,
+        bytes: SAMPLE_TXN.to_vec(),
+        gas_used: 21032.into(),
+    };
+    let txn_1 = SignedTxn {
+        sender,
+        bytes: hex!("f869060a82520894a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a08a152d02c7e14af67995bc801ba00213ef373c5fd6736d9dafd04d45482b038438e5cfd0e9a7cc366454b18bcd8da042c663ec738e79b82dddb2a3819df510a461383b67da01a2928dcbc294de34a2").to_vec(),
+        gas_used: 21000.into(),
+    };
+    let block_metadata = BlockMetadataBuilder::new()
+        .beneficiary(Address::from(hex!(
+            "deadbeefdeadbeefdeadbeefdeadbeefdeadbeef"
+        )))
+        .gas_used((21032 + 21000).into())
+        .build()?;
+    let tries_before = tries_from_accounts(&[(sender, sender_account)]);
+
+    // 1つ目のトランザクションの後のトライを、ブロック全体を作らずに組み立てる
+    let receipt_0 = ReceiptBuilder::new().cumulative_gas(21032.into()).build();
+    let tries_mid = TrieInputs {
+        state_trie: apply_transfer_txn(&tries_before.state_trie, &txn_0, &block_metadata)?,
+        transactions_trie: build_transactions_trie(&[txn_0.bytes.clone()]),
+        receipts_trie: build_receipts_trie(&[receipt_0]),
+        storage_tries: vec![],
+    };
+    let mut builder = BlockBuilder::new(tries_mid, block_metadata.clone());
+    builder.with_previous_txns(1, 21032.into(), [U256::zero(); 8]);
+    builder.push_txn(txn_1.clone());
+    let inputs = builder.build()?.remove(0);
+    assert_eq!(inputs.txn_number_before, 1.into());
+    assert_eq!(inputs.gas_used_before, 21032.into());
+    assert_eq!(inputs.gas_used_after, (21032 + 21000).into());
+
+    // ブロック全体から作った2つ目の入力と同じになる
+    let full_block = block_inputs(vec![txn_0, txn_1], tries_before, block_metadata)?;
+    assert_eq!(
+        inputs.tries.state_trie.hash(),
+        full_block[1].tries.state_trie.hash()
+    );
+    assert_eq!(
+        inputs.tries.transactions_trie.hash(),
+        full_block[1].tries.transactions_trie.hash()
+    );
+    assert_eq!(inputs.trie_roots_after, full_block[1].trie_roots_after);
+
+    let proof = prove_inputs(inputs, HashConfig::Keccak)?;
+    let extra_block_data = &proof.public_values.extra_block_data;
+    assert_eq!(extra_block_data.txn_number_before, 1.into());
+    assert_eq!(extra_block_data.gas_used_before, 21032.into());
+    assert_eq!(extra_block_data.gas_used_after, (21032 + 21000).into());
+    assert_eq!(
+        proof.summary().state_root,
+        full_block[1].trie_roots_after.state_root
+    );
+    proof.verify()
+}
@@ -0,0 +1,105 @@
+use eth_trie_utils::partial_trie::{HashedPartialTrie, PartialTrie};
+use ethereum_types::{Address, U256};
+use hex_literal::hex;
+use keccak_hash::keccak;
+use plonky2_evm::generation::mpt::AccountRlp;
+use plonky2_evm::generation::TrieInputs;
+use plonky2_evm::Node;
+use plonky2_sample::{
+    eth_to_wei, prove_block, BlockMetadataBuilder, HashConfig, SignedTxn, StateTrieBuilder,
+    SAMPLE_TXN,
+};
+
+/// Test a block of two transfers where the second one spends what the first one left.
+#[test]
+fn test_two_transfers_in_one_block() -> anyhow::Result<()> {
+    let beneficiary = hex!("deadbeefdeadbeefdeadbeefdeadbeefdeadbeef");
+    let sender = Address::from(hex!("2c7536e3605d9c16a7a3d7b1898e529396a65c23"));
+    let to = Address::from(hex!("a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0"));
+
+    let sender_account_before = AccountRlp {
+        nonce: 5.into(),
+        balance: eth_to_wei(100_000.into()),
+        storage_root: HashedPartialTrie::from(Node::Empty).hash(),
+        code_hash: keccak([]),
+    };
+    let mut state_before = StateTrieBuilder::new();
+    state_before.insert(sender, sender_account_before);
+    let tries_before = TrieInputs {
+        state_trie: state_before.build(),
+        transactions_trie: HashedPartialTrie::from(Node::Empty),
+        receipts_trie: HashedPartialTrie::from(Node::Empty),
+        storage_tries: vec![],
+    };
+
+    // 1つ目はサンプルと同じ100weiの送金(nonce 5, 21032 gas)
+    let txn_0 = SAMPLE_TXN;
+    // 2つ目は1つ目の後に残った残高からガス代を引いた全額を送る(nonce 6, 21000 gas)
+    // 1つ目の結果が引き継がれていないと、残高が合わずに失敗する
+    let txn_1 = hex!("f869060a82520894a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a08a152d02c7e14af67995bc801ba00213ef373c5fd6736d9dafd04d45482b038438e5cfd0e9a7cc366454b18bcd8da042c663ec738e79b82dddb2a3819df510a461383b67da01a2928dcbc294de34a2");
+    let value_1 = sender_account_before.balance - 100 - 21032 * 10 - 21000 * 10;
+
+    let block_metadata = BlockMetadataBuilder::new()
+        .beneficiary(Address::from(beneficiary))
+        .gas_used((21032 + 21000).into())
+        .build()?;
+
+    let txns = vec![
+        SignedTxn {
+            sender,
+            bytes: txn_0.to_vec(),
+            gas_used: 21032.into(),
+        },
+        SignedTxn {
+            sender,
+            bytes: txn_1.to_vec(),
+            gas_used: 21000.into(),
+        },
+    ];
+    let proofs = prove_block(txns, tries_before, block_metadata, HashConfig::Keccak)?;
+    assert_eq!(proofs.len(), 2);
+
+    // 1つ目の証明の後のルートが、2つ目の証明の前のルートになっている
+    assert_eq!(
+        proofs[0].public_values.trie_roots_after,
+        proofs[1].public_values.trie_roots_before
+    );
+    assert_eq!(
+        proofs[1].public_values.extra_block_data.txn_number_before,
+        1.into()
+    );
+    assert_eq!(
+        proofs[1].public_values.extra_block_data.gas_used_before,
+        21032.into()
+    );
+
+    // 送信者の残高は0になり、受信者は2回分を受け取っている
+    let expected_state_trie_after = {
+        let mut state_after = StateTrieBuilder::new();
+        state_after.insert(
+            sender,
+            AccountRlp {
+                balance: U256::zero(),
+                nonce: 7.into(),
+                ..sender_account_before
+            },
+        );
+        state_after.insert(
+            to,
+            AccountRlp {
+                balance: value_1 + 100,
+                ..AccountRlp::default()
+            },
+        );
+        state_after.build()
+    };
+    assert_eq!(
+        proofs[1].public_values.trie_roots_after.state_root,
+        expected_state_trie_after.hash()
+    );
+
+    for proof in &proofs {
+        proof.verify()?;
+    }
+    Ok(())
+}
@@ -0,0 +1,100 @@
+use eth_trie_utils::partial_trie::{HashedPartialTrie, PartialTrie};
+use ethereum_types::{Address, U256};
+use hex_literal::hex;
+use keccak_hash::keccak;
+use plonky2_evm::generation::mpt::AccountRlp;
+use plonky2_evm::generation::TrieInputs;
+use plonky2_evm::Node;
+use plonky2_sample::{
+    apply_simple_transfer, apply_transfer_txn, block_inputs, eth_to_wei, get_account, new_account,
+    prove_inputs, BlockMetadataBuilder, HashConfig, SignedTxn, StateTrieBuilder, SAMPLE_TXN,
+};
+
+/// Test that the sample transfer creates the receiver's account with empty defaults.
+#[test]
+fn test_transfer_creates_account() -> anyhow::Result<()> {
+    let sender = Address::from(hex!("2c7536e3605d9c16a7a3d7b1898e529396a65c23"));
+    let to = Address::from(hex!("a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0"));
+    let mut state_before = StateTrieBuilder::new();
+    state_before.insert(
+        sender,
+        AccountRlp {
+            nonce: 5.into(),
+            balance: eth_to_wei(100_000.into()),
+            storage_root: HashedPartialTrie::from(Node::Empty).hash(),
+            code_hash: keccak([]),
+        },
+    );
+    let tries_before = TrieInputs {
+        state_trie: state_before.build(),
+        transactions_trie: HashedPartialTrie::from(Node::Empty),
+        receipts_trie: HashedPartialTrie::from(Node::Empty),
+        storage_tries: vec![],
+    };
+    assert_eq!(get_account(&tries_before.state_trie, to)?, None);
+
+    let txn = SAMPLE_TXN;
+    let block_metadata = BlockMetadataBuilder::new().gas_used(21032.into()).build()?;
+    let signed_txn = SignedTxn {
+        sender,
+        bytes: txn.to_vec(),
+        gas_used: 21032.into(),
+    };
+
+    // 新しいアカウントは残高以外が空のデフォルト値になる
+    let state_after = apply_transfer_txn(&tries_before.state_trie, &signed_txn, &block_metadata)?;
+    let to_account = get_account(&state_after, to)?.unwrap();
+    assert_eq!(to_account, new_account(100.into()));
+    assert_eq!(to_account.nonce, U256::zero());
+    assert_eq!(
+        to_account.storage_root,
+        HashedPartialTrie::from(Node::Empty).hash()
+    );
+    assert_eq!(to_account.code_hash, keccak([]));
+
+    // 証明されたステートルートも同じアカウントを含む
+    let inputs = block_inputs(vec![signed_txn], tries_before, block_metadata)?.remove(0);
+    let proof = prove_inputs(inputs, HashConfig::Keccak)?;
+    assert_eq!(proof.summary().state_root, state_after.hash());
+    proof.verify()?;
+    Ok(())
+}
+
+/// Test that a transfer to an existing account only changes its balance.
+#[test]
+fn test_transfer_updates_existing_account() -> anyhow::Result<()> {
+    let sender = Address::from_low_u64_be(1);
+    let to = Address::from_low_u64_be(2);
+    let to_account = AccountRlp {
+        nonce: 3.into(),
+        balance: eth_to_wei(1.into()),
+        storage_root: keccak("storage"),
+        code_hash: keccak("code"),
+    };
+    let mut builder = StateTrieBuilder::new();
+    builder.insert(
+        sender,
+        AccountRlp {
+            balance: eth_to_wei(1.into()),
+            ..AccountRlp::default()
+        },
+    );
+    builder.insert(to, to_account);
+
+    let after = apply_simple_transfer(
+        &builder.build(),
+        sender,
+        to,
+        100.into(),
+        21000.into(),
+        10.into(),
+    )?;
+    assert_eq!(
+        get_account(&after, to)?,
+        Some(AccountRlp {
+            balance: to_account.balance + 100,
+            ..to_account
+        })
+    );
+    Ok(())
+}
@@ -0,0 +1,37 @@
+use std::str::FromStr;
+
+use eth_trie_utils::nibbles::Nibbles;
+use ethereum_types::H256;
+use keccak_hash::keccak;
+use plonky2_sample::{index_key_nibbles, storage_key_nibbles, truncate_front};
+
+/// Test the keys of the entries of a transactions or receipts trie around the single-byte limit.
+#[test]
+fn test_index_key_nibbles() {
+    // 0はRLPでは空の文字列(0x80)、127までは1バイト、128からは長さのプレフィックスが付く
+    let expected = [(0, "0x80"), (1, "0x01"), (127, "0x7f"), (128, "0x8180")];
+    for (index, key) in expected {
+        assert_eq!(
+            index_key_nibbles(index),
+            Nibbles::from_str(key).unwrap(),
+            "index {index}"
+        );
+    }
+    assert_eq!(index_key_nibbles(128).count, 4);
+}
+
+/// Test the storage key of a slot and dropping the nibbles consumed by a branch.
+#[test]
+fn test_storage_key_and_truncate_front() {
+    let slot = H256::zero();
+    let key = storage_key_nibbles(slot);
+    assert_eq!(
+        key,
+        Nibbles::from_bytes_be(keccak(slot).as_bytes()).unwrap()
+    );
+    assert_eq!(key.count, 64);
+
+    let rest = truncate_front(key, 1);
+    assert_eq!(rest.count, 63);
+    assert_eq!(rest.get_nibble(0), key.get_nibble(1));
+}
@@ -0,0 +1,65 @@
+#![cfg(feature = "parallel")]
+
+use eth_trie_utils::partial_trie::HashedPartialTrie;
+use ethereum_types::Address;
+use hex_literal::hex;
+use plonky2_evm::generation::mpt::AccountRlp;
+use plonky2_evm::generation::TrieInputs;
+use plonky2_evm::Node;
+use plonky2_sample::{
+    block_inputs, eth_to_wei, prove_blocks_parallel, BlockMetadataBuilder, SignedTxn,
+    StateTrieBuilder, SAMPLE_TXN,
+};
+
+/// Test proving three independent blocks in parallel.
+#[test]
+fn test_prove_blocks_parallel() -> anyhow::Result<()> {
+    let sender = Address::from(hex!("2c7536e3605d9c16a7a3d7b1898e529396a65c23"));
+    let txn = SAMPLE_TXN;
+
+    // 同じ送金を、残高の違う3つの独立したブロックで証明する
+    let mut inputs = vec![];
+    for (number, balance) in [(1u32, 1_000u32), (2, 10_000), (3, 100_000)] {
+        let mut state_before = StateTrieBuilder::new();
+        state_before.insert(
+            sender,
+            AccountRlp {
+                nonce: 5.into(),
+                balance: eth_to_wei(balance.into()),
+                ..AccountRlp::default()
+            },
+        );
+        let tries_before = TrieInputs {
+            state_trie: state_before.build(),
+            transactions_trie: HashedPartialTrie::from(Node::Empty),
+            receipts_trie: HashedPartialTrie::from(Node::Empty),
+            storage_tries: vec![],
+        };
+        let block_metadata = BlockMetadataBuilder::new()
+            .beneficiary(Address::from(hex!(
+                "deadbeefdeadbeefdeadbeefdeadbeefdeadbeef"
+            )))
+            .number(number.into())
+            .gas_used(21032.into())
+            .build()?;
+        let signed_txn = SignedTxn {
+            sender,
+            bytes: txn.to_vec(),
+            gas_used: 21032.into(),
+        };
+        inputs.extend(block_inputs(
+            vec![signed_txn],
+            tries_before,
+            block_metadata,
+        )?);
+    }
+    let expected_roots: Vec<_> = inputs.iter().map(|i| i.trie_roots_after.clone()).collect();
+
+    let proofs = prove_blocks_parallel(inputs)?;
+    assert_eq!(proofs.len(), 3);
+    for (proof, expected_roots) in proofs.iter().zip(expected_roots) {
+        assert_eq!(proof.public_values.trie_roots_after, expected_roots);
+        proof.verify()?;
+    }
+    Ok(())
+}
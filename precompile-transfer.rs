@@ -0,0 +1,128 @@
+use eth_trie_utils::partial_trie::PartialTrie;
+use ethereum_types::{Address, U256};
+use hex_literal::hex;
+use plonky2_evm::generation::mpt::AccountRlp;
+use plonky2_sample::{
+    apply_simple_transfer, eth_to_wei, get_account, intrinsic_gas, is_precompile, precompile_gas,
+    prove_transfer, BlockMetadataBuilder, ConfigPreset, HashConfig, StateTrieBuilder, TransferTxn,
+};
+
+// nonce 5, gas_price 10, gas_limit 22000, to 0x..04(identity), value 100, dataなし
+const TXN: [u8; 97] = hex!("f85f050a8255f094000000000000000000000000000000000000000464801ba03e4163b28b95cccb2e6e270c82f943cb6b26da8bdf5eb0637a1e66e6a8687598a03f7f66123e0119430c33cab20c5cc66901bc4feafd3c448829e71169c97faa76");
+
+/// Test which addresses are precompiles and what calling them costs.
+#[test]
+fn test_precompile_gas() {
+    assert!(!is_precompile(Address::zero()));
+    assert!(is_precompile(Address::from_low_u64_be(1)));
+    assert!(is_precompile(Address::from_low_u64_be(9)));
+    assert!(!is_precompile(Address::from_low_u64_be(10)));
+    assert!(!is_precompile(Address::from(hex!(
+        "0100000000000000000000000000000000000004"
+    ))));
+
+    let identity = Address::from_low_u64_be(4);
+    assert_eq!(precompile_gas(identity, &[]), Some(15.into()));
+    assert_eq!(precompile_gas(identity, &[0; 33]), Some(21.into()));
+    assert_eq!(
+        precompile_gas(Address::from_low_u64_be(2), &[0; 32]),
+        Some(72.into())
+    );
+    // modexpは入力の中身で値段が変わる
+    assert_eq!(precompile_gas(Address::from_low_u64_be(5), &[]), None);
+    assert_eq!(precompile_gas(Address::from_low_u64_be(10), &[]), None);
+}
+
+/// Test that crediting a precompile that already has a balance keeps its account as is.
+#[test]
+fn test_credit_existing_precompile() -> anyhow::Result<()> {
+    let sender = Address::from_low_u64_be(0x1234);
+    let identity = Address::from_low_u64_be(4);
+    let mut builder = StateTrieBuilder::new();
+    builder.insert(
+        sender,
+        AccountRlp {
+            balance: eth_to_wei(1.into()),
+            ..AccountRlp::default()
+        },
+    );
+    builder.insert(
+        identity,
+        AccountRlp {
+            balance: 1.into(),
+            ..AccountRlp::default()
+        },
+    );
+    let after = apply_simple_transfer(
+        &builder.build(),
+        sender,
+        identity,
+        100.into(),
+        21015.into(),
+        10.into(),
+    )?;
+    let account = get_account(&after, identity)?.expect("the precompile has an account");
+    assert_eq!(
+        account,
+        AccountRlp {
+            balance: 101.into(),
+            ..AccountRlp::default()
+        }
+    );
+    Ok(())
+}
+
+/// Test proving a transfer to the identity precompile, which is credited without running code.
+#[test]
+fn test_transfer_to_identity_precompile() -> anyhow::Result<()> {
+    let sender = Address::from(hex!("2c7536e3605d9c16a7a3d7b1898e529396a65c23"));
+    let identity = Address::from_low_u64_be(4);
+    let sender_account_before = AccountRlp {
+        nonce: 5.into(),
+        balance: eth_to_wei(100_000.into()),
+        ..AccountRlp::default()
+    };
+
+    // 21000 + identityの15(入力は0ワード)
+    let txn = TransferTxn::decode(&TXN)?;
+    let gas_used = intrinsic_gas(&txn) + precompile_gas(identity, &txn.data).unwrap();
+    assert_eq!(gas_used, 21015.into());
+
+    let block_metadata = BlockMetadataBuilder::new().gas_used(gas_used).build()?;
+    let proof = prove_transfer(
+        sender,
+        identity,
+        100.into(),
+        sender_account_before,
+        &TXN,
+        block_metadata,
+        HashConfig::Keccak,
+        ConfigPreset::Fast,
+    )?;
+    assert_eq!(proof.summary().gas_used, gas_used);
+
+    // プリコンパイルにはコードもストレージも無く、残高だけを持つアカウントになる
+    let expected_state_trie_after = {
+        let mut state_after = StateTrieBuilder::new();
+        state_after.insert(
+            sender,
+            AccountRlp {
+                balance: sender_account_before.balance - 100 - U256::from(21015 * 10),
+                nonce: 6.into(),
+                ..sender_account_before
+            },
+        );
+        state_after.insert(
+            identity,
+            AccountRlp {
+                balance: 100.into(),
+                ..AccountRlp::default()
+            },
+        );
+        state_after.build()
+    };
+    assert_eq!(proof.summary().state_root, expected_state_trie_after.hash());
+
+    proof.verify()?;
+    Ok(())
+}
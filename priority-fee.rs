@@ -0,0 +1,62 @@
+use eth_trie_utils::partial_trie::PartialTrie;
+use ethereum_types::{Address, U256};
+use hex_literal::hex;
+use plonky2_evm::generation::mpt::AccountRlp;
+use plonky2_sample::{
+    apply_transfer_txn, eth_to_wei, get_account, prove_transfer, BlockMetadataBuilder,
+    ConfigPreset, HashConfig, SignedTxn, StateTrieBuilder, SAMPLE_TXN,
+};
+
+/// Test that with a base fee below the gas price, the beneficiary gets the priority fee.
+#[test]
+fn test_priority_fee() -> anyhow::Result<()> {
+    let sender = Address::from(hex!("2c7536e3605d9c16a7a3d7b1898e529396a65c23"));
+    let to = Address::from(hex!("a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0"));
+    let beneficiary = Address::from(hex!("deadbeefdeadbeefdeadbeefdeadbeefdeadbeef"));
+    let value = U256::from(100u32);
+    let sender_account_before = AccountRlp {
+        nonce: 5.into(),
+        balance: eth_to_wei(100_000.into()),
+        ..AccountRlp::default()
+    };
+    // gas_price 10 のレガシートランザクション
+    let txn = SAMPLE_TXN;
+    let gas_used = U256::from(21032);
+    // gas_price 10 のうちベースフィーの7は燃やされ、残りの3がbeneficiaryに支払われる
+    let block_metadata = BlockMetadataBuilder::new()
+        .beneficiary(beneficiary)
+        .base_fee(7.into())
+        .gas_used(gas_used)
+        .build()?;
+
+    let mut state_before = StateTrieBuilder::new();
+    state_before.insert(sender, sender_account_before);
+    let signed_txn = SignedTxn {
+        sender,
+        bytes: txn.to_vec(),
+        gas_used,
+    };
+    let expected_state_after =
+        apply_transfer_txn(&state_before.build(), &signed_txn, &block_metadata)?;
+    assert_eq!(
+        get_account(&expected_state_after, sender)?.map(|account| account.balance),
+        Some(sender_account_before.balance - value - gas_used * 10)
+    );
+    assert_eq!(
+        get_account(&expected_state_after, beneficiary)?.map(|account| account.balance),
+        Some(gas_used * 3)
+    );
+
+    let proof = prove_transfer(
+        sender,
+        to,
+        value,
+        sender_account_before,
+        &txn,
+        block_metadata,
+        HashConfig::Keccak,
+        ConfigPreset::Fast,
+    )?;
+    assert_eq!(proof.summary().state_root, expected_state_after.hash());
+    proof.verify()
+}
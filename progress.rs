@@ -0,0 +1,47 @@
+use std::cell::RefCell;
+
+use plonky2_evm::all_stark::AllStark;
+use plonky2_sample::{
+    prove_inputs_with_progress, sample_transfer_inputs, ConfigPreset, HashConfig, ProveError,
+};
+
+/// Test that the progress callback is called with the phases of proving, in order, as each one
+/// finishes.
+#[test]
+fn test_progress_callback() -> anyhow::Result<()> {
+    let inputs = sample_transfer_inputs();
+
+    let phases = RefCell::new(vec![]);
+    let record = |phase: &str| phases.borrow_mut().push(phase.to_string());
+    let proof = prove_inputs_with_progress(
+        &AllStark::default(),
+        ConfigPreset::Fast,
+        inputs,
+        HashConfig::Keccak,
+        Some(&record),
+    )?;
+    proof.verify()?;
+
+    assert_eq!(phases.into_inner(), ["validate inputs", "prove"]);
+    Ok(())
+}
+
+/// Test that a phase that fails isn't reported as finished.
+#[test]
+fn test_progress_callback_on_invalid_inputs() {
+    let mut inputs = sample_transfer_inputs();
+    // 証明の前の検証で落ちるように、トランザクション前の使用済みガスを後より多くする
+    inputs.gas_used_before = inputs.gas_used_after + 1;
+
+    let phases = RefCell::new(vec![]);
+    let record = |phase: &str| phases.borrow_mut().push(phase.to_string());
+    let result = prove_inputs_with_progress(
+        &AllStark::default(),
+        ConfigPreset::Fast,
+        inputs,
+        HashConfig::Keccak,
+        Some(&record),
+    );
+    assert!(matches!(result, Err(ProveError::InvalidInputs(_))));
+    assert!(phases.into_inner().is_empty());
+}
@@ -0,0 +1,29 @@
+use plonky2::plonk::proof::ProofWithPublicInputs;
+use plonky2::util::timing::TimingTree;
+use plonky2_evm::all_stark::AllStark;
+use plonky2_evm::config::StarkConfig;
+use plonky2_sample::{
+    load_proof, recursive_circuits, sample_transfer_inputs, save_proof, RecursiveConfig, D, F,
+};
+
+/// Test that a proof saved to disk can be loaded back and verified.
+#[test]
+fn test_save_and_load_proof() -> anyhow::Result<()> {
+    let inputs = sample_transfer_inputs();
+
+    // ファイルに保存できるのは再帰証明(plonky2のProofWithPublicInputs)
+    let all_stark = AllStark::<F, D>::default();
+    let config = StarkConfig::standard_fast_config();
+    let circuits = recursive_circuits(&all_stark, &config);
+    let mut timing = TimingTree::new("prove root", log::Level::Debug);
+    let (proof, _public_values) = circuits.prove_root(&all_stark, &config, inputs, &mut timing)?;
+
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("proof.json");
+    save_proof(&path, &proof)?;
+    let loaded: ProofWithPublicInputs<F, RecursiveConfig, D> = load_proof(&path)?;
+    assert_eq!(loaded, proof);
+
+    // 同じAllStarkとStarkConfigから作った回路で検証できる
+    circuits.verify_root(loaded)
+}
@@ -0,0 +1,32 @@
+use plonky2::util::timing::TimingTree;
+use plonky2_evm::all_stark::AllStark;
+use plonky2_evm::config::StarkConfig;
+use plonky2_sample::{
+    proof_size_bytes, recursive_circuits, sample_transfer_inputs, save_proof, D, F,
+};
+
+/// Test that the size of the sample proof is that of its file, and a plausible one.
+#[test]
+fn test_proof_size_bytes() -> anyhow::Result<()> {
+    let inputs = sample_transfer_inputs();
+
+    let all_stark = AllStark::<F, D>::default();
+    let config = StarkConfig::standard_fast_config();
+    let circuits = recursive_circuits(&all_stark, &config);
+    let mut timing = TimingTree::new("prove root", log::Level::Debug);
+    let (proof, _public_values) = circuits.prove_root(&all_stark, &config, inputs, &mut timing)?;
+
+    let size = proof_size_bytes(&proof);
+    println!("sample proof size: {size} bytes");
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("proof.json");
+    save_proof(&path, &proof)?;
+    assert_eq!(size as u64, std::fs::metadata(&path)?.len());
+
+    // JSONの再帰証明は数百KBから数MBになる
+    assert!(
+        (10_000..50_000_000).contains(&size),
+        "unexpected proof size {size}"
+    );
+    Ok(())
+}
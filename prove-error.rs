@@ -0,0 +1,75 @@
+use eth_trie_utils::partial_trie::HashedPartialTrie;
+use ethereum_types::{Address, U256};
+use hex_literal::hex;
+use plonky2_evm::generation::mpt::AccountRlp;
+use plonky2_evm::Node;
+use plonky2_sample::{
+    apply_simple_transfer, prove_inputs, prove_transfer, sample_transfer_inputs,
+    BlockMetadataBuilder, ConfigPreset, HashConfig, ProveError, StateTrieBuilder, SAMPLE_TXN,
+};
+
+/// Test that a transfer exceeding the sender balance fails with `InsufficientBalance`.
+#[test]
+fn test_insufficient_balance() -> anyhow::Result<()> {
+    let sender = Address::from(hex!("2c7536e3605d9c16a7a3d7b1898e529396a65c23"));
+    let to = Address::from(hex!("a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0"));
+    let sender_account = AccountRlp {
+        nonce: 5.into(),
+        balance: U256::from(21032 * 10 + 99),
+        ..AccountRlp::default()
+    };
+
+    let mut state = StateTrieBuilder::new();
+    state.insert(sender, sender_account);
+    let err = apply_simple_transfer(
+        &state.build(),
+        sender,
+        to,
+        100.into(),
+        21032.into(),
+        10.into(),
+    )
+    .unwrap_err();
+    match err {
+        ProveError::InsufficientBalance {
+            sender: err_sender,
+            balance,
+            cost,
+        } => {
+            assert_eq!(err_sender, sender);
+            assert_eq!(balance, sender_account.balance);
+            assert_eq!(cost, U256::from(21032 * 10 + 100));
+        }
+        err => panic!("unexpected error: {err}"),
+    }
+
+    // 証明を作る前に、ブロックの組み立てで同じエラーになる
+    let txn = SAMPLE_TXN;
+    let block_metadata = BlockMetadataBuilder::new().gas_used(21032.into()).build()?;
+    let err = prove_transfer(
+        sender,
+        to,
+        100.into(),
+        sender_account,
+        &txn,
+        block_metadata,
+        HashConfig::Keccak,
+        ConfigPreset::Fast,
+    )
+    .unwrap_err();
+    assert!(matches!(err, ProveError::InsufficientBalance { .. }));
+    Ok(())
+}
+
+/// Test that a state trie without the sender makes proving fail with an error, not a panic.
+#[test]
+fn test_prove_without_sender_account() {
+    let mut inputs = sample_transfer_inputs();
+    // 送信者のいないステートは検証では見つからず、証明の途中で失敗する
+    inputs.tries.state_trie = HashedPartialTrie::from(Node::Empty);
+    match prove_inputs(inputs, HashConfig::Keccak) {
+        Err(ProveError::Proving(err)) => assert!(!err.to_string().is_empty()),
+        Err(err) => panic!("unexpected error: {err}"),
+        Ok(_) => panic!("a transfer without a sender was proven"),
+    }
+}
@@ -0,0 +1,82 @@
+use ethereum_types::H256;
+use plonky2_evm::proof::TrieRoots;
+use plonky2_sample::{
+    prove_inputs_with_options, sample_transfer_inputs, HashConfig, ProveError, ProveOptions,
+};
+
+/// Returns the roots after the sample transfer with a wrong state root.
+fn wrong_roots() -> TrieRoots {
+    TrieRoots {
+        state_root: H256::repeat_byte(0x11),
+        ..sample_transfer_inputs().trie_roots_after
+    }
+}
+
+/// Test proving with the roots checked against those of the inputs.
+#[test]
+fn test_prove_checking_roots() -> anyhow::Result<()> {
+    let inputs = sample_transfer_inputs();
+    let options = ProveOptions {
+        check_roots: true,
+        expected_roots: Some(inputs.trie_roots_after.clone()),
+    };
+    let proof = prove_inputs_with_options(inputs.clone(), HashConfig::Keccak, options)?;
+    assert_eq!(
+        proof.public_values.trie_roots_after,
+        inputs.trie_roots_after
+    );
+    proof.verify()?;
+    Ok(())
+}
+
+/// Test that unexpected roots are reported, before proving, only if they're checked.
+#[test]
+fn test_prove_with_unexpected_roots() -> anyhow::Result<()> {
+    let options = ProveOptions {
+        check_roots: true,
+        expected_roots: Some(wrong_roots()),
+    };
+    match prove_inputs_with_options(sample_transfer_inputs(), HashConfig::Keccak, options) {
+        Err(ProveError::TrieRootMismatch { root, .. }) => assert_eq!(root, "state_root"),
+        Err(err) => panic!("unexpected error: {err}"),
+        Ok(_) => panic!("the wrong roots were accepted"),
+    }
+
+    // check_rootsが無ければ、期待するルートは無視される
+    let options = ProveOptions {
+        check_roots: false,
+        expected_roots: Some(wrong_roots()),
+    };
+    let proof = prove_inputs_with_options(sample_transfer_inputs(), HashConfig::Keccak, options)?;
+    proof.verify()?;
+    Ok(())
+}
+
+/// Test that without expected roots, the roots of the inputs are checked against those computed
+/// outside of the EVM.
+#[test]
+fn test_prove_checking_computed_roots() -> anyhow::Result<()> {
+    let options = ProveOptions {
+        check_roots: true,
+        expected_roots: None,
+    };
+    let proof = prove_inputs_with_options(
+        sample_transfer_inputs(),
+        HashConfig::Keccak,
+        options.clone(),
+    )?;
+    proof.verify()?;
+
+    // 入力の主張するルートが間違っていれば、証明する前に気付く
+    let mut inputs = sample_transfer_inputs();
+    inputs.trie_roots_after = wrong_roots();
+    match prove_inputs_with_options(inputs, HashConfig::Keccak, options) {
+        Err(ProveError::TrieRootMismatch { root, got, .. }) => {
+            assert_eq!(root, "state_root");
+            assert_eq!(got, H256::repeat_byte(0x11));
+        }
+        Err(err) => panic!("unexpected error: {err}"),
+        Ok(_) => panic!("the wrong roots were accepted"),
+    }
+    Ok(())
+}
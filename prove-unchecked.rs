@@ -0,0 +1,22 @@
+use ethereum_types::H256;
+use plonky2_evm::proof::TrieRoots;
+use plonky2_sample::{prove_unchecked, sample_transfer_inputs, SAMPLE_SENDER};
+
+/// Test proving the sample transfer without knowing the roots after it.
+#[test]
+fn test_prove_unchecked() -> anyhow::Result<()> {
+    let mut inputs = sample_transfer_inputs();
+
+    // 後のルートが分からない状態を再現する
+    inputs.trie_roots_after = TrieRoots {
+        state_root: H256::zero(),
+        transactions_root: H256::zero(),
+        receipts_root: H256::zero(),
+    };
+    let summary = prove_unchecked(inputs, SAMPLE_SENDER)?;
+
+    assert_ne!(summary.state_root, H256::zero());
+    assert_ne!(summary.transactions_root, H256::zero());
+    assert_ne!(summary.receipts_root, H256::zero());
+    Ok(())
+}
@@ -0,0 +1,27 @@
+use plonky2::util::timing::TimingTree;
+use plonky2_evm::all_stark::AllStark;
+use plonky2_evm::config::StarkConfig;
+use plonky2_sample::{
+    proof_block_number, proof_block_timestamp, proven_gas_used, recursive_circuits,
+    sample_transfer_inputs, D, F,
+};
+
+/// Test reading the gas used, the block number and the timestamp of the sample transfer from its
+/// root proof.
+#[test]
+fn test_proven_gas_used() -> anyhow::Result<()> {
+    let inputs = sample_transfer_inputs();
+
+    let all_stark = AllStark::<F, D>::default();
+    let config = StarkConfig::standard_fast_config();
+    let circuits = recursive_circuits(&all_stark, &config);
+    let (proof, _) =
+        circuits.prove_root(&all_stark, &config, inputs, &mut TimingTree::default())?;
+    circuits.verify_root(proof.clone())?;
+
+    assert_eq!(proven_gas_used(&proof), 21032.into());
+    // BlockMetadataBuilderの既定値のまま
+    assert_eq!(proof_block_number(&proof), 1.into());
+    assert_eq!(proof_block_timestamp(&proof), 0x03e8.into());
+    Ok(())
+}
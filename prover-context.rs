@@ -0,0 +1,66 @@
+use eth_trie_utils::partial_trie::HashedPartialTrie;
+use ethereum_types::Address;
+use hex_literal::hex;
+use plonky2_evm::generation::mpt::AccountRlp;
+use plonky2_evm::generation::TrieInputs;
+use plonky2_evm::Node;
+use plonky2_sample::{
+    block_inputs, eth_to_wei, BlockMetadataBuilder, ConfigPreset, HashConfig, ProverContext,
+    SignedTxn, StateTrieBuilder, SAMPLE_TXN,
+};
+
+/// Test proving the two transfers of a block with a single `ProverContext`.
+#[test]
+fn test_prover_context() -> anyhow::Result<()> {
+    let sender = Address::from(hex!("2c7536e3605d9c16a7a3d7b1898e529396a65c23"));
+    let mut state_before = StateTrieBuilder::new();
+    state_before.insert(
+        sender,
+        AccountRlp {
+            nonce: 5.into(),
+            balance: eth_to_wei(100_000.into()),
+            ..AccountRlp::default()
+        },
+    );
+    let tries_before = TrieInputs {
+        state_trie: state_before.build(),
+        transactions_trie: HashedPartialTrie::from(Node::Empty),
+        receipts_trie: HashedPartialTrie::from(Node::Empty),
+        storage_tries: vec![],
+    };
+    // 100weiの送金(nonce 5, 21032 gas)と1000weiの送金(nonce 6, 21000 gas)
+    let txns = vec![
+        SignedTxn {
+            sender,
+            bytes: SAMPLE_TXN.to_vec(),
+            gas_used: 21032.into(),
+        },
+        SignedTxn {
+            sender,
+            bytes: hex!("f861060a82520894a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a08203e8801ca0ca96224bf09f80556a5f7d05e79ab80e8e42fd2c9af8bcda2a7949c5280ba5eea00571380a38c4a1909aab730adcdeac337a89736ea131994573a893945ad66dff").to_vec(),
+            gas_used: 21000.into(),
+        },
+    ];
+    let block_metadata = BlockMetadataBuilder::new()
+        .beneficiary(Address::from(hex!(
+            "deadbeefdeadbeefdeadbeefdeadbeefdeadbeef"
+        )))
+        .gas_used((21032 + 21000).into())
+        .build()?;
+
+    // AllStarkの構築は1回だけ
+    let context = ProverContext::new(ConfigPreset::Fast, HashConfig::Keccak);
+    let proofs = block_inputs(txns, tries_before, block_metadata)?
+        .into_iter()
+        .map(|inputs| context.prove(inputs))
+        .collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(proofs.len(), 2);
+    assert_eq!(
+        proofs[0].public_values.trie_roots_after,
+        proofs[1].public_values.trie_roots_before
+    );
+    for proof in &proofs {
+        context.verify(proof)?;
+    }
+    Ok(())
+}
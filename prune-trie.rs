@@ -0,0 +1,66 @@
+use eth_trie_utils::partial_trie::{HashedPartialTrie, PartialTrie};
+use eth_trie_utils::trie_ops::ValOrHash;
+use ethereum_types::Address;
+use hex_literal::hex;
+use plonky2_evm::generation::mpt::AccountRlp;
+use plonky2_sample::{
+    apply_transfer_txn, eth_to_wei, get_account, prune_to_touched, BlockMetadataBuilder, SignedTxn,
+    StateTrieBuilder, SAMPLE_TXN,
+};
+
+/// Returns the number of leaves of `trie` that aren't hidden under hash nodes.
+fn count_leaves(trie: &HashedPartialTrie) -> usize {
+    trie.items()
+        .filter(|(_, value)| matches!(value, ValOrHash::Val(_)))
+        .count()
+}
+
+/// Test pruning a state trie of 100 accounts to those of the sample transfer.
+#[test]
+fn test_prune_to_touched() -> anyhow::Result<()> {
+    let sender = Address::from(hex!("2c7536e3605d9c16a7a3d7b1898e529396a65c23"));
+    let to = Address::from(hex!("a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0"));
+    let beneficiary = Address::from(hex!("deadbeefdeadbeefdeadbeefdeadbeefdeadbeef"));
+    let sender_account = AccountRlp {
+        nonce: 5.into(),
+        balance: eth_to_wei(100_000.into()),
+        ..AccountRlp::default()
+    };
+    let mut state = StateTrieBuilder::new();
+    state.insert(sender, sender_account);
+    for i in 1..100 {
+        state.insert(
+            Address::from_low_u64_be(i),
+            AccountRlp {
+                balance: i.into(),
+                ..AccountRlp::default()
+            },
+        );
+    }
+    let full_trie = state.build();
+
+    // 受信者とbeneficiaryはまだ存在しないが、挿入できるよう経路は残る
+    let touched = [sender, to, beneficiary];
+    let pruned = prune_to_touched(&full_trie, &touched);
+    assert_eq!(pruned.hash(), full_trie.hash());
+    assert_eq!(count_leaves(&full_trie), 100);
+    assert!(count_leaves(&pruned) < 100);
+    assert_eq!(get_account(&pruned, sender)?, Some(sender_account));
+
+    // 刈り込んだトライに送金を適用しても、同じルートになる
+    let signed_txn = SignedTxn {
+        sender,
+        bytes: SAMPLE_TXN.to_vec(),
+        gas_used: 21032.into(),
+    };
+    let block_metadata = BlockMetadataBuilder::new()
+        .beneficiary(beneficiary)
+        .base_fee(9.into())
+        .gas_used(21032.into())
+        .build()?;
+    assert_eq!(
+        apply_transfer_txn(&pruned, &signed_txn, &block_metadata)?.hash(),
+        apply_transfer_txn(&full_trie, &signed_txn, &block_metadata)?.hash()
+    );
+    Ok(())
+}
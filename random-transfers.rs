@@ -0,0 +1,166 @@
+#![cfg(feature = "slow-tests")]
+
+use eth_trie_utils::partial_trie::{HashedPartialTrie, PartialTrie};
+use ethereum_types::{Address, U256};
+use hex_literal::hex;
+use k256::ecdsa::SigningKey;
+use keccak_hash::keccak;
+use plonky2_evm::generation::mpt::AccountRlp;
+use plonky2_evm::generation::TrieInputs;
+use plonky2_evm::Node;
+use plonky2_sample::{
+    block_inputs, prove_inputs, BlockMetadataBuilder, HashConfig, SignedTxn, StateTrieBuilder,
+    TransferTxn,
+};
+use proptest::prelude::*;
+use rlp::RlpStream;
+
+/// Returns the address of `key`, i.e. the last 20 bytes of the keccak of its public key.
+fn address_of(key: &SigningKey) -> Address {
+    let public_key = key.verifying_key().to_encoded_point(false);
+    // 先頭の0x04を除いた64バイトのkeccak
+    Address::from_slice(&keccak(&public_key.as_bytes()[1..]).as_bytes()[12..])
+}
+
+/// Signs a pre-EIP-155 legacy transfer of `value` to `to` without data.
+fn sign_transfer(
+    key: &SigningKey,
+    nonce: U256,
+    gas_price: U256,
+    to: Address,
+    value: U256,
+) -> anyhow::Result<Vec<u8>> {
+    let mut txn = TransferTxn {
+        nonce,
+        gas_price,
+        gas_limit: 21000.into(),
+        to: Some(to),
+        value,
+        data: vec![],
+        v: U256::zero(),
+        r: U256::zero(),
+        s: U256::zero(),
+    };
+    // 署名するのは [nonce, gas_price, gas, to, value, data] のRLPのkeccak
+    let mut stream = RlpStream::new_list(6);
+    stream
+        .append(&txn.nonce)
+        .append(&txn.gas_price)
+        .append(&txn.gas_limit)
+        .append(&to)
+        .append(&txn.value)
+        .append(&txn.data);
+    let (signature, recovery_id) = key.sign_prehash_recoverable(keccak(stream.out()).as_bytes())?;
+    txn.v = U256::from(27 + recovery_id.to_byte());
+    txn.r = U256::from_big_endian(&signature.r().to_bytes());
+    txn.s = U256::from_big_endian(&signature.s().to_bytes());
+    Ok(txn.encode())
+}
+
+/// Proves a transfer of `value` from the account of `key` and checks the balances after it.
+fn prove_random_transfer(
+    key: SigningKey,
+    to: Address,
+    nonce: u64,
+    value: u128,
+    gas_price: u64,
+    extra_balance: u128,
+) -> anyhow::Result<()> {
+    let sender = address_of(&key);
+    let beneficiary = Address::from(hex!("deadbeefdeadbeefdeadbeefdeadbeefdeadbeef"));
+    // 送金額とガス代を払っても、extra_balanceだけ残る
+    let gas_cost = U256::from(21000) * gas_price;
+    let sender_account_before = AccountRlp {
+        nonce: nonce.into(),
+        balance: U256::from(value) + gas_cost + extra_balance,
+        storage_root: HashedPartialTrie::from(Node::Empty).hash(),
+        code_hash: keccak([]),
+    };
+    let mut state_before = StateTrieBuilder::new();
+    state_before.insert(sender, sender_account_before);
+    let tries_before = TrieInputs {
+        state_trie: state_before.build(),
+        transactions_trie: HashedPartialTrie::from(Node::Empty),
+        receipts_trie: HashedPartialTrie::from(Node::Empty),
+        storage_tries: vec![],
+    };
+
+    let block_metadata = BlockMetadataBuilder::new()
+        .beneficiary(beneficiary)
+        .gas_used(21000.into())
+        .build()?;
+    let signed_txn = SignedTxn {
+        sender,
+        bytes: sign_transfer(&key, nonce.into(), gas_price.into(), to, value.into())?,
+        gas_used: 21000.into(),
+    };
+    let inputs = block_inputs(vec![signed_txn], tries_before, block_metadata.clone())?.remove(0);
+    let proof = prove_inputs(inputs, HashConfig::Keccak)?;
+    proof.verify()?;
+
+    // ガス価格のうちベースフィーを超えた分はbeneficiaryへ
+    let tip = U256::from(21000) * (U256::from(gas_price) - block_metadata.block_base_fee);
+    let expected_state_trie_after = {
+        let mut state_after = StateTrieBuilder::new();
+        state_after.insert(
+            sender,
+            AccountRlp {
+                nonce: (nonce + 1).into(),
+                balance: extra_balance.into(),
+                ..sender_account_before
+            },
+        );
+        state_after.insert(
+            to,
+            AccountRlp {
+                balance: value.into(),
+                ..AccountRlp::default()
+            },
+        );
+        if !tip.is_zero() {
+            state_after.insert(
+                beneficiary,
+                AccountRlp {
+                    balance: tip,
+                    ..AccountRlp::default()
+                },
+            );
+        }
+        state_after.build()
+    };
+    anyhow::ensure!(
+        proof.summary().state_root == expected_state_trie_after.hash(),
+        "unexpected state root after the transfer"
+    );
+    Ok(())
+}
+
+/// Strategy for a valid secp256k1 signing key.
+fn signing_key() -> impl Strategy<Value = SigningKey> {
+    any::<[u8; 32]>().prop_filter_map("not a valid secret key", |bytes| {
+        SigningKey::from_bytes(&bytes.into()).ok()
+    })
+}
+
+proptest! {
+    // 証明は遅いので、ケース数は少なくする
+    #![proptest_config(ProptestConfig::with_cases(4))]
+
+    /// Test random affordable transfers between fresh accounts.
+    #[test]
+    fn test_random_transfer(
+        key in signing_key(),
+        to in any::<[u8; 20]>().prop_map(Address::from),
+        nonce in 0..1000u64,
+        // 0の送金は受信者が作られないので別扱い
+        value in 1..=u128::MAX / 2,
+        // ベースフィー(10)以上
+        gas_price in 10..100u64,
+        extra_balance in 0..=u128::MAX / 2,
+    ) {
+        prop_assume!(to != address_of(&key));
+        prop_assume!(to != Address::from(hex!("deadbeefdeadbeefdeadbeefdeadbeefdeadbeef")));
+        prove_random_transfer(key, to, nonce, value, gas_price, extra_balance)
+            .map_err(|err| TestCaseError::fail(err.to_string()))?;
+    }
+}
@@ -0,0 +1,119 @@
+use eth_trie_utils::partial_trie::PartialTrie;
+use ethereum_types::{Address, U256};
+use hex_literal::hex;
+use plonky2_evm::generation::mpt::AccountRlp;
+use plonky2_sample::{
+    eth_to_wei, prove_raw_transaction, recover_sender, BlockMetadataBuilder, StateTrieBuilder,
+};
+
+// EIP-155の仕様にある、chain 1向けに署名された1 ETHの送金
+// (nonce 9, gas_price 20 gwei, gas_limit 21000, to 0x3535..35)
+const EIP155_TXN: &str = "0xf86c098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a76400008025a028ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276a067cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83";
+// eip1559.rs と同じEIP-1559の送金
+// (nonce 5, max_priority_fee_per_gas 2, max_fee_per_gas 20, to 0xa0..a0, value 100)
+const EIP1559_TXN: &str = "02f8620105021482520894a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a06480c080a0dcd94a10b7e0bdcdbe47d6cb91dcba8b0c6edc2e80bc1272973b979edbd64a9da027383b59211cf3eef7abc9caea416137cf8bb487e0549c13d7015c32136a7ab3";
+
+/// Test recovering the sender of a legacy and of a typed transaction.
+#[test]
+fn test_recover_raw_senders() -> anyhow::Result<()> {
+    assert_eq!(
+        recover_sender(&hex::decode(&EIP155_TXN[2..])?)?,
+        Address::from(hex!("9d8a62f656a8d1615c1294fd71e9cfb3e4855a4f"))
+    );
+    assert_eq!(
+        recover_sender(&hex::decode(EIP1559_TXN)?)?,
+        Address::from(hex!("2c7536e3605d9c16a7a3d7b1898e529396a65c23"))
+    );
+    Ok(())
+}
+
+/// Test proving the EIP-155 reference transaction from its hex.
+#[test]
+fn test_prove_raw_legacy_transaction() -> anyhow::Result<()> {
+    let sender = Address::from(hex!("9d8a62f656a8d1615c1294fd71e9cfb3e4855a4f"));
+    let to = Address::from(hex!("3535353535353535353535353535353535353535"));
+    let beneficiary = Address::from(hex!("deadbeefdeadbeefdeadbeefdeadbeefdeadbeef"));
+    let sender_account = AccountRlp {
+        nonce: 9.into(),
+        balance: eth_to_wei(10.into()),
+        ..AccountRlp::default()
+    };
+    let block = BlockMetadataBuilder::new()
+        .beneficiary(beneficiary)
+        .build()?;
+
+    let summary = prove_raw_transaction(EIP155_TXN, sender_account, block)?;
+    assert_eq!(summary.gas_used, 21000.into());
+
+    // ガス価格20 gweiのうち、ベースフィーの10 weiを超えた分がbeneficiaryに支払われる
+    let gas_price = U256::from(20_000_000_000u64);
+    let mut state_after = StateTrieBuilder::new();
+    state_after.insert(
+        sender,
+        AccountRlp {
+            nonce: 10.into(),
+            balance: sender_account.balance - eth_to_wei(1.into()) - gas_price * 21000,
+            ..sender_account
+        },
+    );
+    state_after.insert(
+        to,
+        AccountRlp {
+            balance: eth_to_wei(1.into()),
+            ..AccountRlp::default()
+        },
+    );
+    state_after.insert(
+        beneficiary,
+        AccountRlp {
+            balance: (gas_price - 10) * 21000,
+            ..AccountRlp::default()
+        },
+    );
+    assert_eq!(summary.state_root, state_after.build().hash());
+    Ok(())
+}
+
+/// Test proving an EIP-1559 transaction from its hex.
+#[test]
+fn test_prove_raw_typed_transaction() -> anyhow::Result<()> {
+    let sender = Address::from(hex!("2c7536e3605d9c16a7a3d7b1898e529396a65c23"));
+    let to = Address::from(hex!("a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0"));
+    let sender_account = AccountRlp {
+        nonce: 5.into(),
+        balance: eth_to_wei(100_000.into()),
+        ..AccountRlp::default()
+    };
+    // beneficiaryは0x00..00
+    let block = BlockMetadataBuilder::new().build()?;
+
+    let summary = prove_raw_transaction(EIP1559_TXN, sender_account, block)?;
+    assert_eq!(summary.gas_used, 21000.into());
+
+    // 実効ガス価格は min(20, 10 + 2) = 12
+    let mut state_after = StateTrieBuilder::new();
+    state_after.insert(
+        sender,
+        AccountRlp {
+            nonce: 6.into(),
+            balance: sender_account.balance - 100 - 21000 * 12,
+            ..sender_account
+        },
+    );
+    state_after.insert(
+        to,
+        AccountRlp {
+            balance: 100.into(),
+            ..AccountRlp::default()
+        },
+    );
+    state_after.insert(
+        Address::zero(),
+        AccountRlp {
+            balance: U256::from(21000 * 2),
+            ..AccountRlp::default()
+        },
+    );
+    assert_eq!(summary.state_root, state_after.build().hash());
+    Ok(())
+}
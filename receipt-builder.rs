@@ -0,0 +1,38 @@
+use ethereum_types::{Address, Bloom, BloomInput, H256};
+use hex_literal::hex;
+use plonky2_sample::{receipt_bloom, ReceiptBuilder};
+
+/// Test building a receipt with one log, whose bloom must include the log's address.
+#[test]
+fn test_receipt_builder_with_log() {
+    let address = Address::from(hex!("a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0"));
+    let topic = H256::from_low_u64_be(1);
+    let receipt = ReceiptBuilder::new()
+        .status(true)
+        .cumulative_gas(21032.into())
+        .add_log(address, vec![topic], vec![0x42])
+        .build();
+
+    assert!(receipt.status);
+    assert_eq!(receipt.cum_gas_used, 21032.into());
+    assert_eq!(receipt.logs.len(), 1);
+    assert_eq!(receipt.logs[0].address, address);
+
+    let bloom = Bloom::from_slice(&receipt.bloom);
+    assert!(!bloom.is_empty());
+    assert!(bloom.contains_input(BloomInput::Raw(address.as_bytes())));
+    assert!(bloom.contains_input(BloomInput::Raw(topic.as_bytes())));
+    assert_eq!(bloom, receipt_bloom(&receipt));
+}
+
+/// Test that a receipt without logs has an empty bloom.
+#[test]
+fn test_receipt_builder_without_logs() {
+    let receipt = ReceiptBuilder::new()
+        .status(false)
+        .cumulative_gas(21000.into())
+        .build();
+    assert!(!receipt.status);
+    assert_eq!(receipt.bloom.len(), 256);
+    assert!(Bloom::from_slice(&receipt.bloom).is_empty());
+}
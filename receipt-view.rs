@@ -0,0 +1,53 @@
+use eth_trie_utils::partial_trie::PartialTrie;
+use ethereum_types::{Bloom, U256};
+use plonky2_evm::generation::mpt::LegacyReceiptRlp;
+use plonky2_sample::{
+    build_receipts_trie, decode_receipt, sample_transfer_inputs, ProveError, ReceiptBuilder,
+    ReceiptView,
+};
+
+/// Test reading back the receipt of the sample transfer from its receipts trie.
+#[test]
+fn test_receipt_view_from_trie() -> anyhow::Result<()> {
+    let inputs = sample_transfer_inputs();
+    let receipts_trie =
+        build_receipts_trie(&[ReceiptBuilder::new().cumulative_gas(21032.into()).build()]);
+    // サンプルのブロックのレシートトライと同じもの
+    assert_eq!(receipts_trie.hash(), inputs.trie_roots_after.receipts_root);
+
+    let receipt_0 = ReceiptView::from_trie(&receipts_trie, 0)?;
+    assert!(receipt_0.status());
+    assert_eq!(receipt_0.cumulative_gas_used(), U256::from(21032));
+    assert_eq!(receipt_0.bloom(), Bloom::zero());
+    assert!(receipt_0.logs().is_empty());
+
+    assert!(matches!(
+        ReceiptView::from_trie(&receipts_trie, 1),
+        Err(ProveError::UnexpectedReceipt(_))
+    ));
+    Ok(())
+}
+
+/// Test decoding the receipt of a typed transaction, which starts with the type byte.
+#[test]
+fn test_decode_typed_receipt() -> anyhow::Result<()> {
+    let receipt = ReceiptBuilder::new()
+        .status(false)
+        .cumulative_gas(42064.into())
+        .build();
+    let bytes = [&[0x02], &rlp::encode(&receipt)[..]].concat();
+    let decoded: LegacyReceiptRlp = decode_receipt(&bytes)?;
+    assert!(!decoded.status);
+    assert_eq!(decoded.cum_gas_used, 42064.into());
+
+    // ブルームが256バイトでなければ壊れたレシート
+    let truncated = LegacyReceiptRlp {
+        bloom: vec![0; 8].into(),
+        ..receipt
+    };
+    assert!(matches!(
+        decode_receipt(&rlp::encode(&truncated)),
+        Err(ProveError::UnexpectedReceipt(_))
+    ));
+    Ok(())
+}
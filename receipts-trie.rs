@@ -0,0 +1,62 @@
+use std::str::FromStr;
+
+use eth_trie_utils::nibbles::Nibbles;
+use eth_trie_utils::partial_trie::PartialTrie;
+use ethereum_types::{H256, U256};
+use hex_literal::hex;
+use plonky2_evm::generation::mpt::LegacyReceiptRlp;
+use plonky2_sample::build_receipts_trie;
+
+fn receipt(status: bool, cum_gas_used: u64) -> LegacyReceiptRlp {
+    LegacyReceiptRlp {
+        status,
+        cum_gas_used: cum_gas_used.into(),
+        bloom: vec![0; 256].into(),
+        logs: vec![],
+    }
+}
+
+/// Test the receipts root of a block of three transactions.
+#[test]
+fn test_build_receipts_trie() {
+    let receipts = vec![
+        receipt(true, 21000),
+        receipt(true, 42000),
+        receipt(false, 63032),
+    ];
+    let trie = build_receipts_trie(&receipts);
+
+    // キーはインデックスのRLPなので、0は0x80、1は0x01になる
+    assert_eq!(
+        trie.get(Nibbles::from_str("0x80").unwrap()),
+        Some(rlp::encode(&receipts[0]).as_ref())
+    );
+    assert_eq!(
+        trie.get(Nibbles::from_str("0x01").unwrap()),
+        Some(rlp::encode(&receipts[1]).as_ref())
+    );
+    assert_eq!(
+        trie.hash(),
+        H256(hex!(
+            "f95edaca4a933c38e5e5e3b043e0c8aa790fa80e1dcfcd0a5d1234f1bd3d1fab"
+        ))
+    );
+}
+
+/// Test that indices above 127 get a length-prefixed key.
+#[test]
+fn test_build_receipts_trie_long_index() {
+    let receipts: Vec<_> = (0..130u64)
+        .map(|i| receipt(true, 21000 * (i + 1)))
+        .collect();
+    let trie = build_receipts_trie(&receipts);
+
+    let cum_gas_used_at = |key: &str| -> U256 {
+        let bytes = trie.get(Nibbles::from_str(key).unwrap()).unwrap();
+        rlp::decode::<LegacyReceiptRlp>(bytes).unwrap().cum_gas_used
+    };
+    assert_eq!(cum_gas_used_at("0x7f"), (21000 * 128).into());
+    // 128以上は 0x81 + 1バイト
+    assert_eq!(cum_gas_used_at("0x8180"), (21000 * 129).into());
+    assert_eq!(cum_gas_used_at("0x8181"), (21000 * 130).into());
+}
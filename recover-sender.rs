@@ -0,0 +1,46 @@
+use ethereum_types::Address;
+use hex_literal::hex;
+use plonky2_sample::{recover_sender, ProveError, TransferTxn, SAMPLE_TXN};
+
+/// Test recovering the sender of the sample transaction, signed without a chain id.
+#[test]
+fn test_recover_sender() -> anyhow::Result<()> {
+    let sender = Address::from(hex!("2c7536e3605d9c16a7a3d7b1898e529396a65c23"));
+    let txn = SAMPLE_TXN;
+    assert_eq!(recover_sender(&txn)?, sender);
+    Ok(())
+}
+
+/// Test recovering the sender of an EIP-155 transaction signed for chain 1.
+#[test]
+fn test_recover_sender_eip155() -> anyhow::Result<()> {
+    let sender = Address::from(hex!("2c7536e3605d9c16a7a3d7b1898e529396a65c23"));
+    // nonce 5, gas_price 10, gas_limit 21000, value 100, v = 1 * 2 + 35 + 1
+    let txn = hex!("f85f050a82520894a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0648026a04c1cca5c79a570f2901528ce2bc9d56c233a07d7ccf0e114e335aafaf9fdd5e3a0646677e73f405bce4461cfeffcb11201e7e8d0bf7eb346c5da4cbbfed6647dba");
+    assert_eq!(recover_sender(&txn)?, sender);
+    Ok(())
+}
+
+/// Test that a tampered transaction recovers another address, and a bad `v` is an error.
+#[test]
+fn test_recover_sender_tampered() -> anyhow::Result<()> {
+    let sender = Address::from(hex!("2c7536e3605d9c16a7a3d7b1898e529396a65c23"));
+    let txn = TransferTxn::decode(&SAMPLE_TXN)?;
+
+    // 署名の後で送金額を変えると、別のアドレスの署名になる
+    let tampered = TransferTxn {
+        value: 1_000.into(),
+        ..txn.clone()
+    };
+    assert_ne!(tampered.recover_sender()?, sender);
+
+    let bad_v = TransferTxn {
+        v: 29.into(),
+        ..txn
+    };
+    assert!(matches!(
+        bad_v.recover_sender(),
+        Err(ProveError::InvalidTransaction(_))
+    ));
+    Ok(())
+}
@@ -0,0 +1,84 @@
+use eth_trie_utils::partial_trie::PartialTrie;
+use ethereum_types::{Address, Bloom, U256};
+use hex_literal::hex;
+use keccak_hash::keccak;
+use plonky2_evm::generation::mpt::{AccountRlp, LegacyReceiptRlp};
+use plonky2_sample::{
+    apply_reverted_transfer, build_receipts_trie, check_receipt, eth_to_wei,
+    prove_reverting_transfer, BlockMetadataBuilder, ConfigPreset, HashConfig, StateTrieBuilder,
+    SAMPLE_TXN,
+};
+
+/// Test a transfer to a contract that reverts: only the gas is paid and the receipt fails.
+#[test]
+fn test_reverting_transfer() -> anyhow::Result<()> {
+    let sender = Address::from(hex!("2c7536e3605d9c16a7a3d7b1898e529396a65c23"));
+    let to = Address::from(hex!("a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0"));
+    let sender_account_before = AccountRlp {
+        nonce: 5.into(),
+        balance: eth_to_wei(100_000.into()),
+        ..AccountRlp::default()
+    };
+    // PUSH1 0, PUSH1 0, REVERT
+    let code = hex!("60006000fd").to_vec();
+    let txn = SAMPLE_TXN;
+    // 21000 + 32(calldata) + 6(PUSH1 2回)
+    let gas_used = U256::from(21038);
+    let block_metadata = BlockMetadataBuilder::new()
+        .beneficiary(Address::from(hex!(
+            "deadbeefdeadbeefdeadbeefdeadbeefdeadbeef"
+        )))
+        .gas_used(gas_used)
+        .build()?;
+
+    let proof = prove_reverting_transfer(
+        sender,
+        to,
+        100.into(),
+        sender_account_before,
+        code.clone(),
+        &txn,
+        block_metadata,
+        HashConfig::Keccak,
+        ConfigPreset::Fast,
+    )?;
+    proof.verify()?;
+
+    // 送信者はガス代だけを支払い、コントラクトの残高は変わらない
+    let contract = AccountRlp {
+        code_hash: keccak(&code),
+        ..AccountRlp::default()
+    };
+    let mut state_before = StateTrieBuilder::new();
+    state_before.insert(sender, sender_account_before);
+    state_before.insert(to, contract);
+    let state_after = apply_reverted_transfer(
+        &state_before.build(),
+        sender,
+        100.into(),
+        gas_used,
+        10.into(),
+    )?;
+    let mut expected_state_after = StateTrieBuilder::new();
+    expected_state_after.insert(
+        sender,
+        AccountRlp {
+            nonce: 6.into(),
+            balance: sender_account_before.balance - gas_used * 10,
+            ..sender_account_before
+        },
+    );
+    expected_state_after.insert(to, contract);
+    assert_eq!(state_after.hash(), expected_state_after.build().hash());
+
+    let summary = proof.summary();
+    assert_eq!(summary.state_root, state_after.hash());
+    let receipts_trie = build_receipts_trie(&[LegacyReceiptRlp {
+        status: false,
+        cum_gas_used: gas_used,
+        bloom: Bloom::zero().as_bytes().to_vec().into(),
+        logs: vec![],
+    }]);
+    check_receipt(&summary, &receipts_trie, 0, false, gas_used)?;
+    Ok(())
+}
@@ -0,0 +1,22 @@
+use ethereum_types::Address;
+use hex_literal::hex;
+use plonky2_sample::{prove_inputs, sample_transfer_inputs, validate_inputs, HashConfig};
+
+/// Test proving and verifying the sample transfer from its fixture.
+#[test]
+fn test_prove_sample_transfer_inputs() -> anyhow::Result<()> {
+    let inputs = sample_transfer_inputs();
+    validate_inputs(&inputs)?;
+    assert_eq!(inputs.gas_used_after, 21032.into());
+    assert_eq!(
+        inputs.block_metadata.block_beneficiary,
+        Address::from(hex!("deadbeefdeadbeefdeadbeefdeadbeefdeadbeef"))
+    );
+
+    let proof = prove_inputs(inputs.clone(), HashConfig::Keccak)?;
+    let summary = proof.summary();
+    assert_eq!(summary.state_root, inputs.trie_roots_after.state_root);
+    assert_eq!(summary.gas_used, 21032.into());
+    assert_eq!(summary.block_number, 1.into());
+    proof.verify()
+}
@@ -1,6 +1,34 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use eth_trie_utils::partial_trie::{HashedPartialTrie, PartialTrie};
+use ethereum_types::{BigEndianHash, H256, U256};
+use hex_literal::hex;
+use keccak_hash::keccak;
+use log::LevelFilter;
+use plonky2::field::goldilocks_field::GoldilocksField;
+use plonky2::plonk::config::KeccakGoldilocksConfig;
+use plonky2::util::timing::TimingTree;
+use plonky2_evm::all_stark::AllStark;
+use plonky2_evm::config::StarkConfig;
+use plonky2_evm::generation::mpt::{AccountRlp, LegacyReceiptRlp};
+use plonky2_evm::generation::{GenerationInputs, TrieInputs};
+use plonky2_evm::proof::{BlockHashes, BlockMetadata, TrieRoots};
+use plonky2_evm::prover::prove;
+use plonky2_evm::verifier::verify_proof;
+use plonky2_evm::Node;
+use plonky2_sample::{
+    address_to_state_key_nibbles, eth_to_wei, index_key_nibbles, init_logger, truncate_front,
+    SAMPLE_TXN,
+};
+
+type F = GoldilocksField;
+const D: usize = 2;
+type C = KeccakGoldilocksConfig;
+
 #[test]
 fn test_simple_transfer() -> anyhow::Result<()> {
-    init_logger();
+    init_logger(LevelFilter::Info);
 
     // 初期設定
     // stark関連のスタンダードな設定をここで行う。
@@ -20,14 +48,11 @@ fn test_simple_transfer() -> anyhow::Result<()> {
     // Ethereumは各アカウントの状態をState Trieというデータ構造で管理しており、
     // このState Trieで特定のアカウントの情報にアクセスするため、
     // ステートキーを使用している
-    let sender_state_key = keccak(sender);
-    let to_state_key = keccak(to);
-
-    // nibblesを生成
+    // ステートキーはアドレスのkeccakで、そこからnibblesを生成する
     // ニブルとは、半バイト（4ビット）のことで、1バイトのデータを2つのニブルに分割することができる。
     // 後のデータ構造やアルゴリズム内での扱いを効率よくするためにやる
-    let sender_nibbles = Nibbles::from(sender_state_key);
-    let to_nibbles = Nibbles::from(to_state_key);
+    let sender_nibbles = address_to_state_key_nibbles(sender.into());
+    let to_nibbles = address_to_state_key_nibbles(to.into());
     // 送金額
     let value = U256::from(100u32);
 
@@ -37,7 +62,7 @@ fn test_simple_transfer() -> anyhow::Result<()> {
     let sender_account_before = AccountRlp {
         nonce: 5.into(),
         balance: eth_to_wei(100_000.into()),
-        storage_root: PartialTrie::Empty.calc_hash(),
+        storage_root: HashedPartialTrie::from(Node::Empty).hash(),
         // code_hashが空ってことはEOAを表している？
         code_hash: keccak([]),
     };
@@ -47,47 +72,49 @@ fn test_simple_transfer() -> anyhow::Result<()> {
     // キーと値のペアを効率的に管理し、検索することができる。また、Markle Treeと違い、部分更新が可能
     // ここでは最下層のデータを作成している。
     // nibblesがキーでvalueがデータ
-    let state_trie_before = PartialTrie::Leaf {
+    let state_trie_before = Node::Leaf {
         nibbles: sender_nibbles,
         // RLPエンコードとは、Recursive Length Prefixの略で、Ethereumでよく利用されている
         // >RLPは高度に最小化したシリアライゼーションフォーマットで、ネストされたByte配列を保存する目的のためにある。
         // >protobufやBSONなどとは違って、BooleanやFloat、DoubleやIntegerさえ定義しない
         // らしい
         value: rlp::encode(&sender_account_before).to_vec(),
-    };
+    }
+    .into();
     // 証明のためのデータ作成
     let tries_before = TrieInputs {
         state_trie: state_trie_before,
-        transactions_trie: PartialTrie::Empty,
-        receipts_trie: PartialTrie::Empty,
+        transactions_trie: HashedPartialTrie::from(Node::Empty),
+        receipts_trie: HashedPartialTrie::from(Node::Empty),
         storage_tries: vec![],
     };
 
     // senderからtoにvalue送金した時のトランザクションデータをバイト列に変換したもの
-    let txn = hex!("f861050a8255f094a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0648242421ba02c89eb757d9deeb1f5b3859a9d4d679951ef610ac47ad4608dc142beb1b7e313a05af7e9fbab825455d36c36c7f4cfcafbeafa9a77bdff936b52afb36d4fe4bcdd");
+    let txn = SAMPLE_TXN;
 
     // タイムスタンプ、ブロック番号などのブロック情報
-    let block_metadata = BlockMetadata::default();
-    // 証明のためのデータ作成
-    let inputs = GenerationInputs {
-        signed_txns: vec![txn.to_vec()],
-        tries: tries_before,
-        contract_code: HashMap::new(),
-        block_metadata,
+    // ガスリミットが0だとトランザクションを取り込めないので、必要な値は埋めておく
+    let block_metadata = BlockMetadata {
+        block_timestamp: 0x03e8.into(),
+        block_number: 1.into(),
+        block_difficulty: 0x020000.into(),
+        block_random: H256::from_uint(&0x020000.into()),
+        block_gaslimit: 0xff112233u32.into(),
+        block_chain_id: 1.into(),
+        block_base_fee: 0xa.into(),
+        block_gas_used: 21032.into(),
+        ..BlockMetadata::default()
     };
-    // prove中のパフォーマンスを調査する
-    let mut timing = TimingTree::new("prove", log::Level::Debug);
-
-    // ZKのprove(証明)をここでやる。EVMが正しい挙動をしているという証明をしている
-    let proof = prove::<F, C, D>(&all_stark, &config, inputs, &mut timing)?;
-    // 100ms以上の処理時間がかかったもののみを出力
-    timing.filter(Duration::from_millis(100)).print();
 
     // txの後で期待する動作を定義
-    let expected_state_trie_after = {
+    let expected_state_trie_after: HashedPartialTrie = {
+        let txdata_gas = 2 * 16;
+        let gas_used = 21_000 + txdata_gas;
+
         // 送金したので、残高が減っている
         let sender_account_after = AccountRlp {
-            balance: sender_account_before.balance - value, // TODO: Also subtract gas_used * price. まだガスプライスの計算は行なっていない？
+            balance: sender_account_before.balance - value - gas_used * 10, // ガス代(gas_used * price)も差し引く
+            nonce: sender_account_before.nonce + 1,
             ..sender_account_before
         };
         let to_account_after = AccountRlp {
@@ -95,29 +122,82 @@ fn test_simple_transfer() -> anyhow::Result<()> {
             ..AccountRlp::default()
         };
 
-        let mut children = std::array::from_fn(|_| PartialTrie::Empty.into());
-        children[sender_nibbles.get_nibble(0) as usize] = PartialTrie::Leaf {
-            nibbles: sender_nibbles.truncate_n_nibbles_front(1),
+        let mut children = std::array::from_fn(|_| Node::Empty.into());
+        children[sender_nibbles.get_nibble(0) as usize] = Node::Leaf {
+            nibbles: truncate_front(sender_nibbles, 1),
             value: rlp::encode(&sender_account_after).to_vec(),
         }
         .into();
-        children[to_nibbles.get_nibble(0) as usize] = PartialTrie::Leaf {
-            nibbles: to_nibbles.truncate_n_nibbles_front(1),
+        children[to_nibbles.get_nibble(0) as usize] = Node::Leaf {
+            nibbles: truncate_front(to_nibbles, 1),
             value: rlp::encode(&to_account_after).to_vec(),
         }
         .into();
-        PartialTrie::Branch {
+        Node::Branch {
             children,
             value: vec![],
         }
+        .into()
     };
 
+    // トランザクションの実行結果
+    let receipt_0 = LegacyReceiptRlp {
+        status: true,
+        cum_gas_used: 21032.into(),
+        bloom: vec![0; 256].into(),
+        logs: vec![],
+    };
+    let mut receipts_trie = HashedPartialTrie::from(Node::Empty);
+    receipts_trie.insert(index_key_nibbles(0), rlp::encode(&receipt_0).to_vec());
+    let transactions_trie: HashedPartialTrie = Node::Leaf {
+        nibbles: index_key_nibbles(0),
+        value: txn.to_vec(),
+    }
+    .into();
+
+    let trie_roots_after = TrieRoots {
+        state_root: expected_state_trie_after.hash(),
+        transactions_root: transactions_trie.hash(),
+        receipts_root: receipts_trie.hash(),
+    };
+
+    let mut contract_code = HashMap::new();
+    contract_code.insert(keccak(vec![]), vec![]);
+
+    // 証明のためのデータ作成
+    let inputs = GenerationInputs {
+        signed_txn: Some(txn.to_vec()),
+        withdrawals: vec![],
+        tries: tries_before,
+        trie_roots_after,
+        contract_code,
+        genesis_state_trie_root: HashedPartialTrie::from(Node::Empty).hash(),
+        block_metadata,
+        txn_number_before: 0.into(),
+        gas_used_before: 0.into(),
+        gas_used_after: 21032.into(),
+        block_bloom_before: [0.into(); 8],
+        block_bloom_after: [0.into(); 8],
+        block_hashes: BlockHashes {
+            prev_hashes: vec![H256::default(); 256],
+            cur_hash: H256::default(),
+        },
+        addresses: vec![],
+    };
+    // prove中のパフォーマンスを調査する
+    let mut timing = TimingTree::new("prove", log::Level::Debug);
+
+    // ZKのprove(証明)をここでやる。EVMが正しい挙動をしているという証明をしている
+    let proof = prove::<F, C, D>(&all_stark, &config, inputs, &mut timing)?;
+    // 100ms以上の処理時間がかかったもののみを出力
+    timing.filter(Duration::from_millis(100)).print();
+
     // ZK Proofのstate rootと素で計算した送金後のステートルートを比較する
     assert_eq!(
         proof.public_values.trie_roots_after.state_root,
-        expected_state_trie_after.calc_hash()
+        expected_state_trie_after.hash()
     );
 
     // proof(証拠)のverify(検証)もやっておく
-    verify_proof(all_stark, proof, &config)
+    verify_proof(&all_stark, proof, &config)
 }
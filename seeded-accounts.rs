@@ -0,0 +1,79 @@
+use eth_trie_utils::partial_trie::PartialTrie;
+use ethereum_types::{Address, U256};
+use hex_literal::hex;
+use plonky2_evm::generation::mpt::AccountRlp;
+use plonky2_sample::{
+    block_inputs, eth_to_wei, prove_inputs, tries_from_accounts, BlockMetadataBuilder, HashConfig,
+    SignedTxn, StateTrieBuilder, SAMPLE_TXN,
+};
+
+/// Test a transfer between two of five existing accounts, leaving the other three unchanged.
+#[test]
+fn test_transfer_among_seeded_accounts() -> anyhow::Result<()> {
+    let sender = Address::from(hex!("2c7536e3605d9c16a7a3d7b1898e529396a65c23"));
+    let to = Address::from(hex!("a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0"));
+    let sender_account = AccountRlp {
+        nonce: 5.into(),
+        balance: eth_to_wei(100_000.into()),
+        ..AccountRlp::default()
+    };
+    let to_account = AccountRlp {
+        balance: 1_000.into(),
+        ..AccountRlp::default()
+    };
+    // 送金に関係しない3つのアカウント
+    let others: Vec<(Address, AccountRlp)> = [0x01, 0x02, 0x03]
+        .into_iter()
+        .map(|i| {
+            (
+                Address::from_low_u64_be(i),
+                AccountRlp {
+                    nonce: i.into(),
+                    balance: eth_to_wei(i.into()),
+                    ..AccountRlp::default()
+                },
+            )
+        })
+        .collect();
+    let mut accounts = vec![(sender, sender_account), (to, to_account)];
+    accounts.extend(others.iter().copied());
+    let tries_before = tries_from_accounts(&accounts);
+
+    let txn = SAMPLE_TXN;
+    let block_metadata = BlockMetadataBuilder::new()
+        .beneficiary(Address::from(hex!(
+            "deadbeefdeadbeefdeadbeefdeadbeefdeadbeef"
+        )))
+        .gas_used(21032.into())
+        .build()?;
+    let signed_txn = SignedTxn {
+        sender,
+        bytes: txn.to_vec(),
+        gas_used: 21032.into(),
+    };
+    let inputs = block_inputs(vec![signed_txn], tries_before, block_metadata)?.remove(0);
+    let proof = prove_inputs(inputs, HashConfig::Keccak)?;
+
+    // 送信者と受信者だけが変わり、他の3つはそのまま
+    let mut state_after = StateTrieBuilder::new();
+    state_after.insert(
+        sender,
+        AccountRlp {
+            nonce: 6.into(),
+            balance: sender_account.balance - 100 - U256::from(21032) * 10,
+            ..sender_account
+        },
+    );
+    state_after.insert(
+        to,
+        AccountRlp {
+            balance: to_account.balance + 100,
+            ..to_account
+        },
+    );
+    for &(addr, account) in &others {
+        state_after.insert(addr, account);
+    }
+    assert_eq!(proof.summary().state_root, state_after.build().hash());
+    proof.verify()
+}
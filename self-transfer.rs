@@ -0,0 +1,69 @@
+use eth_trie_utils::partial_trie::{HashedPartialTrie, PartialTrie};
+use ethereum_types::Address;
+use hex_literal::hex;
+use keccak_hash::keccak;
+use plonky2_evm::generation::mpt::AccountRlp;
+use plonky2_evm::Node;
+use plonky2_sample::{
+    apply_simple_transfer, eth_to_wei, prove_transfer, BlockMetadataBuilder, ConfigPreset,
+    HashConfig, StateTrieBuilder,
+};
+
+/// Test proving a transfer from the sender to itself.
+#[test]
+fn test_self_transfer() -> anyhow::Result<()> {
+    let sender = Address::from(hex!("2c7536e3605d9c16a7a3d7b1898e529396a65c23"));
+    let sender_account_before = AccountRlp {
+        nonce: 5.into(),
+        balance: eth_to_wei(100_000.into()),
+        storage_root: HashedPartialTrie::from(Node::Empty).hash(),
+        code_hash: keccak([]),
+    };
+
+    // nonce 5, gas_price 10, gas_limit 21000, to 自分自身, value 100
+    let txn = hex!("f85f050a825208942c7536e3605d9c16a7a3d7b1898e529396a65c2364801ca0b0977575e604dba8022d896c86dae1c40a2e4d1c700153e08e800b4f5ced9e10a06724b258aadf20a33702afa1402c3a947ef7b8e28a35d98cdc950c7e9a492021");
+    let block_metadata = BlockMetadataBuilder::new()
+        .beneficiary(Address::from(hex!(
+            "deadbeefdeadbeefdeadbeefdeadbeefdeadbeef"
+        )))
+        .gas_used(21000.into())
+        .build()?;
+    let proof = prove_transfer(
+        sender,
+        sender,
+        100.into(),
+        sender_account_before,
+        &txn,
+        block_metadata,
+        HashConfig::Keccak,
+        ConfigPreset::Fast,
+    )?;
+
+    // 送った100weiは戻ってくるので、ガス代だけが減る
+    let sender_account_after = AccountRlp {
+        balance: sender_account_before.balance - 21000 * 10,
+        nonce: 6.into(),
+        ..sender_account_before
+    };
+    let mut state_after = StateTrieBuilder::new();
+    state_after.insert(sender, sender_account_after);
+    let expected_state_trie_after = state_after.build();
+    // アカウントは1つだけなので、ルートがそのままリーフになる
+    assert!(matches!(*expected_state_trie_after, Node::Leaf { .. }));
+    assert_eq!(proof.summary().state_root, expected_state_trie_after.hash());
+
+    let mut state_before = StateTrieBuilder::new();
+    state_before.insert(sender, sender_account_before);
+    let applied = apply_simple_transfer(
+        &state_before.build(),
+        sender,
+        sender,
+        100.into(),
+        21000.into(),
+        10.into(),
+    )?;
+    assert_eq!(applied.hash(), expected_state_trie_after.hash());
+
+    proof.verify()?;
+    Ok(())
+}
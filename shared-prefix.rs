@@ -0,0 +1,73 @@
+use std::str::FromStr;
+
+use eth_trie_utils::nibbles::Nibbles;
+use eth_trie_utils::partial_trie::PartialTrie;
+use ethereum_types::{Address, U256};
+use hex_literal::hex;
+use plonky2_evm::generation::mpt::AccountRlp;
+use plonky2_evm::Node;
+use plonky2_sample::{
+    address_to_state_key_nibbles, apply_simple_transfer, eth_to_wei, prove_transfer,
+    BlockMetadataBuilder, ConfigPreset, HashConfig, StateTrieBuilder,
+};
+
+/// Test a transfer between two accounts whose state keys share their first four nibbles.
+#[test]
+fn test_transfer_with_shared_key_prefix() -> anyhow::Result<()> {
+    let sender = Address::from(hex!("2c7536e3605d9c16a7a3d7b1898e529396a65c23"));
+    // keccak(sender) = 0x2f93d0df..、keccak(to) = 0x2f93675a.. で、先頭の4ニブルが同じ
+    let to = Address::from_low_u64_be(0x02fdc9);
+    let prefix = Nibbles::from_str("0x2f93").unwrap();
+    for addr in [sender, to] {
+        let key = address_to_state_key_nibbles(addr);
+        let first_nibbles: Vec<u8> = (0..4).map(|i| key.get_nibble(i)).collect();
+        assert_eq!(first_nibbles, vec![0x2, 0xf, 0x9, 0x3]);
+    }
+
+    let value = U256::from(100u32);
+    let sender_account_before = AccountRlp {
+        nonce: 5.into(),
+        balance: eth_to_wei(100_000.into()),
+        ..AccountRlp::default()
+    };
+    // nonce 5, gas_price 10, gas_limit 21000, to 0x..02fdc9, value 100
+    let txn = hex!("f85f050a82520894000000000000000000000000000000000002fdc964801ba047023fb396735380fb898690d3c7061e8584dd7b796d3445cd89467e8dab94b4a036fc34bcf19586674163a588ae125b67e24ac41108cf2cbbbf64e5e6b5f5a9ba");
+    let block_metadata = BlockMetadataBuilder::new()
+        .beneficiary(Address::from(hex!(
+            "deadbeefdeadbeefdeadbeefdeadbeefdeadbeef"
+        )))
+        .gas_used(21000.into())
+        .build()?;
+
+    let mut state_before = StateTrieBuilder::new();
+    state_before.insert(sender, sender_account_before);
+    let expected_state_after = apply_simple_transfer(
+        &state_before.build(),
+        sender,
+        to,
+        value,
+        21000.into(),
+        10.into(),
+    )?;
+    // 共通の4ニブルはエクステンションノードになり、その下で2つに分かれる
+    match &*expected_state_after {
+        Node::Extension { nibbles, child } => {
+            assert_eq!(*nibbles, prefix);
+            assert!(matches!(****child, Node::Branch { .. }));
+        }
+        node => panic!("expected an extension node at the root, got {node:?}"),
+    }
+
+    let proof = prove_transfer(
+        sender,
+        to,
+        value,
+        sender_account_before,
+        &txn,
+        block_metadata,
+        HashConfig::Keccak,
+        ConfigPreset::Fast,
+    )?;
+    assert_eq!(proof.summary().state_root, expected_state_after.hash());
+    proof.verify()
+}
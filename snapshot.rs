@@ -0,0 +1,26 @@
+use ethereum_types::H256;
+use hex_literal::hex;
+use plonky2_sample::{prove_inputs, sample_transfer_inputs, HashConfig};
+
+// plonky2_evm 0.1.1 でサンプルの送金を証明したときの公開値。
+// 上流の変更で出力が変わったら、このテストが失敗する
+const STATE_ROOT: [u8; 32] =
+    hex!("52587c85ce3a0e4039fdf42e6f42107564b212ae83a974459232bb93a76bd689");
+const TRANSACTIONS_ROOT: [u8; 32] =
+    hex!("4eabe0d88efc66bd02d576cd69cddeb9356cf96137faa64588a1f629211f5ec9");
+const RECEIPTS_ROOT: [u8; 32] =
+    hex!("e9c505bc24bbe8eee06563c521ab1361161d10f65180d051d08f7572a89bc091");
+const GAS_USED: u64 = 21032;
+
+/// Test that the public values of the sample transfer's proof haven't changed.
+#[test]
+fn test_sample_public_values_snapshot() -> anyhow::Result<()> {
+    let inputs = sample_transfer_inputs();
+    let summary = prove_inputs(inputs, HashConfig::Keccak)?.summary();
+
+    assert_eq!(summary.state_root, H256(STATE_ROOT));
+    assert_eq!(summary.transactions_root, H256(TRANSACTIONS_ROOT));
+    assert_eq!(summary.receipts_root, H256(RECEIPTS_ROOT));
+    assert_eq!(summary.gas_used, GAS_USED.into());
+    Ok(())
+}
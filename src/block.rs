@@ -0,0 +1,398 @@
+use std::collections::HashMap;
+
+use eth_trie_utils::partial_trie::{HashedPartialTrie, PartialTrie};
+use ethereum_types::{Address, BigEndianHash, H256, U256};
+use keccak_hash::keccak;
+use plonky2_evm::generation::mpt::LegacyReceiptRlp;
+use plonky2_evm::generation::{GenerationInputs, TrieInputs};
+use plonky2_evm::proof::{BlockHashes, BlockMetadata, TrieRoots};
+use plonky2_evm::Node;
+
+use crate::bloom::accumulate_block_bloom;
+use crate::error::{ProveError, Result};
+use crate::nibbles::index_key_nibbles;
+use crate::receipt::ReceiptBuilder;
+use crate::state::{apply_txn_state, apply_withdrawals};
+use crate::txn::{SignedTxn, Txn};
+
+/// Builds a `BlockMetadata`, starting from the values used by the sample transfer.
+#[derive(Debug, Clone)]
+pub struct BlockMetadataBuilder {
+    metadata: BlockMetadata,
+}
+
+impl Default for BlockMetadataBuilder {
+    fn default() -> Self {
+        Self {
+            metadata: BlockMetadata {
+                block_beneficiary: Address::zero(),
+                block_timestamp: 0x03e8.into(),
+                block_number: 1.into(),
+                block_difficulty: 0x020000.into(),
+                block_random: H256::from_uint(&0x020000.into()),
+                block_gaslimit: 0xff112233u32.into(),
+                block_chain_id: 1.into(),
+                block_base_fee: 0xa.into(),
+                block_gas_used: 0.into(),
+                block_bloom: [0.into(); 8],
+            },
+        }
+    }
+}
+
+impl BlockMetadataBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn beneficiary(mut self, beneficiary: Address) -> Self {
+        self.metadata.block_beneficiary = beneficiary;
+        self
+    }
+
+    pub fn timestamp(mut self, timestamp: U256) -> Self {
+        self.metadata.block_timestamp = timestamp;
+        self
+    }
+
+    pub fn number(mut self, number: U256) -> Self {
+        self.metadata.block_number = number;
+        self
+    }
+
+    pub fn difficulty(mut self, difficulty: U256) -> Self {
+        self.metadata.block_difficulty = difficulty;
+        self
+    }
+
+    pub fn random(mut self, random: H256) -> Self {
+        self.metadata.block_random = random;
+        self
+    }
+
+    pub fn gas_limit(mut self, gas_limit: U256) -> Self {
+        self.metadata.block_gaslimit = gas_limit;
+        self
+    }
+
+    pub fn chain_id(mut self, chain_id: U256) -> Self {
+        self.metadata.block_chain_id = chain_id;
+        self
+    }
+
+    pub fn base_fee(mut self, base_fee: U256) -> Self {
+        self.metadata.block_base_fee = base_fee;
+        self
+    }
+
+    pub fn gas_used(mut self, gas_used: U256) -> Self {
+        self.metadata.block_gas_used = gas_used;
+        self
+    }
+
+    pub fn bloom(mut self, bloom: [U256; 8]) -> Self {
+        self.metadata.block_bloom = bloom;
+        self
+    }
+
+    pub fn build(self) -> Result<BlockMetadata> {
+        if self.metadata.block_gas_used > self.metadata.block_gaslimit {
+            return Err(ProveError::InvalidBlock(format!(
+                "block gas used {} exceeds the gas limit {}",
+                self.metadata.block_gas_used, self.metadata.block_gaslimit
+            )));
+        }
+        Ok(self.metadata)
+    }
+}
+
+/// Builds the `BlockHashes` of a block, i.e. the hashes of the 256 previous blocks that `BLOCKHASH`
+/// can read and the hash of the block itself.
+#[derive(Debug, Clone)]
+pub struct BlockHashBuilder {
+    block_number: U256,
+    prev_hashes: Vec<H256>,
+    parent_hashes: Vec<(U256, H256)>,
+    cur_hash: H256,
+}
+
+impl BlockHashBuilder {
+    /// Starts the hashes of block `block_number`, with all previous hashes set to zero.
+    pub fn new(block_number: U256) -> Self {
+        Self {
+            block_number,
+            prev_hashes: vec![H256::zero(); 256],
+            parent_hashes: vec![],
+            cur_hash: H256::zero(),
+        }
+    }
+
+    /// Sets all 256 previous hashes, oldest first, so that the last one is the parent's.
+    pub fn prev_hashes(mut self, prev_hashes: Vec<H256>) -> Self {
+        self.prev_hashes = prev_hashes;
+        self
+    }
+
+    /// Sets the hash of block `number`, one of the 256 blocks before this one.
+    pub fn parent_hash(mut self, number: U256, hash: H256) -> Self {
+        self.parent_hashes.push((number, hash));
+        self
+    }
+
+    pub fn cur_hash(mut self, cur_hash: H256) -> Self {
+        self.cur_hash = cur_hash;
+        self
+    }
+
+    pub fn build(self) -> Result<BlockHashes> {
+        if self.prev_hashes.len() != 256 {
+            return Err(ProveError::InvalidBlock(format!(
+                "expected 256 previous block hashes, got {}",
+                self.prev_hashes.len()
+            )));
+        }
+        let mut prev_hashes = self.prev_hashes;
+        for (number, hash) in self.parent_hashes {
+            // prev_hashes[i]はブロック block_number - 256 + i のハッシュで、
+            // BLOCKHASH(block_number - 1)は最後のprev_hashes[255]を読む
+            if number >= self.block_number || self.block_number - number > 256.into() {
+                return Err(ProveError::InvalidBlock(format!(
+                    "block {number} isn't one of the 256 blocks before block {}",
+                    self.block_number
+                )));
+            }
+            prev_hashes[256 - (self.block_number - number).as_usize()] = hash;
+        }
+        Ok(BlockHashes {
+            prev_hashes,
+            cur_hash: self.cur_hash,
+        })
+    }
+}
+
+/// Builds the `GenerationInputs` of each transaction of a block of transfers.
+#[derive(Debug, Clone)]
+pub struct BlockBuilder {
+    tries: TrieInputs,
+    block_metadata: BlockMetadata,
+    txns: Vec<SignedTxn>,
+    withdrawals: Vec<(Address, U256)>,
+    block_hashes: BlockHashes,
+    addresses: Vec<Address>,
+    genesis_state_root: H256,
+    contract_code: HashMap<H256, Vec<u8>>,
+    txn_number_before: usize,
+    gas_used_before: U256,
+    block_bloom_before: [U256; 8],
+}
+
+impl BlockBuilder {
+    /// Starts a block on top of `tries`.
+    pub fn new(tries: TrieInputs, block_metadata: BlockMetadata) -> Self {
+        Self {
+            tries,
+            block_metadata,
+            txns: vec![],
+            withdrawals: vec![],
+            block_hashes: BlockHashes {
+                prev_hashes: vec![H256::default(); 256],
+                cur_hash: H256::default(),
+            },
+            addresses: vec![],
+            genesis_state_root: HashedPartialTrie::from(Node::Empty).hash(),
+            contract_code: HashMap::from([(keccak(vec![]), vec![])]),
+            txn_number_before: 0,
+            gas_used_before: U256::zero(),
+            block_bloom_before: [U256::zero(); 8],
+        }
+    }
+
+    pub fn push_txn(&mut self, txn: SignedTxn) {
+        self.txns.push(txn);
+    }
+
+    /// Sets the withdrawals credited at the end of the block, as `(address, amount in wei)`.
+    pub fn with_withdrawals(&mut self, withdrawals: Vec<(Address, U256)>) {
+        self.withdrawals = withdrawals;
+    }
+
+    /// Sets the hashes `BLOCKHASH` reads, see `BlockHashBuilder`. They default to zeros.
+    pub fn with_block_hashes(&mut self, block_hashes: BlockHashes) {
+        self.block_hashes = block_hashes;
+    }
+
+    /// Sets the `addresses` of the inputs, the addresses known to be in the state trie.
+    ///
+    /// The state trie only holds the keccak of each address, and plonky2_evm only uses this list
+    /// to name the accounts of the post-state when debugging. It doesn't warm them in the EIP-2929
+    /// sense, so the gas is unaffected: warming needs an EIP-2930 access list in the transaction,
+    /// and the sender and the receiver of a transaction are warm anyway. The addresses of the
+    /// access list of each transaction are added to this list by `build`.
+    pub fn with_accessed_addresses(&mut self, addrs: Vec<Address>) {
+        self.addresses = addrs;
+    }
+
+    /// Sets the state root of the genesis block of the chain, e.g. as returned by `genesis_tries`.
+    ///
+    /// It defaults to the root of the empty trie. Before block 1, the state trie is the genesis
+    /// state, so the root of `tries.state_trie` must then be this root; for later blocks the two
+    /// are unrelated.
+    pub fn with_genesis_state_root(&mut self, genesis_state_root: H256) {
+        self.genesis_state_root = genesis_state_root;
+    }
+
+    /// Adds `code`, keyed by its hash, to the code of the inputs, e.g. that of a contract sending
+    /// value, whose code is kept as is. Only the empty code is there by default.
+    pub fn with_contract_code(&mut self, code: Vec<u8>) {
+        self.contract_code.insert(keccak(&code), code);
+    }
+
+    /// Starts after the first `txn_count` transactions of the block, which used `gas_used` and
+    /// whose receipts accrued to `block_bloom`, to prove the rest of a block, e.g. its `N`-th
+    /// transaction on its own.
+    ///
+    /// `tries` must then be the tries after those transactions, with them and their receipts in
+    /// the transactions and receipts tries at keys `rlp(0)` to `rlp(txn_count - 1)`.
+    pub fn with_previous_txns(&mut self, txn_count: usize, gas_used: U256, block_bloom: [U256; 8]) {
+        self.txn_number_before = txn_count;
+        self.gas_used_before = gas_used;
+        self.block_bloom_before = block_bloom;
+    }
+
+    /// Returns the inputs of each transaction, or of a single proof without transaction if the
+    /// block has none.
+    ///
+    /// The tries after transaction `i` are the tries before transaction `i + 1`, and
+    /// `txn_number_before`, `gas_used_before` and `block_bloom_before` advance accordingly. The
+    /// withdrawals are part of the last proof.
+    ///
+    /// The `addresses` of each input include those of the transaction's access list. Its storage
+    /// keys have no field in `GenerationInputs`: the kernel warms them from the transaction itself.
+    pub fn build(self) -> Result<Vec<GenerationInputs>> {
+        // トランザクションが無いブロックでも、引き出しを処理するために証明は1つ作る
+        let txns: Vec<Option<SignedTxn>> = if self.txns.is_empty() {
+            vec![None]
+        } else {
+            self.txns.into_iter().map(Some).collect()
+        };
+        let num_proofs = txns.len();
+
+        let mut tries_before = self.tries;
+        let mut gas_used_before = self.gas_used_before;
+        let mut block_bloom_before = self.block_bloom_before;
+        let mut inputs = Vec::with_capacity(num_proofs);
+        for (index, signed_txn) in txns.into_iter().enumerate() {
+            // ブロックの途中から始める場合、番号は前のトランザクションの数から数える
+            let txn_number = self.txn_number_before + index;
+            let gas_used_after =
+                gas_used_before + signed_txn.as_ref().map_or(U256::zero(), |txn| txn.gas_used);
+            let (mut tries_after, receipts) = match &signed_txn {
+                Some(signed_txn) => {
+                    let (tries_after, receipt) = apply_txn(
+                        &tries_before,
+                        signed_txn,
+                        txn_number,
+                        gas_used_after,
+                        &self.block_metadata,
+                        true,
+                    )?;
+                    (tries_after, vec![receipt])
+                }
+                None => (tries_before.clone(), vec![]),
+            };
+            // ブロックのブルームは各レシートのブルームのOR
+            let block_bloom_after = accumulate_block_bloom(block_bloom_before, &receipts);
+
+            // 引き出しはブロックの最後にまとめて処理される
+            let withdrawals = if index + 1 == num_proofs {
+                self.withdrawals.clone()
+            } else {
+                vec![]
+            };
+            tries_after.state_trie = apply_withdrawals(&tries_after.state_trie, &withdrawals)?;
+
+            // アクセスリストのアドレスも、ステートトライにあるアドレスとして渡す
+            let mut addresses = self.addresses.clone();
+            if let Some(signed_txn) = &signed_txn {
+                for (addr, _) in Txn::decode(&signed_txn.bytes)?.access_list() {
+                    if !addresses.contains(addr) {
+                        addresses.push(*addr);
+                    }
+                }
+            }
+
+            let trie_roots_after = trie_roots(&tries_after);
+            inputs.push(GenerationInputs {
+                signed_txn: signed_txn.map(|signed_txn| signed_txn.bytes),
+                withdrawals,
+                tries: tries_before,
+                trie_roots_after,
+                contract_code: self.contract_code.clone(),
+                genesis_state_trie_root: self.genesis_state_root,
+                block_metadata: self.block_metadata.clone(),
+                txn_number_before: txn_number.into(),
+                gas_used_before,
+                gas_used_after,
+                block_bloom_before,
+                block_bloom_after,
+                block_hashes: self.block_hashes.clone(),
+                addresses,
+            });
+            tries_before = tries_after;
+            gas_used_before = gas_used_after;
+            block_bloom_before = block_bloom_after;
+        }
+
+        Ok(inputs)
+    }
+}
+
+/// Applies `signed_txn`, the `txn_number`-th transfer of a block, to `tries` and returns the
+/// resulting tries and its receipt.
+///
+/// `cum_gas_used` is the gas used by the block up to and including this transaction. The part of
+/// the gas price above the base fee is paid to the block beneficiary. If `status` is false, the
+/// execution reverted: only the gas is paid, and the receipt records the failure.
+pub(crate) fn apply_txn(
+    tries: &TrieInputs,
+    signed_txn: &SignedTxn,
+    txn_number: usize,
+    cum_gas_used: U256,
+    block_metadata: &BlockMetadata,
+    status: bool,
+) -> Result<(TrieInputs, LegacyReceiptRlp)> {
+    let txn = Txn::decode(&signed_txn.bytes)?;
+    let mut tries_after = tries.clone();
+    tries_after.state_trie = apply_txn_state(&tries.state_trie, signed_txn, block_metadata, status)
+        .map_err(|err| match err {
+            ProveError::InvalidTransaction(msg) => {
+                ProveError::InvalidTransaction(format!("transaction {txn_number}: {msg}"))
+            }
+            err => err,
+        })?;
+    // ブロック内のトランザクションを格納するトランザクショントライ
+    tries_after
+        .transactions_trie
+        .insert(index_key_nibbles(txn_number), signed_txn.bytes.clone());
+    // トランザクションの実行結果
+    let receipt = ReceiptBuilder::new()
+        .status(status)
+        .cumulative_gas(cum_gas_used)
+        .build();
+    // タイプ付きトランザクションのレシートには、先頭にタイプのバイトが付く
+    let mut receipt_bytes = txn.txn_type().map_or(vec![], |txn_type| vec![txn_type]);
+    receipt_bytes.extend_from_slice(&rlp::encode(&receipt));
+    tries_after
+        .receipts_trie
+        .insert(index_key_nibbles(txn_number), receipt_bytes);
+    Ok((tries_after, receipt))
+}
+
+/// Returns the roots of `tries`.
+pub(crate) fn trie_roots(tries: &TrieInputs) -> TrieRoots {
+    TrieRoots {
+        state_root: tries.state_trie.hash(),
+        transactions_root: tries.transactions_trie.hash(),
+        receipts_root: tries.receipts_trie.hash(),
+    }
+}
@@ -0,0 +1,52 @@
+use ethereum_types::{Bloom, BloomInput, U256};
+use plonky2_evm::generation::mpt::{LegacyReceiptRlp, LogRlp};
+
+/// Returns the logs bloom of `logs`, as the 8 big-endian words used by `BlockMetadata`.
+pub fn compute_bloom(logs: &[LogRlp]) -> [U256; 8] {
+    bloom_words(&logs_bloom(logs))
+}
+
+/// Returns the logs bloom of the logs of `receipt`.
+pub fn receipt_bloom(receipt: &LegacyReceiptRlp) -> Bloom {
+    logs_bloom(&receipt.logs)
+}
+
+fn logs_bloom(logs: &[LogRlp]) -> Bloom {
+    // アドレスと各トピックのkeccakから3つずつ、2048ビットのうちのビットを立てる
+    let mut bloom = Bloom::zero();
+    for log in logs {
+        bloom.accrue(BloomInput::Raw(log.address.as_bytes()));
+        for topic in &log.topics {
+            bloom.accrue(BloomInput::Raw(topic.as_bytes()));
+        }
+    }
+    bloom
+}
+
+/// Returns the block bloom after the transactions of `receipts`, i.e. `before` OR the bloom of
+/// each receipt, as the 8 big-endian words of `GenerationInputs::block_bloom_after`.
+pub fn accumulate_block_bloom(before: [U256; 8], receipts: &[LegacyReceiptRlp]) -> [U256; 8] {
+    let mut bloom = words_bloom(&before);
+    for receipt in receipts {
+        bloom.accrue_bloom(&receipt_bloom(receipt));
+    }
+    bloom_words(&bloom)
+}
+
+/// Splits `bloom` into the 8 big-endian 256-bit words used by `BlockMetadata`.
+pub(crate) fn bloom_words(bloom: &Bloom) -> [U256; 8] {
+    let mut words = [U256::zero(); 8];
+    for (word, chunk) in words.iter_mut().zip(bloom.as_bytes().chunks_exact(32)) {
+        *word = U256::from_big_endian(chunk);
+    }
+    words
+}
+
+/// Joins the 8 big-endian words of `bloom_words` back into a bloom.
+pub(crate) fn words_bloom(words: &[U256; 8]) -> Bloom {
+    let mut bytes = [0u8; 256];
+    for (word, chunk) in words.iter().zip(bytes.chunks_exact_mut(32)) {
+        word.to_big_endian(chunk);
+    }
+    Bloom::from(bytes)
+}
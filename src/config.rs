@@ -0,0 +1,139 @@
+use plonky2::fri::reduction_strategies::FriReductionStrategy;
+use plonky2::fri::FriConfig;
+use plonky2::plonk::config::{KeccakGoldilocksConfig, PoseidonGoldilocksConfig};
+use plonky2_evm::config::StarkConfig;
+use plonky2_evm::proof::{AllProof, PublicValues};
+
+use crate::error::{ProveError, Result};
+use crate::{D, F};
+
+/// The hash used by the plonky2 config of a proof.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum HashConfig {
+    /// `KeccakGoldilocksConfig`, cheap to verify on Ethereum.
+    #[default]
+    Keccak,
+    /// `PoseidonGoldilocksConfig`, needed to verify the proof in a recursive circuit.
+    Poseidon,
+}
+
+/// A `StarkConfig` chosen for a tradeoff between security and proving time.
+///
+/// The conjectured security of FRI is `rate_bits * num_query_rounds + proof_of_work_bits` bits.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ConfigPreset {
+    /// `testing_config`, about 28 bits. Only for tests.
+    Testing,
+    /// `StarkConfig::standard_fast_config`, about 100 bits.
+    #[default]
+    Fast,
+    /// `secure_config`, about 128 bits.
+    Secure,
+}
+
+impl ConfigPreset {
+    pub fn stark_config(self) -> StarkConfig {
+        match self {
+            ConfigPreset::Testing => testing_config(),
+            ConfigPreset::Fast => StarkConfig::standard_fast_config(),
+            ConfigPreset::Secure => secure_config(),
+        }
+    }
+}
+
+/// A config with about 128 bits of conjectured security: 2 * 56 + 16.
+///
+/// Doubling `rate_bits` from `standard_fast_config` doubles the size of the LDEs but lets each
+/// query bring twice the security, so fewer queries are needed.
+pub fn secure_config() -> StarkConfig {
+    StarkConfig {
+        security_bits: 128,
+        num_challenges: 2,
+        fri_config: FriConfig {
+            rate_bits: 2,
+            cap_height: 4,
+            proof_of_work_bits: 16,
+            reduction_strategy: FriReductionStrategy::ConstantArityBits(4, 5),
+            num_query_rounds: 56,
+        },
+    }
+}
+
+/// A config with about 28 bits of conjectured security: 1 * 12 + 16. Proofs are small and fast
+/// to verify, but must not be relied upon.
+pub fn testing_config() -> StarkConfig {
+    StarkConfig {
+        security_bits: 28,
+        num_challenges: 2,
+        fri_config: FriConfig {
+            rate_bits: 1,
+            cap_height: 4,
+            proof_of_work_bits: 16,
+            reduction_strategy: FriReductionStrategy::ConstantArityBits(4, 5),
+            num_query_rounds: 12,
+        },
+    }
+}
+
+/// Returns a `StarkConfig` with the FRI parameters `fri`, e.g. tuned for some hardware, claiming
+/// the conjectured security of `fri`.
+///
+/// The parameters are checked first, as plonky2 would panic or loop forever on some of them.
+pub fn fri_stark_config(fri: FriConfig) -> Result<StarkConfig> {
+    let invalid = |msg: String| Err(ProveError::InvalidFriConfig(msg));
+    // EVMのテーブルの制約は次数3なので、商多項式にはブローアップ2以上が必要
+    if fri.rate_bits == 0 {
+        return invalid("rate_bits must be at least 1 for constraints of degree 3".into());
+    }
+    if fri.num_query_rounds == 0 {
+        return invalid("num_query_rounds must be at least 1".into());
+    }
+    if fri.proof_of_work_bits >= 64 {
+        return invalid(format!(
+            "proof_of_work_bits {} doesn't fit in a field element",
+            fri.proof_of_work_bits
+        ));
+    }
+    // アリティが0だと次数が減らず、FRIの折りたたみが終わらない
+    match &fri.reduction_strategy {
+        FriReductionStrategy::ConstantArityBits(0, _) => {
+            return invalid("the arity bits of the reduction strategy must be at least 1".into());
+        }
+        FriReductionStrategy::Fixed(arities) if arities.contains(&0) => {
+            return invalid(format!(
+                "the fixed reduction arities {arities:?} contain 0 bits"
+            ));
+        }
+        _ => {}
+    }
+
+    let security_bits = fri.rate_bits * fri.num_query_rounds + fri.proof_of_work_bits as usize;
+    Ok(StarkConfig {
+        security_bits,
+        num_challenges: 2,
+        fri_config: fri,
+    })
+}
+
+/// An `AllProof` generated under either `HashConfig`.
+#[derive(Debug, Clone)]
+pub enum EvmProof {
+    Keccak(AllProof<F, KeccakGoldilocksConfig, D>),
+    Poseidon(AllProof<F, PoseidonGoldilocksConfig, D>),
+}
+
+impl EvmProof {
+    pub fn hash_config(&self) -> HashConfig {
+        match self {
+            EvmProof::Keccak(_) => HashConfig::Keccak,
+            EvmProof::Poseidon(_) => HashConfig::Poseidon,
+        }
+    }
+
+    pub fn public_values(&self) -> &PublicValues {
+        match self {
+            EvmProof::Keccak(proof) => &proof.public_values,
+            EvmProof::Poseidon(proof) => &proof.public_values,
+        }
+    }
+}
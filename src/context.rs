@@ -0,0 +1,54 @@
+use plonky2_evm::all_stark::AllStark;
+use plonky2_evm::config::StarkConfig;
+use plonky2_evm::generation::GenerationInputs;
+
+use crate::config::{ConfigPreset, HashConfig};
+use crate::error::Result;
+use crate::transfer::{prove_inputs_with, GeneratedProof};
+use crate::{D, F};
+
+/// Owns the `AllStark` and the config of the proofs, so that they're set up once for any number
+/// of proofs.
+///
+/// `AllStark::default()` builds the constraint data of every STARK table, which `prove_transfer`
+/// and `prove_inputs` redo on each call. The `all_stark_setup` bench measures what is saved per
+/// proof.
+pub struct ProverContext {
+    all_stark: AllStark<F, D>,
+    preset: ConfigPreset,
+    hash_config: HashConfig,
+}
+
+impl ProverContext {
+    pub fn new(preset: ConfigPreset, hash_config: HashConfig) -> Self {
+        Self {
+            all_stark: AllStark::default(),
+            preset,
+            hash_config,
+        }
+    }
+
+    pub fn all_stark(&self) -> &AllStark<F, D> {
+        &self.all_stark
+    }
+
+    pub fn config(&self) -> StarkConfig {
+        self.preset.stark_config()
+    }
+
+    /// Proves `inputs`, see `prove_inputs_with`.
+    pub fn prove(&self, inputs: GenerationInputs) -> Result<GeneratedProof> {
+        prove_inputs_with(&self.all_stark, self.preset, inputs, self.hash_config)
+    }
+
+    /// Verifies a proof generated by `prove`, or by any other context with the same preset.
+    pub fn verify(&self, proof: &GeneratedProof) -> Result<()> {
+        proof.verify_with(&self.all_stark)
+    }
+}
+
+impl Default for ProverContext {
+    fn default() -> Self {
+        Self::new(ConfigPreset::default(), HashConfig::default())
+    }
+}
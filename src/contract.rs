@@ -0,0 +1,209 @@
+use eth_trie_utils::partial_trie::{HashedPartialTrie, PartialTrie};
+use ethereum_types::{Address, H256, U256};
+use keccak_hash::keccak;
+use plonky2_evm::generation::mpt::AccountRlp;
+use plonky2_evm::generation::{GenerationInputs, TrieInputs};
+use plonky2_evm::proof::{BlockMetadata, TrieRoots};
+use plonky2_evm::Node;
+use rlp::RlpStream;
+
+use crate::block::{apply_txn, trie_roots};
+use crate::bloom::accumulate_block_bloom;
+use crate::error::{ProveError, Result};
+use crate::gas::gas_fee;
+use crate::inputs::GenerationInputsBuilder;
+use crate::nibbles::index_key_nibbles;
+use crate::receipt::ReceiptBuilder;
+use crate::state::{checked_cost, credit, next_nonce};
+use crate::trie::{StateTrieBuilder, StorageTrieBuilder};
+use crate::txn::{SignedTxn, TransferTxn, Txn};
+use crate::world::WorldState;
+
+/// Returns the address of the contract created by `deployer` with a transaction of nonce `nonce`.
+pub fn contract_address(deployer: Address, nonce: U256) -> Address {
+    let mut stream = RlpStream::new_list(2);
+    stream.append(&deployer).append(&nonce);
+    Address::from_slice(&keccak(stream.out()).as_bytes()[12..])
+}
+
+/// Returns a contract account with `code` and `storage`, together with its code, to be added to
+/// `GenerationInputs::contract_code`, and its storage trie, to be added to
+/// `TrieInputs::storage_tries`.
+///
+/// The account's `code_hash` and `storage_root` are derived from the code and the trie, so the
+/// three can't get out of sync.
+pub fn contract_account(
+    code: Vec<u8>,
+    storage: &[(H256, U256)],
+    balance: U256,
+    nonce: U256,
+) -> Result<(AccountRlp, Vec<u8>, HashedPartialTrie)> {
+    let (storage_trie, storage_root) =
+        StorageTrieBuilder::with_slots(storage.iter().copied())?.build();
+    let account = AccountRlp {
+        nonce,
+        balance,
+        storage_root,
+        code_hash: keccak(&code),
+    };
+    Ok((account, code, storage_trie))
+}
+
+/// Builds the inputs of a block whose only transaction deploys `code`.
+///
+/// `signed_txn` is the contract creation transaction signed by `deployer`, whose init code must
+/// return `code`. `gas_used` includes the creation cost, the init code execution and the code
+/// deposit, since none of them can be derived without running the EVM. The part of the gas price
+/// above the base fee is paid to the block beneficiary, as for a transfer.
+pub fn deploy_contract(
+    code: Vec<u8>,
+    deployer: Address,
+    deployer_account: AccountRlp,
+    signed_txn: &[u8],
+    gas_used: U256,
+    block_metadata: BlockMetadata,
+) -> Result<GenerationInputs> {
+    let txn = TransferTxn::decode(signed_txn)?;
+    if txn.to.is_some() {
+        return Err(ProveError::InvalidTransaction(
+            "not a contract creation transaction".into(),
+        ));
+    }
+    if txn.nonce != deployer_account.nonce {
+        return Err(ProveError::InvalidTransaction(format!(
+            "the transaction nonce {} doesn't match the deployer nonce {}",
+            txn.nonce, deployer_account.nonce
+        )));
+    }
+    let base_fee = block_metadata.block_base_fee;
+    if txn.gas_price < base_fee {
+        return Err(ProveError::InvalidTransaction(format!(
+            "it pays {} per gas, below the base fee {base_fee}",
+            txn.gas_price
+        )));
+    }
+    let cost = checked_cost(
+        deployer,
+        deployer_account.balance,
+        txn.value,
+        gas_used,
+        txn.gas_price,
+    )?;
+
+    let mut state_before = StateTrieBuilder::new();
+    state_before.insert(deployer, deployer_account);
+
+    // デプロイ後はデプロイした人のnonceが増え、
+    // コントラクトのアカウントがコードのハッシュを持つようになる
+    let code_hash = keccak(&code);
+    let mut state_after = StateTrieBuilder::new();
+    state_after.insert(
+        deployer,
+        AccountRlp {
+            nonce: next_nonce(deployer, deployer_account.nonce)?,
+            balance: deployer_account.balance - cost,
+            ..deployer_account
+        },
+    );
+    state_after.insert(
+        contract_address(deployer, txn.nonce),
+        AccountRlp {
+            // EIP-161により、作られたコントラクトのnonceは1から始まる
+            nonce: 1.into(),
+            balance: txn.value,
+            code_hash,
+            ..AccountRlp::default()
+        },
+    );
+    let mut state_trie_after = state_after.build();
+    // 送金と同じく、beneficiaryが受け取るのは優先手数料だけ
+    let fee = gas_fee(gas_used, base_fee, txn.gas_price - base_fee);
+    if !fee.tip.is_zero() {
+        credit(
+            &mut state_trie_after,
+            block_metadata.block_beneficiary,
+            fee.tip,
+        )?;
+    }
+
+    let mut transactions_trie = HashedPartialTrie::from(Node::Empty);
+    transactions_trie.insert(index_key_nibbles(0), signed_txn.to_vec());
+    let receipt = ReceiptBuilder::new().cumulative_gas(gas_used).build();
+    let mut receipts_trie = HashedPartialTrie::from(Node::Empty);
+    receipts_trie.insert(index_key_nibbles(0), rlp::encode(&receipt).to_vec());
+
+    GenerationInputsBuilder::new()
+        .signed_txn(signed_txn.to_vec())
+        .tries(TrieInputs {
+            state_trie: state_before.build(),
+            transactions_trie: HashedPartialTrie::from(Node::Empty),
+            receipts_trie: HashedPartialTrie::from(Node::Empty),
+            storage_tries: vec![],
+        })
+        .trie_roots_after(TrieRoots {
+            state_root: state_trie_after.hash(),
+            transactions_root: transactions_trie.hash(),
+            receipts_root: receipts_trie.hash(),
+        })
+        .contract_code(code)
+        .block_metadata(block_metadata)
+        .gas_used_after(gas_used)
+        .build()
+}
+
+/// Builds the inputs of a block whose only transaction, `signed_txn`, calls a contract of `world`
+/// with calldata, and returns them with the world after the call.
+///
+/// As for `deploy_contract`, what the code does can't be derived without running the EVM, so the
+/// call's effects are passed in: `storage_writes` are the slots of the contract it sets, and
+/// `signed_txn.gas_used` includes the execution of the code. The value and the gas are paid as
+/// for a transfer, see `apply_transfer_txn`.
+pub fn call_contract(
+    world: &WorldState,
+    signed_txn: &SignedTxn,
+    storage_writes: &[(H256, U256)],
+    block_metadata: BlockMetadata,
+) -> Result<(GenerationInputs, WorldState)> {
+    let txn = Txn::decode(&signed_txn.bytes)?;
+    let contract = txn
+        .to()
+        .ok_or_else(|| ProveError::InvalidTransaction("it is a contract creation".into()))?;
+    let code_hash = world.account(contract).map(|account| account.code_hash);
+    if code_hash.is_none() || code_hash == Some(keccak([])) {
+        return Err(ProveError::InvalidTransaction(format!(
+            "{contract:?} has no code to call"
+        )));
+    }
+
+    // 呼び出し後のストレージは、書き込まれたスロットだけが変わる
+    let mut world_after = world.clone();
+    for &(slot, value) in storage_writes {
+        world_after.set_storage(contract, slot, value);
+    }
+    world_after.apply_transfer(signed_txn, &block_metadata)?;
+
+    // トランザクションとレシートのトライは送金と同じように作り、ステートだけ差し替える
+    let tries_before = world.to_trie_inputs();
+    let gas_used = signed_txn.gas_used;
+    let (mut tries_after, receipt) = apply_txn(
+        &tries_before,
+        signed_txn,
+        0,
+        gas_used,
+        &block_metadata,
+        true,
+    )?;
+    tries_after.state_trie = world_after.state_trie();
+
+    let mut builder = GenerationInputsBuilder::new()
+        .signed_txn(signed_txn.bytes.clone())
+        .tries(tries_before)
+        .trie_roots_after(trie_roots(&tries_after))
+        .block_metadata(block_metadata)
+        .gas_used_after(gas_used)
+        .block_bloom_after(accumulate_block_bloom([U256::zero(); 8], &[receipt]));
+    for code in world.contract_code_map().into_values() {
+        builder = builder.contract_code(code);
+    }
+    Ok((builder.build()?, world_after))
+}
@@ -0,0 +1,108 @@
+use std::fmt::Write;
+
+use eth_trie_utils::nibbles::Nibbles;
+use eth_trie_utils::partial_trie::{HashedPartialTrie, PartialTrie};
+use eth_trie_utils::trie_ops::ValOrHash;
+use ethereum_types::{Address, U256};
+use plonky2_evm::generation::mpt::AccountRlp;
+
+use crate::error::Result;
+use crate::nibbles::address_to_state_key_nibbles;
+use crate::trie::get_account;
+
+/// An account that differs between two state tries.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccountDiff {
+    /// The state key of the account, i.e. `keccak(address)`, as the address itself isn't stored.
+    pub state_key: Nibbles,
+    /// The account before, or `None` if it was added.
+    pub before: Option<AccountRlp>,
+    /// The account after, or `None` if it was removed.
+    pub after: Option<AccountRlp>,
+}
+
+impl AccountDiff {
+    /// Returns whether this is the diff of the account at `addr`.
+    pub fn is_for(&self, addr: Address) -> bool {
+        self.state_key == address_to_state_key_nibbles(addr)
+    }
+}
+
+/// Returns the accounts added, removed or modified from `before` to `after`.
+///
+/// Subtries that are only present as hashes are skipped, so both tries should be fully known.
+pub fn diff_tries(
+    before: &HashedPartialTrie,
+    after: &HashedPartialTrie,
+) -> Result<Vec<AccountDiff>> {
+    let mut diffs = vec![];
+    // 変更と削除
+    for (key, value) in before.items() {
+        let ValOrHash::Val(bytes) = value else {
+            continue;
+        };
+        let account_before: AccountRlp = rlp::decode(&bytes)?;
+        let account_after = after.get(key).map(rlp::decode::<AccountRlp>).transpose()?;
+        if account_after != Some(account_before) {
+            diffs.push(AccountDiff {
+                state_key: key,
+                before: Some(account_before),
+                after: account_after,
+            });
+        }
+    }
+    // 追加
+    for (key, value) in after.items() {
+        let ValOrHash::Val(bytes) = value else {
+            continue;
+        };
+        if before.get(key).is_none() {
+            diffs.push(AccountDiff {
+                state_key: key,
+                before: None,
+                after: Some(rlp::decode(&bytes)?),
+            });
+        }
+    }
+    Ok(diffs)
+}
+
+/// Returns how the balance changed from `before` to `after`, as a flag set if it decreased and the
+/// magnitude of the change, since a `U256` can't be negative.
+pub fn balance_delta(before: &AccountRlp, after: &AccountRlp) -> (bool, U256) {
+    if after.balance < before.balance {
+        (true, before.balance - after.balance)
+    } else {
+        (false, after.balance - before.balance)
+    }
+}
+
+/// Returns a table of the balance changes of `accounts`, each given with a label such as
+/// `"sender"`, from the state trie `before` to `after`, e.g. those of a proof, one account per
+/// line.
+///
+/// An account missing from a trie has a zero balance.
+pub fn format_balance_deltas(
+    before: &HashedPartialTrie,
+    after: &HashedPartialTrie,
+    accounts: &[(&str, Address)],
+) -> Result<String> {
+    let width = accounts
+        .iter()
+        .map(|(label, _)| label.len())
+        .max()
+        .unwrap_or(0);
+    let mut table = String::new();
+    for &(label, addr) in accounts {
+        let account_before = get_account(before, addr)?.unwrap_or_default();
+        let account_after = get_account(after, addr)?.unwrap_or_default();
+        let (decreased, amount) = balance_delta(&account_before, &account_after);
+        let sign = match (decreased, amount.is_zero()) {
+            (_, true) => "",
+            (true, false) => "-",
+            (false, false) => "+",
+        };
+        writeln!(table, "{label:<width$} {addr:?} {sign}{amount} wei").unwrap();
+    }
+    Ok(table)
+}
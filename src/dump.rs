@@ -0,0 +1,66 @@
+use std::fmt::Write;
+
+use eth_trie_utils::nibbles::Nibbles;
+use eth_trie_utils::partial_trie::HashedPartialTrie;
+use plonky2_evm::generation::mpt::AccountRlp;
+use plonky2_evm::Node;
+
+/// Returns an indented description of `trie`, one node per line, for debugging root mismatches.
+///
+/// Leaves show their full key, and their account if they decode as one. Subtries only known by
+/// their hash are printed as `Hash` nodes.
+pub fn dump_trie(trie: &HashedPartialTrie) -> String {
+    let mut out = String::new();
+    dump_node(&**trie, "", 0, &mut out);
+    out
+}
+
+fn dump_node(node: &Node, path: &str, depth: usize, out: &mut String) {
+    let indent = "  ".repeat(depth);
+    // Stringへの書き込みは失敗しない
+    match node {
+        Node::Empty => writeln!(out, "{indent}Empty").unwrap(),
+        Node::Hash(hash) => writeln!(out, "{indent}Hash {hash:?}").unwrap(),
+        Node::Branch { children, value } => {
+            if value.is_empty() {
+                writeln!(out, "{indent}Branch").unwrap();
+            } else {
+                writeln!(out, "{indent}Branch value=0x{}", hex::encode(value)).unwrap();
+            }
+            for (i, child) in children.iter().enumerate() {
+                let child = &****child;
+                if matches!(child, Node::Empty) {
+                    continue;
+                }
+                writeln!(out, "{indent}  [{i:x}]").unwrap();
+                dump_node(child, &format!("{path}{i:x}"), depth + 2, out);
+            }
+        }
+        Node::Extension { nibbles, child } => {
+            let nibbles = nibbles_hex(nibbles);
+            writeln!(out, "{indent}Extension {nibbles}").unwrap();
+            dump_node(&****child, &format!("{path}{nibbles}"), depth + 1, out);
+        }
+        Node::Leaf { nibbles, value } => {
+            let nibbles = nibbles_hex(nibbles);
+            write!(out, "{indent}Leaf {nibbles} key=0x{path}{nibbles}").unwrap();
+            // State Trie以外のリーフはアカウントとして読めないので、そのまま出す
+            match rlp::decode::<AccountRlp>(value) {
+                Ok(account) => writeln!(
+                    out,
+                    " nonce={} balance={} storage_root={:?} code_hash={:?}",
+                    account.nonce, account.balance, account.storage_root, account.code_hash
+                )
+                .unwrap(),
+                Err(_) => writeln!(out, " value=0x{}", hex::encode(value)).unwrap(),
+            }
+        }
+    }
+}
+
+/// Returns the nibbles as hex digits, without the `0x` prefix.
+fn nibbles_hex(nibbles: &Nibbles) -> String {
+    (0..nibbles.count)
+        .map(|i| format!("{:x}", nibbles.get_nibble(i)))
+        .collect()
+}
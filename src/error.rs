@@ -0,0 +1,155 @@
+use std::fmt;
+
+use eth_trie_utils::nibbles::Nibbles;
+use ethereum_types::{Address, H256, U256};
+
+/// The ways proving or verifying a transition can fail.
+#[derive(Debug)]
+pub enum ProveError {
+    /// The sender can't pay for the value and the gas of a transaction.
+    InsufficientBalance {
+        sender: Address,
+        balance: U256,
+        cost: U256,
+    },
+    /// An account the transition needs is missing from the state trie.
+    MissingAccount(Address),
+    /// A trie root differs from the one it was expected to be.
+    TrieRootMismatch {
+        /// The name of the root, e.g. `receipts_root`.
+        root: &'static str,
+        expected: H256,
+        got: H256,
+    },
+    /// The transaction was signed for another chain than the one of the block.
+    ChainIdMismatch {
+        txn: U256,
+        block: U256,
+    },
+    /// The signed transaction isn't the one the caller described.
+    InvalidTransaction(String),
+    /// The block metadata is inconsistent.
+    InvalidBlock(String),
+    /// The `GenerationInputs` break an invariant the prover relies on.
+    InvalidInputs(String),
+    /// The keys of the transactions trie aren't `rlp(0)`, `rlp(1)`, ... up to the transactions
+    /// before the proven one.
+    TransactionTrieKeyGap(String),
+    /// Required fields of `GenerationInputsBuilder` are missing or inconsistent, one message per
+    /// field.
+    IncompleteInputs(Vec<String>),
+    /// The accounts of a trie can't all be read, as the subtrie at this prefix is only known by
+    /// its hash.
+    HashedSubtrie(Nibbles),
+    /// A receipt is missing or doesn't have the expected content.
+    UnexpectedReceipt(String),
+    /// The parameters of a custom `FriConfig` can't be used together.
+    InvalidFriConfig(String),
+    /// The bytes aren't a summary encoded by `ProofSummary::to_bytes`.
+    InvalidSummary(String),
+    /// A transaction or an account couldn't be decoded.
+    Rlp(rlp::DecoderError),
+    Io(std::io::Error),
+    Json(serde_json::Error),
+    /// The prover failed, e.g. because the inputs don't match the claimed roots.
+    Proving(anyhow::Error),
+    /// The proof was rejected by the verifier.
+    Verification(anyhow::Error),
+    /// The proof at `index` of a batch was rejected by the verifier.
+    BatchVerification {
+        index: usize,
+        err: anyhow::Error,
+    },
+}
+
+pub type Result<T> = std::result::Result<T, ProveError>;
+
+impl fmt::Display for ProveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InsufficientBalance {
+                sender,
+                balance,
+                cost,
+            } => write!(
+                f,
+                "sender {sender:?} balance {balance} is insufficient to pay {cost}"
+            ),
+            Self::MissingAccount(addr) => {
+                write!(f, "account {addr:?} is missing from the state trie")
+            }
+            Self::TrieRootMismatch {
+                root,
+                expected,
+                got,
+            } => {
+                write!(f, "{root} {got:?} differs from the expected {expected:?}")
+            }
+            Self::ChainIdMismatch { txn, block } => write!(
+                f,
+                "transaction signed for chain {txn} is included in a block of chain {block}"
+            ),
+            Self::InvalidTransaction(msg) => write!(f, "invalid transaction: {msg}"),
+            Self::InvalidBlock(msg) => write!(f, "invalid block: {msg}"),
+            Self::InvalidInputs(msg) => write!(f, "invalid generation inputs: {msg}"),
+            Self::TransactionTrieKeyGap(msg) => {
+                write!(f, "unexpected transactions trie keys: {msg}")
+            }
+            Self::IncompleteInputs(problems) => {
+                write!(f, "incomplete generation inputs: {}", problems.join(", "))
+            }
+            Self::HashedSubtrie(prefix) => {
+                write!(f, "the subtrie at {prefix:?} is only known by its hash")
+            }
+            Self::UnexpectedReceipt(msg) => write!(f, "unexpected receipt: {msg}"),
+            Self::InvalidFriConfig(msg) => write!(f, "invalid FRI config: {msg}"),
+            Self::InvalidSummary(msg) => write!(f, "invalid proof summary: {msg}"),
+            Self::Rlp(err) => write!(f, "RLP decoding failed: {err}"),
+            Self::Io(err) => write!(f, "I/O error: {err}"),
+            Self::Json(err) => write!(f, "JSON error: {err}"),
+            Self::Proving(err) => write!(f, "proving failed: {err}"),
+            Self::Verification(err) => write!(f, "verification failed: {err}"),
+            Self::BatchVerification { index, err } => {
+                write!(f, "verification of proof {index} failed: {err}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ProveError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Rlp(err) => Some(err),
+            Self::Io(err) => Some(err),
+            Self::Json(err) => Some(err),
+            Self::Proving(err) | Self::Verification(err) => Some(err.as_ref()),
+            Self::BatchVerification { err, .. } => Some(err.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+/// plonky2 and plonky2_evm report their failures as `anyhow::Error`.
+impl From<anyhow::Error> for ProveError {
+    fn from(err: anyhow::Error) -> Self {
+        Self::Proving(err)
+    }
+}
+
+impl From<rlp::DecoderError> for ProveError {
+    fn from(err: rlp::DecoderError) -> Self {
+        Self::Rlp(err)
+    }
+}
+
+impl From<std::io::Error> for ProveError {
+    fn from(err: std::io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl From<serde_json::Error> for ProveError {
+    fn from(err: serde_json::Error) -> Self {
+        Self::Json(err)
+    }
+}
@@ -0,0 +1,33 @@
+use ethereum_types::H256;
+use plonky2::field::types::{Field, PrimeField64};
+
+use crate::error::{ProveError, Result};
+use crate::F;
+
+/// Splits `h` into the eight 32-bit limbs, least significant first, that plonky2_evm uses for the
+/// roots in the public inputs of its proofs.
+///
+/// A Goldilocks element holds less than 64 bits, so four of them can't represent every hash; the
+/// prover uses eight 32-bit limbs instead, and so does this.
+pub fn h256_to_fields(h: H256) -> [F; 8] {
+    // 末尾の4バイトが最下位のリム
+    std::array::from_fn(|i| {
+        let end = 32 - 4 * i;
+        let limb = u32::from_be_bytes(h.0[end - 4..end].try_into().unwrap());
+        F::from_canonical_u32(limb)
+    })
+}
+
+/// Joins the limbs returned by `h256_to_fields` back into a hash. Fails if a limb doesn't fit in
+/// 32 bits.
+pub fn fields_to_h256(limbs: [F; 8]) -> Result<H256> {
+    let mut h = H256::zero();
+    for (i, limb) in limbs.iter().enumerate() {
+        let limb = u32::try_from(limb.to_canonical_u64()).map_err(|_| {
+            ProveError::InvalidInputs(format!("limb {i} of a hash doesn't fit in 32 bits"))
+        })?;
+        let end = 32 - 4 * i;
+        h.0[end - 4..end].copy_from_slice(&limb.to_be_bytes());
+    }
+    Ok(h)
+}
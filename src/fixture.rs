@@ -0,0 +1,73 @@
+use eth_trie_utils::partial_trie::HashedPartialTrie;
+use ethereum_types::{Address, H160, H256, U256};
+use hex_literal::hex;
+use plonky2_evm::generation::mpt::AccountRlp;
+use plonky2_evm::generation::{GenerationInputs, TrieInputs};
+use plonky2_evm::Node;
+
+use crate::block::BlockMetadataBuilder;
+use crate::eth_to_wei;
+use crate::summary::ProofSummary;
+use crate::transfer::block_inputs;
+use crate::trie::StateTrieBuilder;
+use crate::txn::SignedTxn;
+
+/// The sender of the sample transfer.
+pub const SAMPLE_SENDER: Address = H160(hex!("2c7536e3605d9c16a7a3d7b1898e529396a65c23"));
+
+/// The sample transfer signed by `SAMPLE_SENDER` at nonce 5: 100 wei to
+/// `0xa0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0` with calldata `0x4242`, at a gas price of 10 and a
+/// gas limit of 22000.
+pub const SAMPLE_TXN: [u8; 99] = hex!("f861050a8255f094a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0648242421ba02c89eb757d9deeb1f5b3859a9d4d679951ef610ac47ad4608dc142beb1b7e313a05af7e9fbab825455d36c36c7f4cfcafbeafa9a77bdff936b52afb36d4fe4bcdd");
+
+/// Returns the inputs of the sample transfer of `simple-transfer.rs`: 100 wei from
+/// `0x2c7536e3605d9c16a7a3d7b1898e529396a65c23`, holding 100,000 ETH at nonce 5, to the new
+/// account `0xa0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0`, as the only transaction of block 1.
+///
+/// The transfer uses 21032 gas at the base fee, so the `0xdeadbeef..` beneficiary gets nothing.
+pub fn sample_transfer_inputs() -> GenerationInputs {
+    let sender = SAMPLE_SENDER;
+    let mut state_before = StateTrieBuilder::new();
+    state_before.insert(
+        sender,
+        AccountRlp {
+            nonce: 5.into(),
+            balance: eth_to_wei(100_000.into()),
+            ..AccountRlp::default()
+        },
+    );
+    let tries_before = TrieInputs {
+        state_trie: state_before.build(),
+        transactions_trie: HashedPartialTrie::from(Node::Empty),
+        receipts_trie: HashedPartialTrie::from(Node::Empty),
+        storage_tries: vec![],
+    };
+    // 21000 + calldata(0x4242)の2バイト * 16
+    let gas_used = U256::from(21032);
+    let block_metadata = BlockMetadataBuilder::new()
+        .beneficiary(H160(hex!("deadbeefdeadbeefdeadbeefdeadbeefdeadbeef")))
+        .gas_used(gas_used)
+        .build()
+        .expect("the sample block uses less gas than its limit");
+    let signed_txn = SignedTxn {
+        sender,
+        bytes: SAMPLE_TXN.to_vec(),
+        gas_used,
+    };
+    block_inputs(vec![signed_txn], tries_before, block_metadata)
+        .expect("the sample transfer is valid")
+        .remove(0)
+}
+
+/// Returns a summary with distinct, made-up values in every field, for tests of its encodings and
+/// comparisons that don't need a proof.
+pub fn sample_summary() -> ProofSummary {
+    ProofSummary {
+        state_root: H256::repeat_byte(0x01),
+        transactions_root: H256::repeat_byte(0x02),
+        receipts_root: H256::repeat_byte(0x03),
+        gas_used: 21032.into(),
+        block_number: 1.into(),
+        beneficiary: Address::repeat_byte(0xde),
+    }
+}
@@ -0,0 +1,72 @@
+use ethereum_types::U256;
+
+use crate::txn::{AccessListItem, TransferTxn, Txn};
+
+/// The gas every transaction pays before executing anything.
+pub const TX_BASE_GAS: u64 = 21_000;
+/// The gas per zero byte of calldata.
+pub const TX_DATA_ZERO_GAS: u64 = 4;
+/// The gas per non-zero byte of calldata.
+pub const TX_DATA_NONZERO_GAS: u64 = 16;
+/// The gas per address of an EIP-2930 access list.
+pub const ACCESS_LIST_ADDRESS_GAS: u64 = 2_400;
+/// The gas per storage key of an EIP-2930 access list.
+pub const ACCESS_LIST_STORAGE_KEY_GAS: u64 = 1_900;
+
+/// Returns the intrinsic gas of `txn`, which is all the gas a transfer to an EOA uses.
+pub fn intrinsic_gas(txn: &TransferTxn) -> U256 {
+    U256::from(TX_BASE_GAS + data_gas(&txn.data))
+}
+
+/// Like `intrinsic_gas`, for a transaction of any type: a typed one also pays for its access list.
+pub fn txn_intrinsic_gas(txn: &Txn) -> U256 {
+    U256::from(TX_BASE_GAS + data_gas(txn.data())) + access_list_gas(txn.access_list())
+}
+
+fn data_gas(data: &[u8]) -> u64 {
+    data.iter()
+        .map(|&byte| {
+            if byte == 0 {
+                TX_DATA_ZERO_GAS
+            } else {
+                TX_DATA_NONZERO_GAS
+            }
+        })
+        .sum()
+}
+
+/// Returns the gas paid upfront to warm the addresses and slots of `access_list`, which is
+/// added to the intrinsic gas of a typed transaction.
+pub fn access_list_gas(access_list: &[AccessListItem]) -> U256 {
+    let keys: usize = access_list.iter().map(|(_, keys)| keys.len()).sum();
+    U256::from(access_list.len()) * ACCESS_LIST_ADDRESS_GAS
+        + U256::from(keys) * ACCESS_LIST_STORAGE_KEY_GAS
+}
+
+/// Returns the fee paid for `gas_used` at `gas_price`.
+pub fn total_gas_cost(gas_used: U256, gas_price: U256) -> U256 {
+    gas_used * gas_price
+}
+
+/// Where the fee of a transaction goes under EIP-1559.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GasFee {
+    /// Debited from the sender: `gas_used * (base_fee + priority_fee)`.
+    pub paid: U256,
+    /// Credited to the block beneficiary: `gas_used * priority_fee`.
+    pub tip: U256,
+    /// Credited to no one: `gas_used * base_fee`.
+    pub burnt: U256,
+}
+
+/// Splits the fee of `gas_used` paid at `base_fee + priority_fee` per gas, where `priority_fee`
+/// is the effective gas price of the transaction minus the base fee.
+pub fn gas_fee(gas_used: U256, base_fee: U256, priority_fee: U256) -> GasFee {
+    let tip = total_gas_cost(gas_used, priority_fee);
+    let burnt = total_gas_cost(gas_used, base_fee);
+    GasFee {
+        paid: tip + burnt,
+        tip,
+        burnt,
+    }
+}
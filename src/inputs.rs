@@ -0,0 +1,197 @@
+use std::collections::HashMap;
+
+use eth_trie_utils::partial_trie::{HashedPartialTrie, PartialTrie};
+use ethereum_types::{Address, H256, U256};
+use keccak_hash::keccak;
+use plonky2_evm::generation::{GenerationInputs, TrieInputs};
+use plonky2_evm::proof::{BlockHashes, BlockMetadata, TrieRoots};
+use plonky2_evm::Node;
+
+use crate::error::{ProveError, Result};
+
+/// Builds `GenerationInputs`, checking at `build` time that the fields fit together.
+///
+/// Fields that have a sensible default (no withdrawals, an empty genesis state, zero block hashes,
+/// ...) can be left out; `tries`, `trie_roots_after`, `block_metadata` and `gas_used_after` can't.
+#[derive(Debug, Clone)]
+pub struct GenerationInputsBuilder {
+    signed_txn: Option<Vec<u8>>,
+    withdrawals: Vec<(Address, U256)>,
+    tries: Option<TrieInputs>,
+    trie_roots_after: Option<TrieRoots>,
+    contract_code: HashMap<H256, Vec<u8>>,
+    genesis_state_trie_root: H256,
+    block_metadata: Option<BlockMetadata>,
+    txn_number_before: U256,
+    gas_used_before: U256,
+    gas_used_after: Option<U256>,
+    block_bloom_before: [U256; 8],
+    block_bloom_after: [U256; 8],
+    block_hashes: BlockHashes,
+    addresses: Vec<Address>,
+}
+
+impl Default for GenerationInputsBuilder {
+    fn default() -> Self {
+        Self {
+            signed_txn: None,
+            withdrawals: vec![],
+            tries: None,
+            trie_roots_after: None,
+            // 空のコードはどのアカウントからも参照されうる
+            contract_code: HashMap::from([(keccak(vec![]), vec![])]),
+            genesis_state_trie_root: HashedPartialTrie::from(Node::Empty).hash(),
+            block_metadata: None,
+            txn_number_before: 0.into(),
+            gas_used_before: 0.into(),
+            gas_used_after: None,
+            block_bloom_before: [0.into(); 8],
+            block_bloom_after: [0.into(); 8],
+            block_hashes: BlockHashes {
+                prev_hashes: vec![H256::zero(); 256],
+                cur_hash: H256::zero(),
+            },
+            addresses: vec![],
+        }
+    }
+}
+
+impl GenerationInputsBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn signed_txn(mut self, signed_txn: Vec<u8>) -> Self {
+        self.signed_txn = Some(signed_txn);
+        self
+    }
+
+    pub fn withdrawals(mut self, withdrawals: Vec<(Address, U256)>) -> Self {
+        self.withdrawals = withdrawals;
+        self
+    }
+
+    pub fn tries(mut self, tries: TrieInputs) -> Self {
+        self.tries = Some(tries);
+        self
+    }
+
+    pub fn trie_roots_after(mut self, trie_roots_after: TrieRoots) -> Self {
+        self.trie_roots_after = Some(trie_roots_after);
+        self
+    }
+
+    /// Adds `code`, keyed by its hash.
+    pub fn contract_code(mut self, code: Vec<u8>) -> Self {
+        self.contract_code.insert(keccak(&code), code);
+        self
+    }
+
+    pub fn genesis_state_trie_root(mut self, root: H256) -> Self {
+        self.genesis_state_trie_root = root;
+        self
+    }
+
+    pub fn block_metadata(mut self, block_metadata: BlockMetadata) -> Self {
+        self.block_metadata = Some(block_metadata);
+        self
+    }
+
+    pub fn txn_number_before(mut self, txn_number_before: U256) -> Self {
+        self.txn_number_before = txn_number_before;
+        self
+    }
+
+    pub fn gas_used_before(mut self, gas_used_before: U256) -> Self {
+        self.gas_used_before = gas_used_before;
+        self
+    }
+
+    pub fn gas_used_after(mut self, gas_used_after: U256) -> Self {
+        self.gas_used_after = Some(gas_used_after);
+        self
+    }
+
+    pub fn block_bloom_before(mut self, bloom: [U256; 8]) -> Self {
+        self.block_bloom_before = bloom;
+        self
+    }
+
+    pub fn block_bloom_after(mut self, bloom: [U256; 8]) -> Self {
+        self.block_bloom_after = bloom;
+        self
+    }
+
+    pub fn block_hashes(mut self, block_hashes: BlockHashes) -> Self {
+        self.block_hashes = block_hashes;
+        self
+    }
+
+    pub fn addresses(mut self, addresses: Vec<Address>) -> Self {
+        self.addresses = addresses;
+        self
+    }
+
+    /// Returns the inputs, or `ProveError::IncompleteInputs` listing every missing or
+    /// inconsistent field.
+    pub fn build(self) -> Result<GenerationInputs> {
+        let mut problems = vec![];
+        if self.tries.is_none() {
+            problems.push("tries is not set".to_string());
+        }
+        if self.trie_roots_after.is_none() {
+            problems.push("trie_roots_after is not set".to_string());
+        }
+        if self.block_metadata.is_none() {
+            problems.push("block_metadata is not set".to_string());
+        }
+        match self.gas_used_after {
+            None => problems.push("gas_used_after is not set".to_string()),
+            Some(gas_used_after) => {
+                // ガスは取引ごとに増えるだけで、ブロック全体の使用量を超えない
+                if gas_used_after < self.gas_used_before {
+                    problems.push(format!(
+                        "gas_used_after {gas_used_after} is below gas_used_before {}",
+                        self.gas_used_before
+                    ));
+                }
+                if let Some(metadata) = &self.block_metadata {
+                    if gas_used_after > metadata.block_gas_used {
+                        problems.push(format!(
+                            "gas_used_after {gas_used_after} exceeds the block gas used {}",
+                            metadata.block_gas_used
+                        ));
+                    }
+                }
+            }
+        }
+
+        let (Some(tries), Some(trie_roots_after), Some(block_metadata), Some(gas_used_after)) = (
+            self.tries,
+            self.trie_roots_after,
+            self.block_metadata,
+            self.gas_used_after,
+        ) else {
+            return Err(ProveError::IncompleteInputs(problems));
+        };
+        if !problems.is_empty() {
+            return Err(ProveError::IncompleteInputs(problems));
+        }
+        Ok(GenerationInputs {
+            signed_txn: self.signed_txn,
+            withdrawals: self.withdrawals,
+            tries,
+            trie_roots_after,
+            contract_code: self.contract_code,
+            genesis_state_trie_root: self.genesis_state_trie_root,
+            block_metadata,
+            txn_number_before: self.txn_number_before,
+            gas_used_before: self.gas_used_before,
+            gas_used_after,
+            block_bloom_before: self.block_bloom_before,
+            block_bloom_after: self.block_bloom_after,
+            block_hashes: self.block_hashes,
+            addresses: self.addresses,
+        })
+    }
+}
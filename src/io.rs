@@ -0,0 +1,69 @@
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Write};
+use std::path::Path;
+
+use plonky2::plonk::config::GenericConfig;
+use plonky2::plonk::proof::ProofWithPublicInputs;
+use plonky2_evm::generation::GenerationInputs;
+
+use crate::error::Result;
+use crate::{D, F};
+
+/// Writes `proof` to `path` as JSON.
+pub fn save_proof<C: GenericConfig<D, F = F>>(
+    path: &Path,
+    proof: &ProofWithPublicInputs<F, C, D>,
+) -> Result<()> {
+    let writer = BufWriter::new(File::create(path)?);
+    serde_json::to_writer(writer, proof)?;
+    #[cfg(feature = "metrics")]
+    crate::metrics::record_proof_size(std::fs::metadata(path)?.len());
+    Ok(())
+}
+
+/// Returns the size in bytes of `proof` as written by `save_proof`, e.g. to budget the storage of
+/// many proofs, without writing it anywhere.
+///
+/// A `GeneratedProof` has no such size: the STARK proofs of its tables can't be serialized, only
+/// the recursive proof wrapping them, see `recursive_circuits`.
+pub fn proof_size_bytes<C: GenericConfig<D, F = F>>(
+    proof: &ProofWithPublicInputs<F, C, D>,
+) -> usize {
+    let mut counter = ByteCounter(0);
+    // 数えるだけなので、書き込みが失敗することはない
+    serde_json::to_writer(&mut counter, proof).expect("a proof can always be serialized");
+    counter.0
+}
+
+/// A writer that only counts the bytes written to it.
+struct ByteCounter(usize);
+
+impl Write for ByteCounter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0 += buf.len();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Reads a proof written by `save_proof`.
+pub fn load_proof<C: GenericConfig<D, F = F>>(
+    path: &Path,
+) -> Result<ProofWithPublicInputs<F, C, D>> {
+    let reader = BufReader::new(File::open(path)?);
+    Ok(serde_json::from_reader(reader)?)
+}
+
+/// Serializes `inputs` to JSON, e.g. to keep the exact inputs of a proof as a test vector.
+pub fn serialize_inputs(inputs: &GenerationInputs) -> Result<String> {
+    // plonky2_evmの型とHashedPartialTrieはserdeに対応しているので、ラッパーは不要
+    Ok(serde_json::to_string(inputs)?)
+}
+
+/// Reads `GenerationInputs` serialized by `serialize_inputs`.
+pub fn deserialize_inputs(json: &str) -> Result<GenerationInputs> {
+    Ok(serde_json::from_str(json)?)
+}
@@ -0,0 +1,108 @@
+use ethereum_types::U256;
+use plonky2::field::goldilocks_field::GoldilocksField;
+use plonky2::plonk::config::KeccakGoldilocksConfig;
+
+pub mod block;
+mod bloom;
+pub mod config;
+pub mod context;
+pub mod contract;
+pub mod diff;
+pub mod dump;
+pub mod error;
+pub mod fields;
+pub mod fixture;
+pub mod gas;
+pub mod inputs;
+pub mod io;
+pub mod logger;
+#[cfg(feature = "metrics")]
+pub mod metrics;
+pub mod nibbles;
+pub mod precompile;
+pub mod receipt;
+pub mod recursion;
+pub mod state;
+pub mod summary;
+#[cfg(feature = "test-utils")]
+pub mod test_utils;
+pub mod timing;
+pub mod trace;
+pub mod transfer;
+pub mod trie;
+pub mod txn;
+pub mod validate;
+pub mod verify;
+pub mod world;
+
+pub use block::{BlockBuilder, BlockHashBuilder, BlockMetadataBuilder};
+pub use bloom::{accumulate_block_bloom, compute_bloom, receipt_bloom};
+pub use config::{
+    fri_stark_config, secure_config, testing_config, ConfigPreset, EvmProof, HashConfig,
+};
+pub use context::ProverContext;
+pub use contract::{call_contract, contract_account, contract_address, deploy_contract};
+pub use diff::{balance_delta, diff_tries, format_balance_deltas, AccountDiff};
+pub use dump::dump_trie;
+pub use error::ProveError;
+pub use fields::{fields_to_h256, h256_to_fields};
+pub use fixture::{sample_summary, sample_transfer_inputs, SAMPLE_SENDER, SAMPLE_TXN};
+pub use gas::{access_list_gas, gas_fee, intrinsic_gas, total_gas_cost, txn_intrinsic_gas, GasFee};
+pub use inputs::GenerationInputsBuilder;
+pub use io::{deserialize_inputs, load_proof, proof_size_bytes, save_proof, serialize_inputs};
+pub use logger::init_logger;
+pub use nibbles::{
+    address_to_state_key_nibbles, index_key_nibbles, storage_key_nibbles, truncate_front,
+};
+pub use precompile::{is_precompile, precompile_gas};
+pub use receipt::{decode_receipt, ReceiptBuilder, ReceiptView};
+pub use recursion::{
+    aggregate_proofs, proof_block_number, proof_block_timestamp, proven_gas_used,
+    recursive_circuits, AggregatedProof, RecursiveConfig,
+};
+pub use state::{
+    apply_reverted_transfer, apply_simple_transfer, apply_transfer_txn, apply_withdrawals,
+    new_account,
+};
+pub use summary::ProofSummary;
+pub use timing::{PhaseTimer, PhaseTiming};
+pub use trace::{table_rows, TableRows, TABLE_NAMES};
+#[cfg(feature = "parallel")]
+pub use transfer::prove_blocks_parallel;
+pub use transfer::{
+    block_inputs, dry_run, prove_block, prove_empty_block, prove_generic, prove_inputs,
+    prove_inputs_timed, prove_inputs_with, prove_inputs_with_options, prove_inputs_with_progress,
+    prove_raw_transaction, prove_reverting_transfer, prove_transfer, prove_unchecked,
+    prove_with_fri, verify_generic, GeneratedProof, ProveOptions,
+};
+pub use trie::{
+    build_receipts_trie, build_transactions_trie, collect_accounts, compute_tx_and_receipt_roots,
+    genesis_tries, get_account, prune_to_touched, tries_from_accounts, StateTrieBuilder,
+    StorageTrieBuilder,
+};
+pub use txn::{
+    recover_sender, AccessListItem, Eip1559Txn, Eip2930Txn, SignedTxn, TransferTxn, Txn,
+};
+pub use validate::{validate_inputs, validate_storage_tries, validate_transaction_trie_keys};
+pub use verify::{assert_roots, check_receipt, verify_file, verify_many, verify_state_transition};
+pub use world::WorldState;
+
+pub type F = GoldilocksField;
+pub const D: usize = 2;
+pub type C = KeccakGoldilocksConfig;
+
+pub fn eth_to_wei(eth: U256) -> U256 {
+    // 1 ether = 10^18 wei.
+    eth * U256::from(10).pow(18.into())
+}
+
+/// Formats `wei` in ether, with up to 18 fractional digits and no trailing zeros.
+pub fn wei_to_eth_string(wei: U256) -> String {
+    let (eth, frac) = wei.div_mod(eth_to_wei(1.into()));
+    if frac.is_zero() {
+        return eth.to_string();
+    }
+    // 端数は10^18未満なのでu64に収まる。先頭の0を埋めてから末尾の0を削る
+    let frac = format!("{:018}", frac.as_u64());
+    format!("{eth}.{}", frac.trim_end_matches('0'))
+}
@@ -0,0 +1,17 @@
+use std::sync::Once;
+
+use env_logger::{Builder, Env, DEFAULT_FILTER_ENV};
+use log::LevelFilter;
+
+static INIT: Once = Once::new();
+
+/// Initializes `env_logger` with `level`, unless `RUST_LOG` is set.
+///
+/// Only the first call has an effect, so every test can call it.
+pub fn init_logger(level: LevelFilter) {
+    INIT.call_once(|| {
+        // 他で既にロガーが設定されていても失敗にはしない
+        let _ = Builder::from_env(Env::default().filter_or(DEFAULT_FILTER_ENV, level.as_str()))
+            .try_init();
+    });
+}
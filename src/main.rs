@@ -0,0 +1,149 @@
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{ensure, Context};
+use clap::{Parser, Subcommand};
+use eth_trie_utils::partial_trie::HashedPartialTrie;
+use ethereum_types::{Address, U256};
+use log::LevelFilter;
+use plonky2::util::timing::TimingTree;
+use plonky2_evm::all_stark::AllStark;
+use plonky2_evm::config::StarkConfig;
+use plonky2_evm::generation::mpt::AccountRlp;
+use plonky2_evm::generation::TrieInputs;
+use plonky2_evm::Node;
+use plonky2_sample::{
+    block_inputs, init_logger, recursive_circuits, save_proof, verify_file, BlockMetadataBuilder,
+    SignedTxn, StateTrieBuilder, Txn, D, F,
+};
+use serde::Deserialize;
+
+/// Proves a transfer described by a JSON file and writes the proof to disk, or verifies such a
+/// proof with `verify`.
+#[derive(Parser)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
+struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+    /// JSON file describing the transfer, see `examples/transfer.json`.
+    #[arg(required = true)]
+    input: Option<PathBuf>,
+    /// Where to write the proof.
+    #[arg(short, long, default_value = "proof.json")]
+    output: PathBuf,
+    /// Verify the proof right after proving it and print the state root.
+    #[arg(long)]
+    verify: bool,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Verify a proof written by this command, without proving again, and print its roots.
+    Verify {
+        /// The proof file.
+        proof: PathBuf,
+    },
+}
+
+#[derive(Deserialize)]
+struct TransferInput {
+    sender: Address,
+    to: Address,
+    value: U256,
+    sender_balance: U256,
+    #[serde(default)]
+    sender_nonce: U256,
+    /// The balance of `to` before the transfer, if it already exists.
+    #[serde(default)]
+    to_balance: Option<U256>,
+    #[serde(default)]
+    beneficiary: Address,
+    gas_used: U256,
+    /// The `0x`-prefixed hex of the signed transaction.
+    signed_txn: String,
+}
+
+fn main() -> anyhow::Result<()> {
+    init_logger(LevelFilter::Info);
+    let args = Args::parse();
+
+    if let Some(Command::Verify { proof }) = &args.command {
+        let summary =
+            verify_file(proof).with_context(|| format!("failed to verify {}", proof.display()))?;
+        println!("verified {}", proof.display());
+        println!("state root: {:?}", summary.state_root);
+        println!("transactions root: {:?}", summary.transactions_root);
+        println!("receipts root: {:?}", summary.receipts_root);
+        return Ok(());
+    }
+
+    // サブコマンドが無いときは、clapが入力ファイルを必須にしている
+    let input_path = args.input.as_ref().expect("the input is required");
+    let input: TransferInput = serde_json::from_str(
+        &fs::read_to_string(input_path)
+            .with_context(|| format!("failed to read {}", input_path.display()))?,
+    )?;
+    let signed_txn = hex::decode(input.signed_txn.trim_start_matches("0x"))?;
+    let txn = Txn::decode(&signed_txn)?;
+    ensure!(
+        txn.to() == Some(input.to) && txn.value() == input.value,
+        "the signed transaction doesn't transfer {} to {:?}",
+        input.value,
+        input.to
+    );
+
+    let mut state_before = StateTrieBuilder::new();
+    state_before.insert(
+        input.sender,
+        AccountRlp {
+            nonce: input.sender_nonce,
+            balance: input.sender_balance,
+            ..AccountRlp::default()
+        },
+    );
+    if let Some(balance) = input.to_balance {
+        state_before.insert(
+            input.to,
+            AccountRlp {
+                balance,
+                ..AccountRlp::default()
+            },
+        );
+    }
+    let tries_before = TrieInputs {
+        state_trie: state_before.build(),
+        transactions_trie: HashedPartialTrie::from(Node::Empty),
+        receipts_trie: HashedPartialTrie::from(Node::Empty),
+        storage_tries: vec![],
+    };
+    let block_metadata = BlockMetadataBuilder::new()
+        .beneficiary(input.beneficiary)
+        .gas_used(input.gas_used)
+        .build()?;
+    let signed_txn = SignedTxn {
+        sender: input.sender,
+        bytes: signed_txn,
+        gas_used: input.gas_used,
+    };
+    let inputs = block_inputs(vec![signed_txn], tries_before, block_metadata)?.remove(0);
+
+    // ファイルに保存できる形にするため、再帰証明で包む
+    let all_stark = AllStark::<F, D>::default();
+    let config = StarkConfig::standard_fast_config();
+    let circuits = recursive_circuits(&all_stark, &config);
+    let mut timing = TimingTree::new("prove root", log::Level::Info);
+    let (proof, public_values) = circuits.prove_root(&all_stark, &config, inputs, &mut timing)?;
+    timing.print();
+
+    save_proof(&args.output, &proof)?;
+    println!("proof written to {}", args.output.display());
+
+    if args.verify {
+        circuits.verify_root(proof)?;
+        println!(
+            "verified, state root: {:?}",
+            public_values.trie_roots_after.state_root
+        );
+    }
+    Ok(())
+}
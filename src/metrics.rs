@@ -0,0 +1,34 @@
+use std::time::Duration;
+
+use metrics::{gauge, histogram};
+
+use crate::config::ConfigPreset;
+
+/// The histogram of the seconds spent proving, labelled by `preset`.
+pub const PROVING_SECONDS: &str = "plonky2_sample_proving_seconds";
+/// The histogram of the seconds spent verifying, labelled by `preset`.
+pub const VERIFICATION_SECONDS: &str = "plonky2_sample_verification_seconds";
+/// The gauge of the size in bytes of the last proof written by `save_proof`.
+pub const PROOF_BYTES: &str = "plonky2_sample_proof_bytes";
+
+pub(crate) fn record_proving(preset: ConfigPreset, duration: Duration) {
+    histogram!(
+        PROVING_SECONDS,
+        duration.as_secs_f64(),
+        "preset" => format!("{preset:?}")
+    );
+}
+
+pub(crate) fn record_verification(preset: ConfigPreset, duration: Duration) {
+    histogram!(
+        VERIFICATION_SECONDS,
+        duration.as_secs_f64(),
+        "preset" => format!("{preset:?}")
+    );
+}
+
+/// Records the size of a proof written by `save_proof`. Recursive proofs don't know the preset of
+/// the proofs they wrap, so this one isn't labelled.
+pub(crate) fn record_proof_size(bytes: u64) {
+    gauge!(PROOF_BYTES, bytes as f64);
+}
@@ -0,0 +1,33 @@
+use eth_trie_utils::nibbles::Nibbles;
+use ethereum_types::{Address, H256};
+use keccak_hash::keccak;
+
+/// Returns the nibbles of the state key of `addr`, i.e. of `keccak(addr)`, under which its
+/// account is stored in the state trie.
+pub fn address_to_state_key_nibbles(addr: Address) -> Nibbles {
+    hashed_key_nibbles(keccak(addr))
+}
+
+/// Returns the nibbles of the key of `slot` in a storage trie, i.e. of `keccak(slot)`.
+pub fn storage_key_nibbles(slot: H256) -> Nibbles {
+    hashed_key_nibbles(keccak(slot))
+}
+
+/// Returns the key of the `index`-th entry of a transactions or receipts trie, i.e. `rlp(index)`.
+///
+/// Indices up to 127 are a single byte (with 0 encoded as `0x80`), larger ones get a length
+/// prefix, e.g. 128 is `0x8180`.
+pub fn index_key_nibbles(index: usize) -> Nibbles {
+    Nibbles::from_bytes_be(&rlp::encode(&index))
+        .expect("the RLP of an index always converts to nibbles")
+}
+
+/// Returns `nibbles` without its first `n` nibbles, e.g. the rest of a key below a branch.
+pub fn truncate_front(nibbles: Nibbles, n: usize) -> Nibbles {
+    nibbles.truncate_n_nibbles_front(n)
+}
+
+fn hashed_key_nibbles(hash: H256) -> Nibbles {
+    // 32バイトのキーは必ずNibblesに収まる
+    Nibbles::from_bytes_be(hash.as_bytes()).expect("a 32-byte key always converts to nibbles")
+}
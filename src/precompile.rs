@@ -0,0 +1,37 @@
+use ethereum_types::{Address, U256};
+
+/// The gas of the ecrecover precompile at 0x01.
+pub const ECRECOVER_GAS: u64 = 3_000;
+/// The base gas and gas per 32-byte word of input of the SHA-256 precompile at 0x02.
+pub const SHA256_GAS: (u64, u64) = (60, 12);
+/// The base gas and gas per 32-byte word of input of the RIPEMD-160 precompile at 0x03.
+pub const RIPEMD160_GAS: (u64, u64) = (600, 120);
+/// The base gas and gas per 32-byte word of input of the identity precompile at 0x04.
+pub const IDENTITY_GAS: (u64, u64) = (15, 3);
+
+/// Returns whether `addr` is one of the precompiled contracts, 0x01 to 0x09.
+///
+/// A precompile has no code in the state trie: a transfer to it is credited like one to an EOA,
+/// but the call also runs the precompile on the transaction data and pays for it.
+pub fn is_precompile(addr: Address) -> bool {
+    (1..=9).contains(&addr.to_low_u64_be()) && addr.0[..12] == [0; 12]
+}
+
+/// Returns the gas used by calling the precompile `addr` with `input`, on top of the intrinsic
+/// gas, or `None` if `addr` isn't a precompile whose cost only depends on the input length
+/// (0x01 to 0x04).
+pub fn precompile_gas(addr: Address, input: &[u8]) -> Option<U256> {
+    if !is_precompile(addr) {
+        return None;
+    }
+    let words = input.len().div_ceil(32) as u64;
+    // 0x05以降(modexp、楕円曲線、blake2f)は入力の中身で値段が変わる
+    let (base, per_word) = match addr.to_low_u64_be() {
+        1 => (ECRECOVER_GAS, 0),
+        2 => SHA256_GAS,
+        3 => RIPEMD160_GAS,
+        4 => IDENTITY_GAS,
+        _ => return None,
+    };
+    Some(U256::from(base + per_word * words))
+}
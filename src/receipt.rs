@@ -0,0 +1,123 @@
+use eth_trie_utils::partial_trie::{HashedPartialTrie, PartialTrie};
+use ethereum_types::{Address, Bloom, H256, U256};
+use plonky2_evm::generation::mpt::{LegacyReceiptRlp, LogRlp};
+
+use crate::bloom::receipt_bloom;
+use crate::error::{ProveError, Result};
+use crate::nibbles::index_key_nibbles;
+
+/// Builds a `LegacyReceiptRlp`, computing its bloom from its logs, e.g. to build a receipts trie
+/// with `build_receipts_trie`.
+#[derive(Debug, Clone)]
+pub struct ReceiptBuilder {
+    receipt: LegacyReceiptRlp,
+}
+
+impl Default for ReceiptBuilder {
+    fn default() -> Self {
+        Self {
+            receipt: LegacyReceiptRlp {
+                status: true,
+                cum_gas_used: U256::zero(),
+                bloom: vec![].into(),
+                logs: vec![],
+            },
+        }
+    }
+}
+
+impl ReceiptBuilder {
+    /// Starts a receipt of a successful transaction without logs.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn status(mut self, status: bool) -> Self {
+        self.receipt.status = status;
+        self
+    }
+
+    /// Sets the gas used by the block up to and including the transaction.
+    pub fn cumulative_gas(mut self, cum_gas_used: U256) -> Self {
+        self.receipt.cum_gas_used = cum_gas_used;
+        self
+    }
+
+    /// Appends a log emitted by `address`.
+    pub fn add_log(mut self, address: Address, topics: Vec<H256>, data: Vec<u8>) -> Self {
+        self.receipt.logs.push(LogRlp {
+            address,
+            topics,
+            data: data.into(),
+        });
+        self
+    }
+
+    pub fn build(mut self) -> LegacyReceiptRlp {
+        // ブルームはログから決まるので、最後にまとめて計算する
+        self.receipt.bloom = receipt_bloom(&self.receipt).as_bytes().to_vec().into();
+        self.receipt
+    }
+}
+
+/// Decodes a receipt as stored in a receipts trie, with the type byte of a typed transaction's
+/// receipt if any.
+pub fn decode_receipt(bytes: &[u8]) -> Result<LegacyReceiptRlp> {
+    // タイプ付きトランザクションのレシートは、RLPのリストの前にタイプのバイトが付く
+    let bytes = match bytes.first() {
+        Some(&txn_type) if txn_type < 0xc0 => &bytes[1..],
+        _ => bytes,
+    };
+    let receipt: LegacyReceiptRlp = rlp::decode(bytes)?;
+    if receipt.bloom.len() != Bloom::len_bytes() {
+        return Err(ProveError::UnexpectedReceipt(format!(
+            "the bloom has {} bytes, expected {}",
+            receipt.bloom.len(),
+            Bloom::len_bytes()
+        )));
+    }
+    Ok(receipt)
+}
+
+/// A decoded receipt, read back e.g. from the receipts trie of a proven block.
+#[derive(Debug, Clone)]
+pub struct ReceiptView {
+    receipt: LegacyReceiptRlp,
+}
+
+impl ReceiptView {
+    /// Decodes a receipt, see `decode_receipt`.
+    pub fn decode(bytes: &[u8]) -> Result<Self> {
+        decode_receipt(bytes).map(|receipt| Self { receipt })
+    }
+
+    /// Decodes the receipt of the `index`-th transaction of the block of `receipts_trie`.
+    pub fn from_trie(receipts_trie: &HashedPartialTrie, index: usize) -> Result<Self> {
+        let bytes = receipts_trie
+            .get(index_key_nibbles(index))
+            .ok_or_else(|| ProveError::UnexpectedReceipt(format!("no receipt at index {index}")))?;
+        Self::decode(bytes)
+    }
+
+    /// Whether the transaction succeeded.
+    pub fn status(&self) -> bool {
+        self.receipt.status
+    }
+
+    /// The gas used by the block up to and including the transaction.
+    pub fn cumulative_gas_used(&self) -> U256 {
+        self.receipt.cum_gas_used
+    }
+
+    pub fn bloom(&self) -> Bloom {
+        Bloom::from_slice(&self.receipt.bloom)
+    }
+
+    pub fn logs(&self) -> &[LogRlp] {
+        &self.receipt.logs
+    }
+
+    pub fn into_inner(self) -> LegacyReceiptRlp {
+        self.receipt
+    }
+}
@@ -0,0 +1,111 @@
+use std::ops::Range;
+
+use ethereum_types::U256;
+use plonky2::plonk::config::{GenericConfig, PoseidonGoldilocksConfig};
+use plonky2::plonk::proof::ProofWithPublicInputs;
+use plonky2_evm::all_stark::{AllStark, NUM_TABLES};
+use plonky2_evm::config::StarkConfig;
+use plonky2_evm::fixed_recursive_verifier::AllRecursiveCircuits;
+use plonky2_evm::proof::{ExtraBlockData, PublicValues};
+
+use crate::error::{ProveError, Result};
+use crate::verify::compare_roots;
+use crate::{D, F};
+
+/// The config of recursive proofs. The recursion circuits verify hashes in-circuit, so they need
+/// an algebraic hasher and can't use the Keccak-based `C`.
+pub type RecursiveConfig = PoseidonGoldilocksConfig;
+
+/// The range of degree bits, per STARK table, of the proofs the recursion circuits can wrap.
+/// They cover the tables of a simple transfer; `AllProof::degree_bits` gives the actual values.
+pub const DEGREE_BITS_RANGES: [Range<usize>; NUM_TABLES] =
+    [16..17, 10..13, 14..17, 14..15, 9..11, 12..13, 17..20];
+
+/// Builds the recursion circuits. This is slow and is best done once per process.
+pub fn recursive_circuits(
+    all_stark: &AllStark<F, D>,
+    config: &StarkConfig,
+) -> AllRecursiveCircuits<F, RecursiveConfig, D> {
+    AllRecursiveCircuits::new(all_stark, &DEGREE_BITS_RANGES, config)
+}
+
+/// A proof attesting several consecutive transitions, with the public values spanning all of them.
+#[derive(Debug, Clone)]
+pub struct AggregatedProof {
+    pub proof: ProofWithPublicInputs<F, RecursiveConfig, D>,
+    pub public_values: PublicValues,
+}
+
+impl AggregatedProof {
+    /// Verifies the proof with the circuits that aggregated it.
+    pub fn verify(&self, circuits: &AllRecursiveCircuits<F, RecursiveConfig, D>) -> Result<()> {
+        circuits
+            .verify_aggregation(&self.proof)
+            .map_err(ProveError::Verification)
+    }
+}
+
+/// Aggregates two root proofs, as returned by `AllRecursiveCircuits::prove_root`, into one.
+///
+/// `right` must start from the tries `left` ends with, e.g. two consecutive transactions of a
+/// block.
+pub fn aggregate_proofs(
+    circuits: &AllRecursiveCircuits<F, RecursiveConfig, D>,
+    left: (ProofWithPublicInputs<F, RecursiveConfig, D>, PublicValues),
+    right: (ProofWithPublicInputs<F, RecursiveConfig, D>, PublicValues),
+) -> Result<AggregatedProof> {
+    let (left_proof, left_values) = left;
+    let (right_proof, right_values) = right;
+    compare_roots(
+        &left_values.trie_roots_after,
+        &right_values.trie_roots_before,
+    )?;
+
+    // 集約した証明は、左の前の状態から右の後の状態までの遷移を表す
+    let public_values = PublicValues {
+        trie_roots_before: left_values.trie_roots_before,
+        extra_block_data: ExtraBlockData {
+            txn_number_before: left_values.extra_block_data.txn_number_before,
+            gas_used_before: left_values.extra_block_data.gas_used_before,
+            block_bloom_before: left_values.extra_block_data.block_bloom_before,
+            ..right_values.extra_block_data
+        },
+        ..right_values
+    };
+    // どちらもルート証明なので、lhs_is_agg と rhs_is_agg は false
+    let (proof, public_values) =
+        circuits.prove_aggregation(false, &left_proof, false, &right_proof, public_values)?;
+    Ok(AggregatedProof {
+        proof,
+        public_values,
+    })
+}
+
+/// Returns the gas used by the block up to and including the transactions proven by `proof`, a
+/// root or aggregation proof, as computed by the EVM rather than as supplied in the inputs.
+pub fn proven_gas_used<C: GenericConfig<D, F = F>>(proof: &ProofWithPublicInputs<F, C, D>) -> U256 {
+    // 公開入力の先頭に、公開値がそのまま並んでいる
+    PublicValues::from_public_inputs(&proof.public_inputs)
+        .extra_block_data
+        .gas_used_after
+}
+
+/// Returns the number of the block of the transactions proven by `proof`, a root or aggregation
+/// proof, e.g. to index proofs by block.
+pub fn proof_block_number<C: GenericConfig<D, F = F>>(
+    proof: &ProofWithPublicInputs<F, C, D>,
+) -> U256 {
+    PublicValues::from_public_inputs(&proof.public_inputs)
+        .block_metadata
+        .block_number
+}
+
+/// Returns the timestamp of the block of the transactions proven by `proof`, a root or
+/// aggregation proof.
+pub fn proof_block_timestamp<C: GenericConfig<D, F = F>>(
+    proof: &ProofWithPublicInputs<F, C, D>,
+) -> U256 {
+    PublicValues::from_public_inputs(&proof.public_inputs)
+        .block_metadata
+        .block_timestamp
+}
@@ -0,0 +1,226 @@
+use eth_trie_utils::partial_trie::{HashedPartialTrie, PartialTrie};
+use ethereum_types::{Address, U256};
+use plonky2_evm::generation::mpt::AccountRlp;
+use plonky2_evm::proof::BlockMetadata;
+
+use crate::error::{ProveError, Result};
+use crate::gas::gas_fee;
+use crate::nibbles::address_to_state_key_nibbles;
+use crate::trie::get_account;
+use crate::txn::{SignedTxn, Txn};
+
+/// Applies a plain value transfer to `before` and returns the resulting state trie.
+///
+/// The sender pays `value + gas_used * gas_price` and its nonce is incremented; the receiver is
+/// credited `value`, and created if it doesn't exist yet unless `value` is zero. If
+/// `sender == to`, the account only pays for the gas, but must still afford `value` on top of it.
+///
+/// Neither account needs to be an EOA: a contract sender, like an existing receiver, keeps its
+/// code and storage, and a precompile is credited like any other address, see `is_precompile`.
+/// Only `gas_used` accounts for what the receiver executes.
+pub fn apply_simple_transfer(
+    before: &HashedPartialTrie,
+    sender: Address,
+    to: Address,
+    value: U256,
+    gas_used: U256,
+    gas_price: U256,
+) -> Result<HashedPartialTrie> {
+    let mut after = before.clone();
+
+    let sender_account = get_account(before, sender)?.ok_or(ProveError::MissingAccount(sender))?;
+    let cost = checked_cost(sender, sender_account.balance, value, gas_used, gas_price)?;
+    // 自分自身への送金では送った分がそのまま戻ってくるので、減るのはガス代だけ。
+    // 同じキーに2回書き込まないよう、1つのアカウントとして更新する
+    let sender_account_after = AccountRlp {
+        balance: if sender == to {
+            sender_account.balance - (cost - value)
+        } else {
+            sender_account.balance - cost
+        },
+        nonce: next_nonce(sender, sender_account.nonce)?,
+        ..sender_account
+    };
+    after.insert(
+        address_to_state_key_nibbles(sender),
+        rlp::encode(&sender_account_after).to_vec(),
+    );
+
+    // 受信者のアカウントがまだ無ければ、残高だけを持つ新しいアカウントになる。
+    // ただし0の送金では、EIP-161により空のアカウントは作られない
+    if sender != to && !value.is_zero() {
+        credit(&mut after, to, value)?;
+    }
+
+    Ok(after)
+}
+
+/// Applies a transfer whose execution reverts, e.g. a call to a contract that runs `REVERT`, to
+/// `before` and returns the resulting state trie.
+///
+/// The sender only pays `gas_used * gas_price` and its nonce is incremented; the value isn't
+/// transferred and the receiver is unchanged. The sender must still afford `value` on top of the
+/// gas for the transaction to be valid.
+pub fn apply_reverted_transfer(
+    before: &HashedPartialTrie,
+    sender: Address,
+    value: U256,
+    gas_used: U256,
+    gas_price: U256,
+) -> Result<HashedPartialTrie> {
+    let mut after = before.clone();
+
+    let sender_account = get_account(before, sender)?.ok_or(ProveError::MissingAccount(sender))?;
+    let cost = checked_cost(sender, sender_account.balance, value, gas_used, gas_price)?;
+    // 実行は巻き戻されるが、消費したガスの支払いとnonceの増加は残る
+    let sender_account_after = AccountRlp {
+        balance: sender_account.balance - (cost - value),
+        nonce: next_nonce(sender, sender_account.nonce)?,
+        ..sender_account
+    };
+    after.insert(
+        address_to_state_key_nibbles(sender),
+        rlp::encode(&sender_account_after).to_vec(),
+    );
+
+    Ok(after)
+}
+
+/// Applies `signed_txn`, a transfer included in a block with `block_metadata`, to `before` and
+/// returns the resulting state trie.
+///
+/// The gas is paid at the effective gas price of the transaction under the block's base fee. The
+/// base fee is burnt and the rest, the priority fee, is credited to the block beneficiary, see
+/// `gas_fee`.
+pub fn apply_transfer_txn(
+    before: &HashedPartialTrie,
+    signed_txn: &SignedTxn,
+    block_metadata: &BlockMetadata,
+) -> Result<HashedPartialTrie> {
+    apply_txn_state(before, signed_txn, block_metadata, true)
+}
+
+/// Like `apply_transfer_txn`, but if `status` is false the execution reverted, see
+/// `apply_reverted_transfer`. The beneficiary is paid in both cases.
+pub(crate) fn apply_txn_state(
+    before: &HashedPartialTrie,
+    signed_txn: &SignedTxn,
+    block_metadata: &BlockMetadata,
+    status: bool,
+) -> Result<HashedPartialTrie> {
+    let txn = Txn::decode(&signed_txn.bytes)?;
+    let to = txn
+        .to()
+        .ok_or_else(|| ProveError::InvalidTransaction("it is a contract creation".into()))?;
+    let base_fee = block_metadata.block_base_fee;
+    let gas_price = txn.effective_gas_price(base_fee);
+    if gas_price < base_fee {
+        return Err(ProveError::InvalidTransaction(format!(
+            "it pays {gas_price} per gas, below the base fee {base_fee}"
+        )));
+    }
+
+    let mut after = if status {
+        apply_simple_transfer(
+            before,
+            signed_txn.sender,
+            to,
+            txn.value(),
+            signed_txn.gas_used,
+            gas_price,
+        )?
+    } else {
+        apply_reverted_transfer(
+            before,
+            signed_txn.sender,
+            txn.value(),
+            signed_txn.gas_used,
+            gas_price,
+        )?
+    };
+    // 送信者はgas_priceの全額を払うが、beneficiaryが受け取るのは優先手数料だけで、
+    // ベースフィーの分はどのアカウントにも入らずに燃やされる
+    let fee = gas_fee(signed_txn.gas_used, base_fee, gas_price - base_fee);
+    if !fee.tip.is_zero() {
+        credit(&mut after, block_metadata.block_beneficiary, fee.tip)?;
+    }
+    Ok(after)
+}
+
+/// Credits each `(address, amount in wei)` withdrawal to its account, creating it if needed.
+pub fn apply_withdrawals(
+    before: &HashedPartialTrie,
+    withdrawals: &[(Address, U256)],
+) -> Result<HashedPartialTrie> {
+    let mut after = before.clone();
+    for &(addr, amount) in withdrawals {
+        credit(&mut after, addr, amount)?;
+    }
+    Ok(after)
+}
+
+/// Returns the account created by sending `balance` to an address without an account.
+///
+/// It has a nonce of 0, and the roots of the empty storage trie and of the empty code rather than
+/// zeros, like `AccountRlp::default()`.
+pub fn new_account(balance: U256) -> AccountRlp {
+    AccountRlp {
+        balance,
+        ..AccountRlp::default()
+    }
+}
+
+/// Adds `amount` to the balance of `addr` in `trie`, creating the account if needed.
+pub(crate) fn credit(trie: &mut HashedPartialTrie, addr: Address, amount: U256) -> Result<()> {
+    let account_after = match get_account(trie, addr)? {
+        // 既存のアカウントは残高だけが変わり、nonceやコードはそのまま
+        Some(account) => {
+            let balance = account.balance.checked_add(amount).ok_or_else(|| {
+                ProveError::InvalidInputs(format!(
+                    "crediting {amount} to {addr:?} overflows its balance"
+                ))
+            })?;
+            AccountRlp { balance, ..account }
+        }
+        None => new_account(amount),
+    };
+    trie.insert(
+        address_to_state_key_nibbles(addr),
+        rlp::encode(&account_after).to_vec(),
+    );
+    Ok(())
+}
+
+/// Returns `value + gas_used * gas_price` if `sender` can afford it with `balance`.
+///
+/// A cost that overflows can't be afforded, and is reported as `U256::MAX`.
+pub(crate) fn checked_cost(
+    sender: Address,
+    balance: U256,
+    value: U256,
+    gas_used: U256,
+    gas_price: U256,
+) -> Result<U256> {
+    let cost = gas_used
+        .checked_mul(gas_price)
+        .and_then(|gas_cost| gas_cost.checked_add(value));
+    match cost {
+        Some(cost) if cost <= balance => Ok(cost),
+        cost => Err(ProveError::InsufficientBalance {
+            sender,
+            balance,
+            cost: cost.unwrap_or(U256::MAX),
+        }),
+    }
+}
+
+/// Returns the nonce of `addr` after sending a transaction with `nonce`.
+pub(crate) fn next_nonce(addr: Address, nonce: U256) -> Result<U256> {
+    // EIP-2681により、nonceは2^64 - 1に達したらそれ以上増やせない
+    if nonce >= U256::from(u64::MAX) {
+        return Err(ProveError::InvalidTransaction(format!(
+            "the nonce {nonce} of {addr:?} can't be incremented"
+        )));
+    }
+    Ok(nonce + 1)
+}
@@ -0,0 +1,131 @@
+use std::fmt;
+
+use ethereum_types::{Address, H256, U256};
+use plonky2_evm::proof::PublicValues;
+
+use crate::error::{ProveError, Result};
+use crate::transfer::GeneratedProof;
+
+/// The version of the layout written by `ProofSummary::to_bytes`.
+const SUMMARY_VERSION: u8 = 1;
+/// The length of the encoding: the version, 3 roots, 2 words and an address.
+const SUMMARY_LEN: usize = 1 + 3 * 32 + 2 * 32 + 20;
+
+/// The public values of a proof most callers care about.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProofSummary {
+    pub state_root: H256,
+    pub transactions_root: H256,
+    pub receipts_root: H256,
+    /// The gas used by the block up to and including the proven transactions.
+    pub gas_used: U256,
+    pub block_number: U256,
+    pub beneficiary: Address,
+}
+
+impl ProofSummary {
+    pub fn from_proof(proof: &GeneratedProof) -> Self {
+        Self::from_public_values(&proof.public_values)
+    }
+
+    pub fn from_public_values(public_values: &PublicValues) -> Self {
+        let trie_roots_after = &public_values.trie_roots_after;
+        Self {
+            state_root: trie_roots_after.state_root,
+            transactions_root: trie_roots_after.transactions_root,
+            receipts_root: trie_roots_after.receipts_root,
+            gas_used: public_values.extra_block_data.gas_used_after,
+            block_number: public_values.block_metadata.block_number,
+            beneficiary: public_values.block_metadata.block_beneficiary,
+        }
+    }
+
+    /// Encodes the summary in a compact layout that won't change between versions of this crate,
+    /// e.g. to log it or send it to another service without the proof.
+    ///
+    /// The layout is a version byte, the state, transactions and receipts roots, the gas used and
+    /// the block number as 32-byte little-endian words, and the beneficiary.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(SUMMARY_LEN);
+        bytes.push(SUMMARY_VERSION);
+        bytes.extend_from_slice(self.state_root.as_bytes());
+        bytes.extend_from_slice(self.transactions_root.as_bytes());
+        bytes.extend_from_slice(self.receipts_root.as_bytes());
+        let mut word = [0u8; 32];
+        for value in [self.gas_used, self.block_number] {
+            value.to_little_endian(&mut word);
+            bytes.extend_from_slice(&word);
+        }
+        bytes.extend_from_slice(self.beneficiary.as_bytes());
+        bytes
+    }
+
+    /// Decodes a summary encoded by `to_bytes`.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        // 途中で切れた入力でパニックしないよう、先に長さを確認する
+        if bytes.len() != SUMMARY_LEN {
+            return Err(ProveError::InvalidSummary(format!(
+                "it is {} bytes long instead of {SUMMARY_LEN}",
+                bytes.len()
+            )));
+        }
+        if bytes[0] != SUMMARY_VERSION {
+            return Err(ProveError::InvalidSummary(format!(
+                "unknown version {}",
+                bytes[0]
+            )));
+        }
+        let (roots, rest) = bytes[1..].split_at(3 * 32);
+        let (words, beneficiary) = rest.split_at(2 * 32);
+        Ok(Self {
+            state_root: H256::from_slice(&roots[..32]),
+            transactions_root: H256::from_slice(&roots[32..64]),
+            receipts_root: H256::from_slice(&roots[64..]),
+            gas_used: U256::from_little_endian(&words[..32]),
+            block_number: U256::from_little_endian(&words[32..]),
+            beneficiary: Address::from_slice(beneficiary),
+        })
+    }
+
+    /// Checks that `other` has the same public values, e.g. to compare the outputs of two
+    /// implementations, and names the first field that differs.
+    pub fn assert_eq(&self, other: &ProofSummary) -> std::result::Result<(), String> {
+        // 違いを読みやすくするため、フィールドごとに比べる
+        macro_rules! compare {
+            ($($field:ident),*) => {
+                $(
+                    if self.$field != other.$field {
+                        return Err(format!(
+                            "{} differs: {:?} != {:?}",
+                            stringify!($field),
+                            self.$field,
+                            other.$field
+                        ));
+                    }
+                )*
+            };
+        }
+        compare!(
+            state_root,
+            transactions_root,
+            receipts_root,
+            gas_used,
+            block_number,
+            beneficiary
+        );
+        Ok(())
+    }
+}
+
+impl fmt::Display for ProofSummary {
+    /// Writes one field per line, with the roots and the beneficiary in full `0x`-prefixed hex.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // H256のDisplayは途中を省略するので、LowerHexで全桁を書く
+        writeln!(f, "block number: {}", self.block_number)?;
+        writeln!(f, "beneficiary: {:#x}", self.beneficiary)?;
+        writeln!(f, "gas used: {}", self.gas_used)?;
+        writeln!(f, "state root: {:#x}", self.state_root)?;
+        writeln!(f, "transactions root: {:#x}", self.transactions_root)?;
+        write!(f, "receipts root: {:#x}", self.receipts_root)
+    }
+}
@@ -0,0 +1,32 @@
+use plonky2::field::extension::Extendable;
+use plonky2::field::types::Field;
+use plonky2::plonk::config::GenericConfig;
+use plonky2::plonk::proof::ProofWithPublicInputs;
+use plonky2_evm::generation::GenerationInputs;
+
+use crate::context::ProverContext;
+use crate::error::Result;
+use crate::summary::ProofSummary;
+use crate::{D, F};
+
+/// Changes one of the wire values opened by `proof`, so that it no longer matches the wires
+/// committed to, to check that verification rejects a tampered proof.
+pub fn tamper_proof<C: GenericConfig<D, F = F>>(proof: &mut ProofWithPublicInputs<F, C, D>) {
+    // 公開入力ではなく、コミットされたデータの開示値を書き換える
+    proof.proof.openings.wires[0] += <F as Extendable<D>>::Extension::ONE;
+}
+
+/// Proves `inputs` twice and asserts that both proofs commit to the same public values, which
+/// are returned.
+///
+/// Only the public values are compared: the proofs themselves needn't be identical.
+pub fn assert_deterministic_public_values(inputs: GenerationInputs) -> Result<ProofSummary> {
+    // AllStarkは2回の証明で共有する
+    let context = ProverContext::default();
+    let first = context.prove(inputs.clone())?.summary();
+    let second = context.prove(inputs)?.summary();
+    if let Err(msg) = first.assert_eq(&second) {
+        panic!("proving the same inputs twice gave different public values: {msg}");
+    }
+    Ok(first)
+}
@@ -0,0 +1,45 @@
+use std::time::{Duration, Instant};
+
+/// The wall-clock time spent in one phase run by `prove_inputs_timed`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PhaseTiming {
+    pub name: String,
+    pub duration: Duration,
+}
+
+/// Times the phases this crate runs around the prover, i.e. validating the inputs and proving
+/// them, with the wall clock.
+///
+/// The phases within the prover, like trace generation or the proof of each STARK table, can't be
+/// timed: plonky2's `TimingTree` keeps the durations it measures private and only prints them, and
+/// plonky2_evm runs those phases behind a single `prove` call.
+#[derive(Debug, Clone, Default)]
+pub struct PhaseTimer {
+    entries: Vec<PhaseTiming>,
+}
+
+impl PhaseTimer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Runs `phase` and records how long it took under `name`.
+    pub fn time<T>(&mut self, name: &str, phase: impl FnOnce() -> T) -> T {
+        let start = Instant::now();
+        let result = phase();
+        self.entries.push(PhaseTiming {
+            name: name.to_string(),
+            duration: start.elapsed(),
+        });
+        result
+    }
+
+    /// Returns the phases that took at least `threshold`, in the order they ran.
+    pub fn phases(&self, threshold: Duration) -> Vec<PhaseTiming> {
+        self.entries
+            .iter()
+            .filter(|entry| entry.duration >= threshold)
+            .cloned()
+            .collect()
+    }
+}
@@ -0,0 +1,75 @@
+use std::fmt;
+
+use plonky2_evm::all_stark::NUM_TABLES;
+use plonky2_evm::generation::GenerationInputs;
+
+use crate::config::{EvmProof, HashConfig};
+use crate::error::Result;
+use crate::transfer::{prove_inputs, GeneratedProof};
+
+/// The STARK tables of `plonky2_evm`, in the order of `AllProof::stark_proofs`.
+pub const TABLE_NAMES: [&str; NUM_TABLES] = [
+    "Arithmetic",
+    "BytePacking",
+    "Cpu",
+    "Keccak",
+    "KeccakSponge",
+    "Logic",
+    "Memory",
+];
+
+/// The size of the trace of each STARK table of a proof, i.e. how much of each circuit a
+/// transaction uses.
+///
+/// `plonky2_evm` doesn't expose the trace it generates, only the proofs of its tables, so the
+/// sizes are read from those and are padded to a power of two.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TableRows {
+    /// The log2 of the number of rows of each table, in the order of `TABLE_NAMES`.
+    pub degree_bits: [usize; NUM_TABLES],
+}
+
+impl TableRows {
+    pub fn from_proof(proof: &GeneratedProof) -> Self {
+        let config = proof.preset.stark_config();
+        let degree_bits = match &proof.proof {
+            EvmProof::Keccak(proof) => proof.degree_bits(&config),
+            EvmProof::Poseidon(proof) => proof.degree_bits(&config),
+        };
+        Self { degree_bits }
+    }
+
+    /// The number of rows of each table, in the order of `TABLE_NAMES`.
+    pub fn rows(&self) -> [usize; NUM_TABLES] {
+        self.degree_bits.map(|bits| 1 << bits)
+    }
+
+    /// Returns the rows as a JSON object keyed by table name, e.g. for external tooling.
+    pub fn to_json(&self) -> String {
+        let tables: serde_json::Map<_, _> = TABLE_NAMES
+            .iter()
+            .zip(self.degree_bits)
+            .map(|(name, bits)| {
+                let table = serde_json::json!({ "degree_bits": bits, "rows": 1usize << bits });
+                (name.to_string(), table)
+            })
+            .collect();
+        serde_json::Value::Object(tables).to_string()
+    }
+}
+
+impl fmt::Display for TableRows {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (name, bits) in TABLE_NAMES.iter().zip(self.degree_bits) {
+            writeln!(f, "{name:<12} 2^{bits:<2} = {} rows", 1usize << bits)?;
+        }
+        Ok(())
+    }
+}
+
+/// Proves `inputs` and returns the size of the trace of each table.
+pub fn table_rows(inputs: GenerationInputs) -> Result<TableRows> {
+    // トレースだけを取り出すAPIは無いので、証明してからそのサイズを読む
+    let proof = prove_inputs(inputs, HashConfig::Keccak)?;
+    Ok(TableRows::from_proof(&proof))
+}
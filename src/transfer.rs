@@ -0,0 +1,654 @@
+use std::any::Any;
+use std::panic::{self, AssertUnwindSafe};
+use std::time::Duration;
+
+use eth_trie_utils::partial_trie::HashedPartialTrie;
+use ethereum_types::{Address, U256};
+use keccak_hash::keccak;
+use plonky2::field::extension::Extendable;
+use plonky2::fri::FriConfig;
+use plonky2::hash::hash_types::RichField;
+use plonky2::plonk::config::{GenericConfig, KeccakGoldilocksConfig, PoseidonGoldilocksConfig};
+use plonky2::util::timing::TimingTree;
+use plonky2_evm::all_stark::AllStark;
+use plonky2_evm::config::StarkConfig;
+use plonky2_evm::generation::mpt::AccountRlp;
+use plonky2_evm::generation::{GenerationInputs, TrieInputs};
+use plonky2_evm::proof::{AllProof, BlockMetadata, PublicValues, TrieRoots};
+use plonky2_evm::prover::prove;
+use plonky2_evm::verifier::verify_proof;
+use plonky2_evm::Node;
+
+use crate::block::{apply_txn, trie_roots, BlockBuilder};
+use crate::bloom::accumulate_block_bloom;
+use crate::config::{fri_stark_config, ConfigPreset, EvmProof, HashConfig};
+use crate::error::{ProveError, Result};
+use crate::gas::txn_intrinsic_gas;
+use crate::inputs::GenerationInputsBuilder;
+use crate::state::apply_withdrawals;
+use crate::summary::ProofSummary;
+use crate::timing::{PhaseTimer, PhaseTiming};
+use crate::trie::StateTrieBuilder;
+use crate::txn::{recover_sender, SignedTxn, Txn};
+use crate::validate::validate_inputs;
+use crate::verify::compare_roots;
+use crate::{D, F};
+
+/// A proof of a state transition together with the public values it commits to.
+#[derive(Debug, Clone)]
+pub struct GeneratedProof {
+    pub proof: EvmProof,
+    pub public_values: PublicValues,
+    /// The preset of the `StarkConfig` the proof was generated with.
+    pub preset: ConfigPreset,
+}
+
+impl GeneratedProof {
+    /// Verifies the proof with the default `AllStark` and the config and hash it was proven with.
+    pub fn verify(&self) -> Result<()> {
+        self.verify_with(&AllStark::<F, D>::default())
+    }
+
+    /// Verifies the proof with the `AllStark` it was proven with.
+    pub fn verify_with(&self, all_stark: &AllStark<F, D>) -> Result<()> {
+        let config = self.preset.stark_config();
+        #[cfg(feature = "metrics")]
+        let start = std::time::Instant::now();
+        let result = match &self.proof {
+            EvmProof::Keccak(proof) => verify_generic(all_stark, proof.clone(), &config),
+            EvmProof::Poseidon(proof) => verify_generic(all_stark, proof.clone(), &config),
+        };
+        #[cfg(feature = "metrics")]
+        crate::metrics::record_verification(self.preset, start.elapsed());
+        result
+    }
+
+    /// Verifies the proof as one generated under `hash_config` and `config`, e.g. those a
+    /// verifier was set up for, rather than those recorded with the proof.
+    ///
+    /// A proof generated under the other hash is rejected up front, as the hash is part of its
+    /// type; one generated under another `config` is rejected by `verify_generic`.
+    pub fn verify_as(&self, hash_config: HashConfig, config: &StarkConfig) -> Result<()> {
+        let proof_hash_config = self.proof.hash_config();
+        if proof_hash_config != hash_config {
+            return Err(ProveError::Verification(anyhow::anyhow!(
+                "the proof was generated with {proof_hash_config:?}, not {hash_config:?}"
+            )));
+        }
+        let all_stark = AllStark::<F, D>::default();
+        match &self.proof {
+            EvmProof::Keccak(proof) => verify_generic(&all_stark, proof.clone(), config),
+            EvmProof::Poseidon(proof) => verify_generic(&all_stark, proof.clone(), config),
+        }
+    }
+
+    pub fn summary(&self) -> ProofSummary {
+        ProofSummary::from_proof(self)
+    }
+}
+
+/// Whether `prove_inputs_with_options` checks the roots a proof commits to.
+#[derive(Debug, Clone, Default)]
+pub struct ProveOptions {
+    /// Checks the roots after the transaction, both before proving, against the
+    /// `trie_roots_after` of the inputs, and after, against the public values of the proof.
+    pub check_roots: bool,
+    /// The roots to check, e.g. those of a block header. If `None`, the roots computed by
+    /// `dry_run` are checked, so only transfers are supported. Ignored unless `check_roots` is
+    /// set.
+    pub expected_roots: Option<TrieRoots>,
+}
+
+/// Proves a transfer of `value` from `sender` to `to`.
+///
+/// `signed_txn` is the legacy RLP-encoded transaction signed by `sender`, and
+/// `block_metadata.block_gas_used` is the gas it consumes as the only transaction of the block.
+/// The proof is generated with the config of `preset`.
+#[allow(clippy::too_many_arguments)]
+pub fn prove_transfer(
+    sender: Address,
+    to: Address,
+    value: U256,
+    sender_account: AccountRlp,
+    signed_txn: &[u8],
+    block_metadata: BlockMetadata,
+    hash_config: HashConfig,
+    preset: ConfigPreset,
+) -> Result<GeneratedProof> {
+    let txn = Txn::decode(signed_txn)?;
+    if txn.to() != Some(to) || txn.value() != value {
+        return Err(ProveError::InvalidTransaction(format!(
+            "the signed transaction doesn't transfer {value} to {to:?}"
+        )));
+    }
+
+    // 送信前は送信者のアカウントだけがState Trieに存在する
+    let mut state_before = StateTrieBuilder::new();
+    state_before.insert(sender, sender_account);
+    let tries_before = TrieInputs {
+        state_trie: state_before.build(),
+        transactions_trie: HashedPartialTrie::from(Node::Empty),
+        receipts_trie: HashedPartialTrie::from(Node::Empty),
+        storage_tries: vec![],
+    };
+
+    // ブロック内の唯一のトランザクションなので、ブロックのガス使用量がそのままトランザクションのガス使用量になる
+    let signed_txn = SignedTxn {
+        sender,
+        bytes: signed_txn.to_vec(),
+        gas_used: block_metadata.block_gas_used,
+    };
+    let inputs = block_inputs(vec![signed_txn], tries_before, block_metadata)?.remove(0);
+    prove_inputs_with(&AllStark::default(), preset, inputs, hash_config)
+}
+
+/// Proves a signed transfer given as hex, e.g. the raw transaction of a block explorer, legacy or
+/// typed, in a block with `block`.
+///
+/// The sender is recovered from the signature and is the only account before the transfer, with
+/// `sender_account`. The transfer must be to an account without code, so it uses its intrinsic
+/// gas, which replaces `block.block_gas_used`.
+pub fn prove_raw_transaction(
+    txn_hex: &str,
+    sender_account: AccountRlp,
+    block: BlockMetadata,
+) -> Result<ProofSummary> {
+    let bytes = hex::decode(txn_hex.trim().trim_start_matches("0x")).map_err(|err| {
+        ProveError::InvalidTransaction(format!("the transaction isn't valid hex: {err}"))
+    })?;
+    let txn = Txn::decode(&bytes)?;
+    let sender = txn.recover_sender()?;
+
+    let mut state_before = StateTrieBuilder::new();
+    state_before.insert(sender, sender_account);
+    let tries_before = TrieInputs {
+        state_trie: state_before.build(),
+        transactions_trie: HashedPartialTrie::from(Node::Empty),
+        receipts_trie: HashedPartialTrie::from(Node::Empty),
+        storage_tries: vec![],
+    };
+    // コードの無いアカウントへの送金なので、使うガスは固有ガスだけ
+    let gas_used = txn_intrinsic_gas(&txn);
+    let block = BlockMetadata {
+        block_gas_used: gas_used,
+        ..block
+    };
+    let signed_txn = SignedTxn {
+        sender,
+        bytes,
+        gas_used,
+    };
+    let inputs = block_inputs(vec![signed_txn], tries_before, block)?.remove(0);
+    Ok(prove_inputs(inputs, HashConfig::Keccak)?.summary())
+}
+
+/// Proves a transfer of `value` from `sender` to a contract whose `code` reverts, e.g.
+/// `60006000fd` (`PUSH1 0, PUSH1 0, REVERT`).
+///
+/// The receipt records the failure, the sender only pays for the gas and the contract keeps its
+/// balance. As for `prove_transfer`, `block_metadata.block_gas_used` is the gas the transaction
+/// consumes, including the execution of `code`.
+#[allow(clippy::too_many_arguments)]
+pub fn prove_reverting_transfer(
+    sender: Address,
+    to: Address,
+    value: U256,
+    sender_account: AccountRlp,
+    code: Vec<u8>,
+    signed_txn: &[u8],
+    block_metadata: BlockMetadata,
+    hash_config: HashConfig,
+    preset: ConfigPreset,
+) -> Result<GeneratedProof> {
+    let txn = Txn::decode(signed_txn)?;
+    if txn.to() != Some(to) || txn.value() != value {
+        return Err(ProveError::InvalidTransaction(format!(
+            "the signed transaction doesn't transfer {value} to {to:?}"
+        )));
+    }
+
+    // 送信前は送信者と、巻き戻すコードを持つコントラクトがState Trieに存在する
+    let code_hash = keccak(&code);
+    let mut state_before = StateTrieBuilder::new();
+    state_before.insert(sender, sender_account);
+    state_before.insert(
+        to,
+        AccountRlp {
+            code_hash,
+            ..AccountRlp::default()
+        },
+    );
+    let tries_before = TrieInputs {
+        state_trie: state_before.build(),
+        transactions_trie: HashedPartialTrie::from(Node::Empty),
+        receipts_trie: HashedPartialTrie::from(Node::Empty),
+        storage_tries: vec![],
+    };
+
+    let gas_used = block_metadata.block_gas_used;
+    let signed_txn = SignedTxn {
+        sender,
+        bytes: signed_txn.to_vec(),
+        gas_used,
+    };
+    let (tries_after, receipt) = apply_txn(
+        &tries_before,
+        &signed_txn,
+        0,
+        gas_used,
+        &block_metadata,
+        false,
+    )?;
+
+    let inputs = GenerationInputsBuilder::new()
+        .signed_txn(signed_txn.bytes)
+        .trie_roots_after(trie_roots(&tries_after))
+        .tries(tries_before)
+        .contract_code(code)
+        .block_metadata(block_metadata)
+        .gas_used_after(gas_used)
+        .block_bloom_after(accumulate_block_bloom([U256::zero(); 8], &[receipt]))
+        .build()?;
+    prove_inputs_with(&AllStark::default(), preset, inputs, hash_config)
+}
+
+/// Proves each transfer of a block in order, one proof per transaction.
+pub fn prove_block(
+    txns: Vec<SignedTxn>,
+    tries: TrieInputs,
+    block_metadata: BlockMetadata,
+    hash_config: HashConfig,
+) -> Result<Vec<GeneratedProof>> {
+    // 初期設定
+    // stark関連のスタンダードな設定をここで行う。
+    // snarkと違い、starkは大量のデータや複雑な計算に対しても効率的に動作する
+    let all_stark = AllStark::<F, D>::default();
+
+    block_inputs(txns, tries, block_metadata)?
+        .into_iter()
+        .map(|inputs| prove_inputs_with(&all_stark, ConfigPreset::Fast, inputs, hash_config))
+        .collect()
+}
+
+/// Proves independent blocks, e.g. each built by `block_inputs`, concurrently with `rayon`, one
+/// proof per element of `inputs`, in the same order.
+///
+/// The proofs share one `AllStark` and use the `Fast` preset. Each proof in flight holds its own
+/// traces and polynomial commitments, several GB for a simple transfer, so the peak memory grows
+/// with the number of rayon threads; limit them with `RAYON_NUM_THREADS` if needed.
+#[cfg(feature = "parallel")]
+pub fn prove_blocks_parallel(inputs: Vec<GenerationInputs>) -> Result<Vec<GeneratedProof>> {
+    use rayon::prelude::*;
+
+    // AllStarkは読むだけなので、参照を各スレッドで共有できる
+    let all_stark = AllStark::<F, D>::default();
+    inputs
+        .into_par_iter()
+        .map(|inputs| prove_inputs_with(&all_stark, ConfigPreset::Fast, inputs, HashConfig::Keccak))
+        .collect()
+}
+
+/// Proves a block without transactions nor withdrawals, whose roots are the same before and after.
+///
+/// `block_metadata.block_gas_used` must be zero. For an empty block with withdrawals, see
+/// `BlockBuilder::with_withdrawals`.
+pub fn prove_empty_block(tries: TrieInputs, block_metadata: BlockMetadata) -> Result<ProofSummary> {
+    if !block_metadata.block_gas_used.is_zero() {
+        return Err(ProveError::InvalidBlock(format!(
+            "an empty block can't use {} gas",
+            block_metadata.block_gas_used
+        )));
+    }
+    let inputs = block_inputs(vec![], tries, block_metadata)?.remove(0);
+    Ok(prove_inputs(inputs, HashConfig::Keccak)?.summary())
+}
+
+/// Builds the `GenerationInputs` of each transfer of a block, see `BlockBuilder`.
+pub fn block_inputs(
+    txns: Vec<SignedTxn>,
+    tries: TrieInputs,
+    block_metadata: BlockMetadata,
+) -> Result<Vec<GenerationInputs>> {
+    let mut builder = BlockBuilder::new(tries, block_metadata);
+    for txn in txns {
+        builder.push_txn(txn);
+    }
+    builder.build()
+}
+
+/// Proves arbitrary `GenerationInputs`, e.g. built by `block_inputs` or `deploy_contract`.
+pub fn prove_inputs(inputs: GenerationInputs, hash_config: HashConfig) -> Result<GeneratedProof> {
+    let all_stark = AllStark::<F, D>::default();
+    prove_inputs_with(&all_stark, ConfigPreset::Fast, inputs, hash_config)
+}
+
+/// Proves `inputs` like `prove_inputs`, and checks the roots the proof commits to if
+/// `options.check_roots` is set, reporting the first one that differs as `TrieRootMismatch`.
+///
+/// Expected roots that differ from the inputs' are reported before proving, since the proof
+/// always commits to the roots it was given.
+pub fn prove_inputs_with_options(
+    inputs: GenerationInputs,
+    hash_config: HashConfig,
+    options: ProveOptions,
+) -> Result<GeneratedProof> {
+    if !options.check_roots {
+        return prove_inputs(inputs, hash_config);
+    }
+    // 入力のルートを自分自身と比べても意味がないので、EVMの外で計算したルートと比べる
+    let expected_roots = match options.expected_roots {
+        Some(roots) => roots,
+        None => dry_run(&inputs)?,
+    };
+    // 証明に時間をかける前に、入力の主張するルートと比べておく
+    compare_roots(&expected_roots, &inputs.trie_roots_after)?;
+    let proof = prove_inputs(inputs, hash_config)?;
+    compare_roots(&expected_roots, &proof.public_values.trie_roots_after)?;
+    Ok(proof)
+}
+
+/// Proves `inputs` after replacing its `trie_roots_after` with the roots obtained by applying its
+/// transaction and withdrawals outside of the EVM, and returns the summary of the proof.
+///
+/// This is meant for developing new scenarios whose post-state isn't known yet: the caller can
+/// copy the returned roots into a strict test, e.g. one using `verify_state_transition`. Only
+/// transfers are supported, and as the sender isn't recovered from the signature, it's passed as
+/// `sender`.
+pub fn prove_unchecked(mut inputs: GenerationInputs, sender: Address) -> Result<ProofSummary> {
+    // 与えられたtrie_roots_afterは使わず、計算したルートで上書きする
+    inputs.trie_roots_after = roots_after(&inputs, sender)?;
+
+    Ok(prove_inputs(inputs, HashConfig::Keccak)?.summary())
+}
+
+/// Returns the roots after `inputs`, obtained by applying its transaction and withdrawals
+/// outside of the EVM, without proving anything.
+///
+/// This takes well under a second, so it's a cheap check that a scenario is self-consistent
+/// before proving it: the roots should equal its `trie_roots_after`. Like `prove_unchecked`, only
+/// transfers are supported, and the sender is recovered from the signature of the transaction.
+pub fn dry_run(inputs: &GenerationInputs) -> Result<TrieRoots> {
+    let sender = match &inputs.signed_txn {
+        Some(bytes) => recover_sender(bytes)?,
+        // 送信者がいないので何でもよい
+        None => Address::zero(),
+    };
+    roots_after(inputs, sender)
+}
+
+/// Applies the transaction of `inputs`, sent by `sender`, and its withdrawals to its tries, and
+/// returns the resulting roots.
+fn roots_after(inputs: &GenerationInputs, sender: Address) -> Result<TrieRoots> {
+    let mut tries_after = match &inputs.signed_txn {
+        Some(bytes) => {
+            // 入力は検証されていないので、パニックせずにエラーを返す
+            let gas_used = inputs
+                .gas_used_after
+                .checked_sub(inputs.gas_used_before)
+                .ok_or_else(|| {
+                    ProveError::InvalidInputs(format!(
+                        "gas used before {} is larger than gas used after {}",
+                        inputs.gas_used_before, inputs.gas_used_after
+                    ))
+                })?;
+            let txn_number = usize::try_from(inputs.txn_number_before).map_err(|_| {
+                ProveError::InvalidInputs(format!(
+                    "the transaction number {} doesn't fit in a usize",
+                    inputs.txn_number_before
+                ))
+            })?;
+            let signed_txn = SignedTxn {
+                sender,
+                bytes: bytes.clone(),
+                gas_used,
+            };
+            apply_txn(
+                &inputs.tries,
+                &signed_txn,
+                txn_number,
+                inputs.gas_used_after,
+                &inputs.block_metadata,
+                true,
+            )?
+            .0
+        }
+        None => inputs.tries.clone(),
+    };
+    tries_after.state_trie = apply_withdrawals(&tries_after.state_trie, &inputs.withdrawals)?;
+    Ok(trie_roots(&tries_after))
+}
+
+/// Proves `inputs` with the FRI parameters `fri`, see `fri_stark_config`, and returns the summary
+/// of the proof.
+///
+/// `GeneratedProof` only records a `ConfigPreset`, so the proof can't be verified later under
+/// `fri`: it's verified here before the summary is returned.
+pub fn prove_with_fri(inputs: GenerationInputs, fri: FriConfig) -> Result<ProofSummary> {
+    let config = fri_stark_config(fri)?;
+    let all_stark = AllStark::<F, D>::default();
+    let proof = prove_generic::<F, KeccakGoldilocksConfig, D>(&all_stark, &config, inputs)?;
+    let summary = ProofSummary::from_public_values(&proof.public_values);
+    verify_generic(&all_stark, proof, &config)?;
+    Ok(summary)
+}
+
+/// Proves `inputs` for any field `F`, extension degree `D` and config `C` supported by plonky2.
+///
+/// The rest of the crate fixes `F` to Goldilocks and `D` to 2. Goldilocks has extensions of
+/// degree 2, 4 and 5, but plonky2 only provides configs, Keccak and Poseidon, for `D = 2`, so
+/// other degrees need a custom `GenericConfig`.
+pub fn prove_generic<F, C, const D: usize>(
+    all_stark: &AllStark<F, D>,
+    config: &StarkConfig,
+    inputs: GenerationInputs,
+) -> Result<AllProof<F, C, D>>
+where
+    F: RichField + Extendable<D>,
+    C: GenericConfig<D, F = F>,
+{
+    validate_inputs(&inputs)?;
+    let mut timing = TimingTree::new("prove", log::Level::Debug);
+    let proof = catch_prover_panic(|| prove::<F, C, D>(all_stark, config, inputs, &mut timing))?;
+    timing.filter(Duration::from_millis(100)).print();
+    Ok(proof)
+}
+
+/// Verifies a proof generated by `prove_generic` with the same `all_stark` and `config`.
+///
+/// A proof can't be verified under another hash than it was generated with, as the hash is part
+/// of its type, see `GeneratedProof::verify_as`. A proof generated under another `config` is
+/// rejected before verifying it, if its cap height, number of queries or FRI reductions don't
+/// match `config`; any other mismatch that makes plonky2 panic is reported as
+/// `ProveError::Verification` too, like any rejected proof.
+pub fn verify_generic<F, C, const D: usize>(
+    all_stark: &AllStark<F, D>,
+    proof: AllProof<F, C, D>,
+    config: &StarkConfig,
+) -> Result<()>
+where
+    F: RichField + Extendable<D>,
+    C: GenericConfig<D, F = F>,
+{
+    let verify = || {
+        check_proof_shape(&proof, config)?;
+        verify_proof(all_stark, proof, config).map_err(ProveError::Verification)
+    };
+    match panic::catch_unwind(AssertUnwindSafe(verify)) {
+        Ok(result) => result,
+        Err(payload) => Err(ProveError::Verification(anyhow::anyhow!(
+            "the verifier panicked: {}",
+            panic_message(payload)
+        ))),
+    }
+}
+
+/// Proves `inputs` with a caller-provided `AllStark`, so that it can be built once and reused
+/// across proofs, and the config of `preset`.
+pub fn prove_inputs_with(
+    all_stark: &AllStark<F, D>,
+    preset: ConfigPreset,
+    inputs: GenerationInputs,
+    hash_config: HashConfig,
+) -> Result<GeneratedProof> {
+    prove_inputs_with_progress(all_stark, preset, inputs, hash_config, None)
+}
+
+/// Like `prove_inputs_with`, but calls `progress` with the name of each phase of proving as it
+/// finishes: `"validate inputs"`, then `"prove"`.
+///
+/// The phases within the prover, like the proof of each STARK table, can't be reported:
+/// plonky2_evm proves every table behind a single `prove` call, and `TimingTree` only prints the
+/// scopes it measured once proving is over. A progress indicator can thus only show that proving
+/// is under way, not how far along it is.
+pub fn prove_inputs_with_progress(
+    all_stark: &AllStark<F, D>,
+    preset: ConfigPreset,
+    inputs: GenerationInputs,
+    hash_config: HashConfig,
+    progress: Option<&dyn Fn(&str)>,
+) -> Result<GeneratedProof> {
+    let mut timer = PhaseTimer::new();
+    prove_phases(all_stark, preset, inputs, hash_config, progress, &mut timer)
+}
+
+/// Like `prove_inputs_with`, but also returns how long validating the inputs and proving them
+/// took. The phases within the prover aren't timed separately, see `PhaseTimer`.
+pub fn prove_inputs_timed(
+    all_stark: &AllStark<F, D>,
+    preset: ConfigPreset,
+    inputs: GenerationInputs,
+    hash_config: HashConfig,
+) -> Result<(GeneratedProof, Vec<PhaseTiming>)> {
+    let mut timer = PhaseTimer::new();
+    let proof = prove_phases(all_stark, preset, inputs, hash_config, None, &mut timer)?;
+    Ok((proof, timer.phases(Duration::ZERO)))
+}
+
+fn prove_phases(
+    all_stark: &AllStark<F, D>,
+    preset: ConfigPreset,
+    inputs: GenerationInputs,
+    hash_config: HashConfig,
+    progress: Option<&dyn Fn(&str)>,
+    timer: &mut PhaseTimer,
+) -> Result<GeneratedProof> {
+    let report = |phase: &str| {
+        if let Some(progress) = progress {
+            progress(phase);
+        }
+    };
+    let config = &preset.stark_config();
+    // plonky2の奥で分かりにくいエラーになる前に、入力の整合性を確認する
+    timer.time("validate inputs", || validate_inputs(&inputs))?;
+    report("validate inputs");
+
+    // prove中のパフォーマンスを調査する
+    let mut timing = TimingTree::new("prove", log::Level::Debug);
+    // ZKのprove(証明)をここでやる。EVMが正しい挙動をしているという証明をしている
+    let proof = timer.time("prove", || {
+        catch_prover_panic(|| {
+            Ok(match hash_config {
+                HashConfig::Keccak => EvmProof::Keccak(prove::<F, KeccakGoldilocksConfig, D>(
+                    all_stark,
+                    config,
+                    inputs,
+                    &mut timing,
+                )?),
+                HashConfig::Poseidon => {
+                    EvmProof::Poseidon(prove::<F, PoseidonGoldilocksConfig, D>(
+                        all_stark,
+                        config,
+                        inputs,
+                        &mut timing,
+                    )?)
+                }
+            })
+        })
+    })?;
+    report("prove");
+    #[cfg(feature = "metrics")]
+    if let Some(prove) = timer
+        .phases(Duration::ZERO)
+        .into_iter()
+        .find(|entry| entry.name == "prove")
+    {
+        crate::metrics::record_proving(preset, prove.duration);
+    }
+    // 100ms以上の処理時間がかかったもののみを出力
+    timing.filter(Duration::from_millis(100)).print();
+
+    Ok(GeneratedProof {
+        public_values: proof.public_values().clone(),
+        proof,
+        preset,
+    })
+}
+
+/// Runs `prove`, turning a panic of plonky2 or plonky2_evm into `ProveError::Proving` like any
+/// other failure of the prover, so that inputs the validation can't catch, e.g. a state trie
+/// without the sender, don't bring the caller down.
+fn catch_prover_panic<T>(prove: impl FnOnce() -> anyhow::Result<T>) -> Result<T> {
+    match panic::catch_unwind(AssertUnwindSafe(prove)) {
+        Ok(result) => Ok(result?),
+        Err(payload) => Err(ProveError::Proving(anyhow::anyhow!(
+            "the prover panicked: {}",
+            panic_message(payload)
+        ))),
+    }
+}
+
+/// Checks that the proof of each table has the shape a proof generated under `config` has.
+fn check_proof_shape<F, C, const D: usize>(
+    proof: &AllProof<F, C, D>,
+    config: &StarkConfig,
+) -> Result<()>
+where
+    F: RichField + Extendable<D>,
+    C: GenericConfig<D, F = F>,
+{
+    let fri_config = &config.fri_config;
+    for (table, stark_proof) in proof.stark_proofs.iter().enumerate() {
+        let stark_proof = &stark_proof.proof;
+        let opening_proof = &stark_proof.opening_proof;
+        let mismatch = |msg: String| {
+            ProveError::Verification(anyhow::anyhow!(
+                "the proof of table {table} wasn't generated with the config: {msg}"
+            ))
+        };
+        let cap_len = stark_proof.trace_cap.0.len();
+        if cap_len != 1 << fri_config.cap_height {
+            return Err(mismatch(format!(
+                "its caps have {cap_len} hashes, not 2^{}",
+                fri_config.cap_height
+            )));
+        }
+        let num_queries = opening_proof.query_round_proofs.len();
+        if num_queries != fri_config.num_query_rounds {
+            return Err(mismatch(format!(
+                "it has {num_queries} queries, not {}",
+                fri_config.num_query_rounds
+            )));
+        }
+        // 次数はMerkle証明の長さとconfigから復元されるので、FRIの折り畳みの回数と最終多項式の
+        // 長さがその次数のものと合うかで確かめる
+        let degree_bits = stark_proof.recover_degree_bits(config);
+        let fri_params = config.fri_params(degree_bits);
+        if opening_proof.commit_phase_merkle_caps.len() != fri_params.reduction_arity_bits.len()
+            || opening_proof.final_poly.len() != fri_params.final_poly_len()
+        {
+            return Err(mismatch(format!(
+                "its FRI reductions don't match a degree of 2^{degree_bits}"
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    // panic!の引数は&strかStringのどちらか
+    payload
+        .downcast_ref::<&str>()
+        .map(|msg| msg.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".into())
+}
@@ -0,0 +1,281 @@
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+
+use eth_trie_utils::nibbles::Nibbles;
+use eth_trie_utils::partial_trie::{HashedPartialTrie, PartialTrie};
+use eth_trie_utils::trie_ops::ValOrHash;
+use ethereum_types::{Address, BigEndianHash, H256, U256};
+use keccak_hash::keccak;
+use plonky2_evm::generation::mpt::{AccountRlp, LegacyReceiptRlp};
+use plonky2_evm::generation::TrieInputs;
+use plonky2_evm::Node;
+
+use crate::error::{ProveError, Result};
+use crate::nibbles::{
+    address_to_state_key_nibbles, index_key_nibbles, storage_key_nibbles, truncate_front,
+};
+
+/// Builds a state trie from a set of accounts keyed by address.
+#[derive(Debug, Default, Clone)]
+pub struct StateTrieBuilder {
+    accounts: HashMap<Address, AccountRlp>,
+    hashed_subtries: Vec<(Nibbles, H256)>,
+}
+
+impl StateTrieBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `account` at `addr`, replacing any account previously inserted there.
+    pub fn insert(&mut self, addr: Address, account: AccountRlp) {
+        self.accounts.insert(addr, account);
+    }
+
+    /// Adds a subtrie known only by its `hash`, at the keys starting with `prefix`.
+    ///
+    /// This is how a partial trie from a node represents the accounts a transaction doesn't
+    /// touch. No account inserted in the builder may have a state key starting with `prefix`.
+    pub fn insert_hash(&mut self, prefix: Nibbles, hash: H256) {
+        self.hashed_subtries.push((prefix, hash));
+    }
+
+    pub fn build(self) -> HashedPartialTrie {
+        let mut trie = HashedPartialTrie::from(Node::Empty);
+        // ハッシュノードは中身が分からないので、その下には何も挿入できない
+        for (prefix, hash) in self.hashed_subtries {
+            trie.insert(prefix, hash);
+        }
+        for (addr, account) in self.accounts {
+            // ブランチやエクステンションの組み立てはinsertに任せるので、
+            // 先頭のニブルが重なっていても正しいトライになる
+            trie.insert(
+                address_to_state_key_nibbles(addr),
+                rlp::encode(&account).to_vec(),
+            );
+        }
+        trie
+    }
+}
+
+/// Returns the account at `addr` in the state trie `trie`, or `None` if there's none.
+pub fn get_account(trie: &HashedPartialTrie, addr: Address) -> Result<Option<AccountRlp>> {
+    // ステートキーからリーフを探し、その値をAccountRlpとしてデコードする
+    match trie.get(address_to_state_key_nibbles(addr)) {
+        Some(bytes) => Ok(Some(rlp::decode(bytes)?)),
+        None => Ok(None),
+    }
+}
+
+/// Returns every account of the state trie `trie`, keyed by its state key, i.e. `keccak(address)`,
+/// e.g. to snapshot a state and compare it later.
+///
+/// Fails with `ProveError::HashedSubtrie` if a subtrie is only present as a hash, as the accounts
+/// under it can't be listed, e.g. for a trie pruned by `prune_to_touched`.
+pub fn collect_accounts(trie: &HashedPartialTrie) -> Result<HashMap<H256, AccountRlp>> {
+    let mut accounts = HashMap::new();
+    for (key, value) in trie.items() {
+        match value {
+            ValOrHash::Val(bytes) => {
+                accounts.insert(H256::from_uint(&key.packed), rlp::decode(&bytes)?);
+            }
+            ValOrHash::Hash(_) => return Err(ProveError::HashedSubtrie(key)),
+        }
+    }
+    Ok(accounts)
+}
+
+/// Returns `full_trie`, a state trie, with every subtrie off the paths to the accounts of `touched`
+/// replaced by a hash node, so that it has the same root but only the nodes a transaction between
+/// those accounts needs.
+///
+/// The nodes along each path are kept, even if the account doesn't exist yet, so that it can be
+/// inserted. Every subtrie of a state trie is referenced by its hash, as its leaves are accounts,
+/// hence the root is unchanged; this doesn't hold for tries with small values.
+pub fn prune_to_touched(full_trie: &HashedPartialTrie, touched: &[Address]) -> HashedPartialTrie {
+    let keys: Vec<_> = touched
+        .iter()
+        .map(|&addr| address_to_state_key_nibbles(addr))
+        .collect();
+    HashedPartialTrie::from(prune_node(full_trie, &keys))
+}
+
+/// Prunes `trie`, keeping the nodes along the paths of `keys`, relative to `trie`.
+fn prune_node(trie: &HashedPartialTrie, keys: &[Nibbles]) -> Node<HashedPartialTrie> {
+    // どの経路にも乗らない部分木は、ハッシュだけを残す
+    if keys.is_empty() {
+        return match &**trie {
+            Node::Empty => Node::Empty,
+            Node::Hash(hash) => Node::Hash(*hash),
+            _ => Node::Hash(trie.hash()),
+        };
+    }
+    match &**trie {
+        Node::Branch { children, value } => {
+            let children = std::array::from_fn(|i| {
+                let child_keys: Vec<_> = keys
+                    .iter()
+                    .filter(|key| key.count > 0 && key.get_nibble(0) as usize == i)
+                    .map(|key| truncate_front(*key, 1))
+                    .collect();
+                prune_node(&children[i], &child_keys).into()
+            });
+            Node::Branch {
+                children,
+                value: value.clone(),
+            }
+        }
+        Node::Extension { nibbles, child } => {
+            let child_keys: Vec<_> = keys
+                .iter()
+                .filter(|key| {
+                    key.count >= nibbles.count
+                        && (0..nibbles.count).all(|i| key.get_nibble(i) == nibbles.get_nibble(i))
+                })
+                .map(|key| truncate_front(*key, nibbles.count))
+                .collect();
+            Node::Extension {
+                nibbles: *nibbles,
+                child: prune_node(child, &child_keys).into(),
+            }
+        }
+        // リーフと既にハッシュのノードはそのまま
+        node => node.clone(),
+    }
+}
+
+/// Builds the tries before a block on top of existing accounts, e.g. copied from a real chain,
+/// with empty transactions, receipts and storage tries.
+///
+/// If an address appears several times, its last account is kept.
+pub fn tries_from_accounts(accounts: &[(Address, AccountRlp)]) -> TrieInputs {
+    let mut state = StateTrieBuilder::new();
+    for &(addr, account) in accounts {
+        state.insert(addr, account);
+    }
+    TrieInputs {
+        state_trie: state.build(),
+        transactions_trie: HashedPartialTrie::from(Node::Empty),
+        receipts_trie: HashedPartialTrie::from(Node::Empty),
+        storage_tries: vec![],
+    }
+}
+
+/// Builds the tries of a chain starting from the genesis allocation `alloc`, with empty
+/// transactions, receipts and storage tries.
+///
+/// Also returns the genesis state root, to be used as `GenerationInputs::genesis_state_trie_root`.
+pub fn genesis_tries(alloc: HashMap<Address, AccountRlp>) -> (TrieInputs, H256) {
+    let state_trie = StateTrieBuilder {
+        accounts: alloc,
+        ..StateTrieBuilder::default()
+    }
+    .build();
+    let genesis_root = state_trie.hash();
+    let tries = TrieInputs {
+        state_trie,
+        transactions_trie: HashedPartialTrie::from(Node::Empty),
+        receipts_trie: HashedPartialTrie::from(Node::Empty),
+        storage_tries: vec![],
+    };
+    (tries, genesis_root)
+}
+
+/// Builds the storage trie of an account from its `(slot, value)` pairs.
+#[derive(Debug, Default, Clone)]
+pub struct StorageTrieBuilder {
+    slots: HashMap<H256, U256>,
+}
+
+impl StorageTrieBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns a builder with all of `slots`, e.g. to set up an account with many slots at once.
+    pub fn with_slots(slots: impl IntoIterator<Item = (H256, U256)>) -> Result<Self> {
+        let slots = slots.into_iter();
+        let mut builder = Self {
+            slots: HashMap::with_capacity(slots.size_hint().0),
+        };
+        for (slot, value) in slots {
+            builder.insert(slot, value)?;
+        }
+        Ok(builder)
+    }
+
+    /// Sets `slot` to `value`. A slot can only be set once: a second value for it is rejected
+    /// rather than silently replacing the first.
+    pub fn insert(&mut self, slot: H256, value: U256) -> Result<()> {
+        match self.slots.entry(slot) {
+            Entry::Occupied(_) => Err(ProveError::InvalidInputs(format!(
+                "storage slot {slot:?} is set twice"
+            ))),
+            Entry::Vacant(entry) => {
+                entry.insert(value);
+                Ok(())
+            }
+        }
+    }
+
+    /// Returns the storage trie and its root, to be used as the account's `storage_root`.
+    pub fn build(self) -> (HashedPartialTrie, H256) {
+        let mut trie = HashedPartialTrie::from(Node::Empty);
+        for (slot, value) in self.slots {
+            // 値が0のスロットはトライに入らない
+            if !value.is_zero() {
+                // ステートトライと同じく、キーはスロットのkeccak
+                trie.insert(storage_key_nibbles(slot), rlp::encode(&value).to_vec());
+            }
+        }
+        let root = trie.hash();
+        (trie, root)
+    }
+
+    /// Builds the storage trie of `addr`, adds it to `tries.storage_tries` and returns its root.
+    pub fn build_into(self, addr: Address, tries: &mut TrieInputs) -> H256 {
+        let (trie, root) = self.build();
+        // storage_triesはアカウントのステートキー(keccak(addr))で引かれる
+        tries.storage_tries.push((keccak(addr), trie));
+        root
+    }
+}
+
+/// Builds the transactions trie of a block, with the `i`-th raw signed transaction at key `rlp(i)`.
+pub fn build_transactions_trie(txns: &[Vec<u8>]) -> HashedPartialTrie {
+    let mut trie = HashedPartialTrie::from(Node::Empty);
+    for (index, txn) in txns.iter().enumerate() {
+        trie.insert(index_key_nibbles(index), txn.clone());
+    }
+    trie
+}
+
+/// Builds the receipts trie of a block, with the `i`-th receipt at key `rlp(i)`.
+pub fn build_receipts_trie(receipts: &[LegacyReceiptRlp]) -> HashedPartialTrie {
+    let mut trie = HashedPartialTrie::from(Node::Empty);
+    for (index, receipt) in receipts.iter().enumerate() {
+        trie.insert(index_key_nibbles(index), rlp::encode(receipt).to_vec());
+    }
+    trie
+}
+
+/// Returns the transactions root and the receipts root of a block whose `i`-th transaction has
+/// the `i`-th receipt, both keyed by `rlp(i)`.
+///
+/// Fails with `ProveError::UnexpectedReceipt` if there isn't exactly one receipt per transaction.
+pub fn compute_tx_and_receipt_roots(
+    txns: &[Vec<u8>],
+    receipts: &[LegacyReceiptRlp],
+) -> Result<(H256, H256)> {
+    if txns.len() != receipts.len() {
+        return Err(ProveError::UnexpectedReceipt(format!(
+            "each transaction must have a receipt, but there are {} transactions and {} receipts",
+            txns.len(),
+            receipts.len()
+        )));
+    }
+    Ok((
+        build_transactions_trie(txns).hash(),
+        build_receipts_trie(receipts).hash(),
+    ))
+}
@@ -0,0 +1,490 @@
+use ethereum_types::{Address, H256, U256};
+use k256::ecdsa::{RecoveryId, Signature, VerifyingKey};
+use keccak_hash::keccak;
+use rlp::{Rlp, RlpStream};
+
+use crate::error::{ProveError, Result};
+
+/// A signed legacy value transfer transaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransferTxn {
+    pub nonce: U256,
+    pub gas_price: U256,
+    pub gas_limit: U256,
+    /// The receiver, or `None` for a contract creation.
+    pub to: Option<Address>,
+    pub value: U256,
+    pub data: Vec<u8>,
+    pub v: U256,
+    pub r: U256,
+    pub s: U256,
+}
+
+impl TransferTxn {
+    /// Returns the chain id the transaction was signed for, or `None` for a pre-EIP-155
+    /// transaction whose `v` is 27 or 28.
+    pub fn chain_id(&self) -> Option<U256> {
+        // EIP-155では v = chain_id * 2 + 35 または 36
+        (self.v >= 35.into()).then(|| (self.v - 35) / 2)
+    }
+
+    /// Returns the hash signed by the sender: of the unsigned fields, followed by the chain id and
+    /// two zeros under EIP-155.
+    pub fn signing_hash(&self) -> H256 {
+        let chain_id = self.chain_id();
+        let mut stream = RlpStream::new_list(if chain_id.is_some() { 9 } else { 6 });
+        stream
+            .append(&self.nonce)
+            .append(&self.gas_price)
+            .append(&self.gas_limit);
+        match self.to {
+            Some(to) => stream.append(&to),
+            None => stream.append_empty_data(),
+        };
+        stream.append(&self.value).append(&self.data);
+        if let Some(chain_id) = chain_id {
+            stream.append(&chain_id).append(&0u8).append(&0u8);
+        }
+        keccak(stream.out())
+    }
+
+    /// Recovers the address that signed the transaction from its `v`, `r` and `s`.
+    pub fn recover_sender(&self) -> Result<Address> {
+        let invalid =
+            |msg: String| ProveError::InvalidTransaction(format!("invalid signature: {msg}"));
+        // vからリカバリーIDを取り出す。EIP-155ではchain_idの分を引く
+        let recovery_id = match self.chain_id() {
+            Some(chain_id) => self.v - 35 - chain_id * 2,
+            None if self.v >= 27.into() => self.v - 27,
+            None => return Err(invalid(format!("v {} is below 27", self.v))),
+        };
+        recover_signer(self.signing_hash(), recovery_id, self.r, self.s)
+    }
+
+    /// Encodes the transaction as the signed RLP list expected in `GenerationInputs::signed_txn`.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut stream = RlpStream::new_list(9);
+        stream
+            .append(&self.nonce)
+            .append(&self.gas_price)
+            .append(&self.gas_limit);
+        match self.to {
+            Some(to) => stream.append(&to),
+            // コントラクト作成ではtoが空文字列になる
+            None => stream.append_empty_data(),
+        };
+        stream
+            .append(&self.value)
+            .append(&self.data)
+            .append(&self.v)
+            .append(&self.r)
+            .append(&self.s);
+        stream.out().to_vec()
+    }
+
+    pub fn decode(bytes: &[u8]) -> Result<Self> {
+        // レガシートランザクションは [nonce, gas_price, gas, to, value, data, v, r, s] のRLPリスト
+        let rlp = Rlp::new(bytes);
+        if !rlp.is_list() || rlp.item_count()? != 9 {
+            return Err(ProveError::InvalidTransaction(
+                "a legacy transaction must be an RLP list of 9 items".into(),
+            ));
+        }
+        Ok(Self {
+            nonce: rlp.val_at(0)?,
+            gas_price: rlp.val_at(1)?,
+            gas_limit: rlp.val_at(2)?,
+            to: if rlp.at(3)?.is_empty() {
+                None
+            } else {
+                Some(rlp.val_at(3)?)
+            },
+            value: rlp.val_at(4)?,
+            data: rlp.val_at(5)?,
+            v: rlp.val_at(6)?,
+            r: rlp.val_at(7)?,
+            s: rlp.val_at(8)?,
+        })
+    }
+}
+
+/// Recovers the sender of the signed transaction `txn`, legacy or typed, to check it against the
+/// sender the caller expects.
+pub fn recover_sender(txn: &[u8]) -> Result<Address> {
+    Txn::decode(txn)?.recover_sender()
+}
+
+/// Recovers the address whose key signed `hash` with the signature `(recovery_id, r, s)`.
+fn recover_signer(hash: H256, recovery_id: U256, r: U256, s: U256) -> Result<Address> {
+    let invalid = |msg: String| ProveError::InvalidTransaction(format!("invalid signature: {msg}"));
+    let recovery_id = (recovery_id <= 1.into())
+        .then(|| RecoveryId::from_byte(recovery_id.as_u32() as u8))
+        .flatten()
+        .ok_or_else(|| invalid(format!("{recovery_id} isn't a recovery id")))?;
+
+    let mut rs = [0u8; 64];
+    r.to_big_endian(&mut rs[..32]);
+    s.to_big_endian(&mut rs[32..]);
+    let signature = Signature::from_slice(&rs).map_err(|err| invalid(err.to_string()))?;
+    let key = VerifyingKey::recover_from_prehash(hash.as_bytes(), &signature, recovery_id)
+        .map_err(|err| invalid(err.to_string()))?;
+
+    // アドレスは非圧縮の公開鍵(先頭の0x04を除く)のkeccakの下位20バイト
+    let public_key = key.to_encoded_point(false);
+    Ok(Address::from_slice(
+        &keccak(&public_key.as_bytes()[1..]).as_bytes()[12..],
+    ))
+}
+
+/// An access list entry: an address and the storage slots of it the transaction will access.
+pub type AccessListItem = (Address, Vec<H256>);
+
+fn append_access_list(stream: &mut RlpStream, access_list: &[AccessListItem]) {
+    // アクセスリストは [[address, [slot, ..]], ..]。空なら空リスト(0xc0)になる
+    stream.begin_list(access_list.len());
+    for (addr, slots) in access_list {
+        stream.begin_list(2);
+        stream.append(addr);
+        stream.append_list::<H256, H256>(slots);
+    }
+}
+
+/// Decodes an access list, rejecting any entry that isn't `[address, [slot, ..]]` with a 20-byte
+/// address and 32-byte slots.
+fn decode_access_list(rlp: &Rlp) -> Result<Vec<AccessListItem>> {
+    let invalid = |msg: &str| ProveError::InvalidTransaction(format!("invalid access list: {msg}"));
+    if !rlp.is_list() {
+        return Err(invalid("it must be an RLP list"));
+    }
+    rlp.iter()
+        .map(|item| {
+            if !item.is_list() || item.item_count()? != 2 {
+                return Err(invalid("an entry must be a list of an address and slots"));
+            }
+            let address = item.at(0)?;
+            if !address.is_data() || address.size() != Address::len_bytes() {
+                return Err(invalid("an address must be 20 bytes"));
+            }
+            let slots = item.at(1)?;
+            if !slots.is_list() {
+                return Err(invalid("the slots of an entry must be a list"));
+            }
+            let slots = slots
+                .iter()
+                .map(|slot| {
+                    if !slot.is_data() || slot.size() != H256::len_bytes() {
+                        return Err(invalid("a slot must be 32 bytes"));
+                    }
+                    Ok(slot.as_val()?)
+                })
+                .collect::<Result<Vec<H256>>>()?;
+            Ok((address.as_val()?, slots))
+        })
+        .collect()
+}
+
+/// A signed EIP-2930 (type 1) transaction, a legacy one with a chain id and an access list.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Eip2930Txn {
+    pub chain_id: U256,
+    pub nonce: U256,
+    pub gas_price: U256,
+    pub gas_limit: U256,
+    /// The receiver, or `None` for a contract creation.
+    pub to: Option<Address>,
+    pub value: U256,
+    pub data: Vec<u8>,
+    /// The addresses and slots warmed before the execution starts, see EIP-2929.
+    pub access_list: Vec<AccessListItem>,
+    pub y_parity: U256,
+    pub r: U256,
+    pub s: U256,
+}
+
+impl Eip2930Txn {
+    /// The type byte prefixed to the RLP payload of the transaction and of its receipt.
+    pub const TYPE: u8 = 0x01;
+
+    /// Returns the hash signed by the sender: `keccak(0x01 || rlp([chain_id, .., access_list]))`.
+    pub fn signing_hash(&self) -> H256 {
+        let mut stream = RlpStream::new_list(8);
+        self.append_unsigned(&mut stream);
+        keccak([&[Self::TYPE], stream.out().as_ref()].concat())
+    }
+
+    /// Recovers the address that signed the transaction from its `y_parity`, `r` and `s`.
+    pub fn recover_sender(&self) -> Result<Address> {
+        recover_signer(self.signing_hash(), self.y_parity, self.r, self.s)
+    }
+
+    /// Encodes the transaction as `0x01 || rlp([chain_id, nonce, .., access_list, y_parity, r, s])`.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut stream = RlpStream::new_list(11);
+        self.append_unsigned(&mut stream);
+        stream
+            .append(&self.y_parity)
+            .append(&self.r)
+            .append(&self.s);
+        [&[Self::TYPE], stream.out().as_ref()].concat()
+    }
+
+    fn append_unsigned(&self, stream: &mut RlpStream) {
+        stream
+            .append(&self.chain_id)
+            .append(&self.nonce)
+            .append(&self.gas_price)
+            .append(&self.gas_limit);
+        match self.to {
+            Some(to) => stream.append(&to),
+            None => stream.append_empty_data(),
+        };
+        stream.append(&self.value).append(&self.data);
+        append_access_list(stream, &self.access_list);
+    }
+
+    pub fn decode(bytes: &[u8]) -> Result<Self> {
+        let Some((&Self::TYPE, payload)) = bytes.split_first() else {
+            return Err(ProveError::InvalidTransaction(
+                "an EIP-2930 transaction must start with 0x01".into(),
+            ));
+        };
+        let rlp = Rlp::new(payload);
+        if !rlp.is_list() || rlp.item_count()? != 11 {
+            return Err(ProveError::InvalidTransaction(
+                "an EIP-2930 transaction must be an RLP list of 11 items".into(),
+            ));
+        }
+        Ok(Self {
+            chain_id: rlp.val_at(0)?,
+            nonce: rlp.val_at(1)?,
+            gas_price: rlp.val_at(2)?,
+            gas_limit: rlp.val_at(3)?,
+            to: if rlp.at(4)?.is_empty() {
+                None
+            } else {
+                Some(rlp.val_at(4)?)
+            },
+            value: rlp.val_at(5)?,
+            data: rlp.val_at(6)?,
+            access_list: decode_access_list(&rlp.at(7)?)?,
+            y_parity: rlp.val_at(8)?,
+            r: rlp.val_at(9)?,
+            s: rlp.val_at(10)?,
+        })
+    }
+}
+
+/// A signed EIP-1559 (type 2) transaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Eip1559Txn {
+    pub chain_id: U256,
+    pub nonce: U256,
+    pub max_priority_fee_per_gas: U256,
+    pub max_fee_per_gas: U256,
+    pub gas_limit: U256,
+    /// The receiver, or `None` for a contract creation.
+    pub to: Option<Address>,
+    pub value: U256,
+    pub data: Vec<u8>,
+    pub access_list: Vec<AccessListItem>,
+    pub y_parity: U256,
+    pub r: U256,
+    pub s: U256,
+}
+
+impl Eip1559Txn {
+    /// The type byte prefixed to the RLP payload of the transaction and of its receipt.
+    pub const TYPE: u8 = 0x02;
+
+    /// Returns the price paid per gas in a block whose base fee is `base_fee`.
+    pub fn effective_gas_price(&self, base_fee: U256) -> U256 {
+        // ベースフィーに優先手数料を足したものだが、max_fee_per_gasは超えない。
+        // 署名さえあれば誰でも巨大な値を入れられるので、オーバーフローさせない
+        self.max_fee_per_gas
+            .min(base_fee.saturating_add(self.max_priority_fee_per_gas))
+    }
+
+    /// Returns the hash signed by the sender: `keccak(0x02 || rlp([chain_id, .., access_list]))`.
+    pub fn signing_hash(&self) -> H256 {
+        let mut stream = RlpStream::new_list(9);
+        self.append_unsigned(&mut stream);
+        keccak([&[Self::TYPE], stream.out().as_ref()].concat())
+    }
+
+    /// Recovers the address that signed the transaction from its `y_parity`, `r` and `s`.
+    pub fn recover_sender(&self) -> Result<Address> {
+        recover_signer(self.signing_hash(), self.y_parity, self.r, self.s)
+    }
+
+    /// Encodes the transaction as `0x02 || rlp([chain_id, nonce, .., access_list, y_parity, r, s])`.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut stream = RlpStream::new_list(12);
+        self.append_unsigned(&mut stream);
+        stream
+            .append(&self.y_parity)
+            .append(&self.r)
+            .append(&self.s);
+        [&[Self::TYPE], stream.out().as_ref()].concat()
+    }
+
+    fn append_unsigned(&self, stream: &mut RlpStream) {
+        stream
+            .append(&self.chain_id)
+            .append(&self.nonce)
+            .append(&self.max_priority_fee_per_gas)
+            .append(&self.max_fee_per_gas)
+            .append(&self.gas_limit);
+        match self.to {
+            Some(to) => stream.append(&to),
+            None => stream.append_empty_data(),
+        };
+        stream.append(&self.value).append(&self.data);
+        append_access_list(stream, &self.access_list);
+    }
+
+    pub fn decode(bytes: &[u8]) -> Result<Self> {
+        let Some((&Self::TYPE, payload)) = bytes.split_first() else {
+            return Err(ProveError::InvalidTransaction(
+                "an EIP-1559 transaction must start with 0x02".into(),
+            ));
+        };
+        let rlp = Rlp::new(payload);
+        if !rlp.is_list() || rlp.item_count()? != 12 {
+            return Err(ProveError::InvalidTransaction(
+                "an EIP-1559 transaction must be an RLP list of 12 items".into(),
+            ));
+        }
+        Ok(Self {
+            chain_id: rlp.val_at(0)?,
+            nonce: rlp.val_at(1)?,
+            max_priority_fee_per_gas: rlp.val_at(2)?,
+            max_fee_per_gas: rlp.val_at(3)?,
+            gas_limit: rlp.val_at(4)?,
+            to: if rlp.at(5)?.is_empty() {
+                None
+            } else {
+                Some(rlp.val_at(5)?)
+            },
+            value: rlp.val_at(6)?,
+            data: rlp.val_at(7)?,
+            access_list: decode_access_list(&rlp.at(8)?)?,
+            y_parity: rlp.val_at(9)?,
+            r: rlp.val_at(10)?,
+            s: rlp.val_at(11)?,
+        })
+    }
+}
+
+/// A signed transaction of any of the supported types.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Txn {
+    Legacy(TransferTxn),
+    Eip2930(Eip2930Txn),
+    Eip1559(Eip1559Txn),
+}
+
+impl Txn {
+    /// Decodes a legacy transaction, or a typed one if `bytes` starts with its type byte.
+    pub fn decode(bytes: &[u8]) -> Result<Self> {
+        // レガシーはRLPリストなので先頭は0xc0以上、タイプ付きは先頭がタイプのバイト
+        match bytes.first() {
+            Some(&Eip2930Txn::TYPE) => Ok(Self::Eip2930(Eip2930Txn::decode(bytes)?)),
+            Some(&Eip1559Txn::TYPE) => Ok(Self::Eip1559(Eip1559Txn::decode(bytes)?)),
+            _ => Ok(Self::Legacy(TransferTxn::decode(bytes)?)),
+        }
+    }
+
+    pub fn encode(&self) -> Vec<u8> {
+        match self {
+            Self::Legacy(txn) => txn.encode(),
+            Self::Eip2930(txn) => txn.encode(),
+            Self::Eip1559(txn) => txn.encode(),
+        }
+    }
+
+    /// Returns the type byte of the transaction, or `None` for a legacy one.
+    pub fn txn_type(&self) -> Option<u8> {
+        match self {
+            Self::Legacy(_) => None,
+            Self::Eip2930(_) => Some(Eip2930Txn::TYPE),
+            Self::Eip1559(_) => Some(Eip1559Txn::TYPE),
+        }
+    }
+
+    pub fn nonce(&self) -> U256 {
+        match self {
+            Self::Legacy(txn) => txn.nonce,
+            Self::Eip2930(txn) => txn.nonce,
+            Self::Eip1559(txn) => txn.nonce,
+        }
+    }
+
+    pub fn to(&self) -> Option<Address> {
+        match self {
+            Self::Legacy(txn) => txn.to,
+            Self::Eip2930(txn) => txn.to,
+            Self::Eip1559(txn) => txn.to,
+        }
+    }
+
+    pub fn value(&self) -> U256 {
+        match self {
+            Self::Legacy(txn) => txn.value,
+            Self::Eip2930(txn) => txn.value,
+            Self::Eip1559(txn) => txn.value,
+        }
+    }
+
+    /// Returns the chain id the transaction was signed for, or `None` for a legacy transaction
+    /// signed before EIP-155, which is valid on any chain.
+    pub fn chain_id(&self) -> Option<U256> {
+        match self {
+            Self::Legacy(txn) => txn.chain_id(),
+            Self::Eip2930(txn) => Some(txn.chain_id),
+            Self::Eip1559(txn) => Some(txn.chain_id),
+        }
+    }
+
+    /// Returns the price paid per gas in a block whose base fee is `base_fee`.
+    pub fn effective_gas_price(&self, base_fee: U256) -> U256 {
+        match self {
+            Self::Legacy(txn) => txn.gas_price,
+            Self::Eip2930(txn) => txn.gas_price,
+            Self::Eip1559(txn) => txn.effective_gas_price(base_fee),
+        }
+    }
+
+    /// Recovers the address that signed the transaction.
+    pub fn recover_sender(&self) -> Result<Address> {
+        match self {
+            Self::Legacy(txn) => txn.recover_sender(),
+            Self::Eip2930(txn) => txn.recover_sender(),
+            Self::Eip1559(txn) => txn.recover_sender(),
+        }
+    }
+
+    pub fn data(&self) -> &[u8] {
+        match self {
+            Self::Legacy(txn) => &txn.data,
+            Self::Eip2930(txn) => &txn.data,
+            Self::Eip1559(txn) => &txn.data,
+        }
+    }
+
+    /// Returns the access list of the transaction, empty for a legacy one.
+    pub fn access_list(&self) -> &[AccessListItem] {
+        match self {
+            Self::Legacy(_) => &[],
+            Self::Eip2930(txn) => &txn.access_list,
+            Self::Eip1559(txn) => &txn.access_list,
+        }
+    }
+}
+
+/// A signed transaction in a block, with the sender and gas used needed to derive its effects.
+#[derive(Debug, Clone)]
+pub struct SignedTxn {
+    pub sender: Address,
+    pub bytes: Vec<u8>,
+    pub gas_used: U256,
+}
@@ -0,0 +1,185 @@
+use eth_trie_utils::nibbles::Nibbles;
+use eth_trie_utils::partial_trie::{HashedPartialTrie, PartialTrie};
+use eth_trie_utils::trie_ops::ValOrHash;
+use ethereum_types::{BigEndianHash, H256};
+use plonky2_evm::generation::mpt::AccountRlp;
+use plonky2_evm::generation::{GenerationInputs, TrieInputs};
+use plonky2_evm::Node;
+
+use crate::error::{ProveError, Result};
+use crate::nibbles::index_key_nibbles;
+use crate::txn::Txn;
+
+/// Checks the invariants of `inputs` that can be checked without running the EVM, so that
+/// inconsistent inputs are reported before spending time on proving.
+pub fn validate_inputs(inputs: &GenerationInputs) -> Result<()> {
+    if inputs.gas_used_before > inputs.gas_used_after {
+        return Err(ProveError::InvalidInputs(format!(
+            "gas used before {} is larger than gas used after {}",
+            inputs.gas_used_before, inputs.gas_used_after
+        )));
+    }
+    if inputs.gas_used_after > inputs.block_metadata.block_gas_used {
+        return Err(ProveError::InvalidInputs(format!(
+            "gas used after {} exceeds the gas used by the block {}",
+            inputs.gas_used_after, inputs.block_metadata.block_gas_used
+        )));
+    }
+
+    // ブロック1の最初のトランザクションの前のステートはジェネシスのステート。
+    // 空のトライのルートは、ジェネシスが与えられていないものとして扱う
+    let genesis_root = inputs.genesis_state_trie_root;
+    if inputs.block_metadata.block_number == 1.into()
+        && inputs.txn_number_before.is_zero()
+        && genesis_root != HashedPartialTrie::from(Node::Empty).hash()
+        && inputs.tries.state_trie.hash() != genesis_root
+    {
+        return Err(ProveError::InvalidInputs(format!(
+            "the state root {:?} before block 1 isn't the genesis state root {genesis_root:?}",
+            inputs.tries.state_trie.hash()
+        )));
+    }
+
+    validate_storage_tries(&inputs.tries)?;
+
+    let txn_number_before = usize::try_from(inputs.txn_number_before).map_err(|_| {
+        ProveError::InvalidInputs(format!(
+            "the transaction number {} doesn't fit in a usize",
+            inputs.txn_number_before
+        ))
+    })?;
+    validate_transaction_trie_keys(&inputs.tries.transactions_trie, txn_number_before)?;
+
+    // トランザクショントライの後のルートは、実行しなくても計算できる
+    let mut transactions_trie = inputs.tries.transactions_trie.clone();
+    match &inputs.signed_txn {
+        Some(signed_txn) => {
+            if signed_txn.is_empty() {
+                return Err(ProveError::InvalidInputs(
+                    "the signed transaction is empty".into(),
+                ));
+            }
+            // 別のチェーン向けに署名されたトランザクションはリプレイになる
+            let block_chain_id = inputs.block_metadata.block_chain_id;
+            match Txn::decode(signed_txn)?.chain_id() {
+                Some(chain_id) if chain_id != block_chain_id => {
+                    return Err(ProveError::ChainIdMismatch {
+                        txn: chain_id,
+                        block: block_chain_id,
+                    });
+                }
+                _ => {}
+            }
+            transactions_trie.insert(index_key_nibbles(txn_number_before), signed_txn.clone());
+        }
+        None => {
+            // トランザクションが無ければ、ガスもレシートも変わらない
+            if inputs.gas_used_before != inputs.gas_used_after {
+                return Err(ProveError::InvalidInputs(
+                    "gas is used without a transaction".into(),
+                ));
+            }
+            let receipts_root = inputs.tries.receipts_trie.hash();
+            if inputs.trie_roots_after.receipts_root != receipts_root {
+                return Err(ProveError::TrieRootMismatch {
+                    root: "receipts_root",
+                    expected: receipts_root,
+                    got: inputs.trie_roots_after.receipts_root,
+                });
+            }
+            let state_root = inputs.tries.state_trie.hash();
+            if inputs.withdrawals.is_empty() && inputs.trie_roots_after.state_root != state_root {
+                return Err(ProveError::TrieRootMismatch {
+                    root: "state_root",
+                    expected: state_root,
+                    got: inputs.trie_roots_after.state_root,
+                });
+            }
+        }
+    }
+    if inputs.trie_roots_after.transactions_root != transactions_trie.hash() {
+        return Err(ProveError::TrieRootMismatch {
+            root: "transactions_root",
+            expected: transactions_trie.hash(),
+            got: inputs.trie_roots_after.transactions_root,
+        });
+    }
+    Ok(())
+}
+
+/// Checks that `trie` holds the transactions `0` to `txn_count - 1` of a block at keys `rlp(0)`,
+/// `rlp(1)`, ..., and nothing else.
+///
+/// Keys hidden under hash nodes can't be checked, so a trie with hash nodes is accepted as is.
+pub fn validate_transaction_trie_keys(trie: &HashedPartialTrie, txn_count: usize) -> Result<()> {
+    let mut keys = vec![];
+    for (key, value) in trie.items() {
+        match value {
+            ValOrHash::Val(_) => keys.push(key),
+            ValOrHash::Hash(_) => return Ok(()),
+        }
+    }
+    // 0から順に、抜けている番号が無いか調べる
+    for index in 0..txn_count {
+        let key = index_key_nibbles(index);
+        if !keys.contains(&key) {
+            return Err(ProveError::TransactionTrieKeyGap(format!(
+                "no transaction {index} at key {}, but {txn_count} transactions come before",
+                key_hex(&key)
+            )));
+        }
+    }
+    if keys.len() > txn_count {
+        let expected: Vec<_> = (0..txn_count).map(index_key_nibbles).collect();
+        let extra = keys
+            .iter()
+            .find(|key| !expected.contains(key))
+            .expect("a key beyond the expected ones");
+        return Err(ProveError::TransactionTrieKeyGap(format!(
+            "key {} isn't the index of one of the {txn_count} transactions before",
+            key_hex(extra)
+        )));
+    }
+    Ok(())
+}
+
+/// Checks that every account of the state trie with storage, e.g. a contract, has its storage trie
+/// in `tries.storage_tries`, with the account's `storage_root`.
+///
+/// Otherwise the kernel would start from an empty storage and compute a wrong state root.
+/// Accounts hidden under hash nodes can't be checked.
+pub fn validate_storage_tries(tries: &TrieInputs) -> Result<()> {
+    let empty_root = HashedPartialTrie::from(Node::Empty).hash();
+    for (key, value) in tries.state_trie.items() {
+        let ValOrHash::Val(bytes) = value else {
+            continue;
+        };
+        let account: AccountRlp = rlp::decode(&bytes)?;
+        if account.storage_root == empty_root {
+            continue;
+        }
+        // storage_triesはアドレスではなくステートキーで引かれる
+        let state_key = H256::from_uint(&key.packed);
+        match tries.storage_tries.iter().find(|(k, _)| *k == state_key) {
+            Some((_, trie)) if trie.hash() == account.storage_root => {}
+            Some((_, trie)) => {
+                return Err(ProveError::TrieRootMismatch {
+                    root: "storage_root",
+                    expected: account.storage_root,
+                    got: trie.hash(),
+                })
+            }
+            None => {
+                return Err(ProveError::InvalidInputs(format!(
+                "the account at state key {state_key:?} has storage root {:?}, but no storage trie",
+                account.storage_root
+            )))
+            }
+        }
+    }
+    Ok(())
+}
+
+fn key_hex(key: &Nibbles) -> String {
+    format!("0x{:0width$x}", key.packed, width = key.count)
+}
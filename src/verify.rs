@@ -0,0 +1,134 @@
+use std::path::Path;
+
+use eth_trie_utils::partial_trie::{HashedPartialTrie, PartialTrie};
+use ethereum_types::{H256, U256};
+use plonky2::plonk::proof::ProofWithPublicInputs;
+use plonky2_evm::all_stark::AllStark;
+use plonky2_evm::config::StarkConfig;
+use plonky2_evm::proof::{AllProof, PublicValues, TrieRoots};
+
+use crate::error::{ProveError, Result};
+use crate::io::load_proof;
+use crate::receipt::ReceiptView;
+use crate::recursion::{recursive_circuits, RecursiveConfig};
+use crate::summary::ProofSummary;
+use crate::transfer::{verify_generic, GeneratedProof};
+use crate::{C, D, F};
+
+/// Verifies `proof` and checks that it commits to `expected_root` as the state root after the
+/// transition, so that a valid proof of the wrong transition is rejected too.
+pub fn verify_state_transition(proof: &GeneratedProof, expected_root: H256) -> Result<()> {
+    proof.verify()?;
+    let state_root = proof.public_values.trie_roots_after.state_root;
+    // 証明自体は正しくても、別の遷移の証明かもしれない
+    if state_root != expected_root {
+        return Err(ProveError::TrieRootMismatch {
+            root: "state_root",
+            expected: expected_root,
+            got: state_root,
+        });
+    }
+    Ok(())
+}
+
+/// Verifies a root proof written by `save_proof` at `path`, without proving again, and returns
+/// the public values it commits to.
+///
+/// The recursion circuits are rebuilt from the default `AllStark` and
+/// `StarkConfig::standard_fast_config`, which must be those the proof was generated with.
+pub fn verify_file(path: &Path) -> Result<ProofSummary> {
+    let proof: ProofWithPublicInputs<F, RecursiveConfig, D> = load_proof(path)?;
+    // 公開値は証明の公開入力に含まれているので、入力が無くても取り出せる
+    let public_values = PublicValues::from_public_inputs(&proof.public_inputs);
+    let circuits = recursive_circuits(
+        &AllStark::<F, D>::default(),
+        &StarkConfig::standard_fast_config(),
+    );
+    circuits
+        .verify_root(proof)
+        .map_err(ProveError::Verification)?;
+    Ok(ProofSummary::from_public_values(&public_values))
+}
+
+/// Verifies independent proofs, e.g. of several blocks, and reports the index of the first one
+/// that is rejected.
+///
+/// The proofs are verified one after another: the verifier of each STARK already runs in parallel.
+/// Like `verify_generic`, a malformed proof that makes plonky2 panic is reported as rejected.
+pub fn verify_many(
+    all_stark: &AllStark<F, D>,
+    proofs: Vec<AllProof<F, C, D>>,
+    config: &StarkConfig,
+) -> Result<()> {
+    for (index, proof) in proofs.into_iter().enumerate() {
+        verify_generic(all_stark, proof, config).map_err(|err| {
+            let err = match err {
+                ProveError::Verification(err) => err,
+                err => err.into(),
+            };
+            ProveError::BatchVerification { index, err }
+        })?;
+    }
+    Ok(())
+}
+
+/// Checks that the receipt of the `index`-th transaction has the expected status and cumulative
+/// gas, e.g. to tell a successful transaction from a reverted one.
+///
+/// The proof only commits to the receipts root, so the receipts trie is passed as
+/// `receipts_trie` and must hash to `proof.receipts_root`.
+pub fn check_receipt(
+    proof: &ProofSummary,
+    receipts_trie: &HashedPartialTrie,
+    index: usize,
+    expected_status: bool,
+    expected_cum_gas: U256,
+) -> Result<()> {
+    if receipts_trie.hash() != proof.receipts_root {
+        return Err(ProveError::TrieRootMismatch {
+            root: "receipts_root",
+            expected: proof.receipts_root,
+            got: receipts_trie.hash(),
+        });
+    }
+    let receipt = ReceiptView::from_trie(receipts_trie, index)?;
+    if receipt.status() != expected_status || receipt.cumulative_gas_used() != expected_cum_gas {
+        return Err(ProveError::UnexpectedReceipt(format!(
+            "receipt {index} has status {} and cumulative gas {}, expected {expected_status} and {expected_cum_gas}",
+            receipt.status(), receipt.cumulative_gas_used()
+        )));
+    }
+    Ok(())
+}
+
+/// Checks that `proof` commits to exactly the `expected` roots, e.g. taken from the block header
+/// of an execution client, and names the first root that differs.
+pub fn assert_roots(proof: &ProofSummary, expected: TrieRoots) -> Result<()> {
+    let got = TrieRoots {
+        state_root: proof.state_root,
+        transactions_root: proof.transactions_root,
+        receipts_root: proof.receipts_root,
+    };
+    compare_roots(&expected, &got)
+}
+
+/// Returns a `TrieRootMismatch` naming the first root of `got` that differs from `expected`.
+pub(crate) fn compare_roots(expected: &TrieRoots, got: &TrieRoots) -> Result<()> {
+    let roots = [
+        ("state_root", expected.state_root, got.state_root),
+        (
+            "transactions_root",
+            expected.transactions_root,
+            got.transactions_root,
+        ),
+        ("receipts_root", expected.receipts_root, got.receipts_root),
+    ];
+    match roots.into_iter().find(|(_, expected, got)| expected != got) {
+        Some((root, expected, got)) => Err(ProveError::TrieRootMismatch {
+            root,
+            expected,
+            got,
+        }),
+        None => Ok(()),
+    }
+}
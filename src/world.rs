@@ -0,0 +1,134 @@
+use std::collections::HashMap;
+
+use eth_trie_utils::partial_trie::{HashedPartialTrie, PartialTrie};
+use ethereum_types::{Address, H256, U256};
+use keccak_hash::keccak;
+use plonky2_evm::generation::mpt::AccountRlp;
+use plonky2_evm::generation::TrieInputs;
+use plonky2_evm::proof::BlockMetadata;
+use plonky2_evm::Node;
+
+use crate::error::{ProveError, Result};
+use crate::state::{apply_transfer_txn, new_account};
+use crate::trie::{get_account, StateTrieBuilder, StorageTrieBuilder};
+use crate::txn::{SignedTxn, Txn};
+
+/// The accounts of a chain together with their code and storage, so that the state trie, the
+/// storage tries and the contract code can't get out of sync.
+///
+/// The `code_hash` and `storage_root` of each account are derived from what `set_code` and
+/// `set_storage` set, whatever was passed to `set_account`.
+#[derive(Debug, Default, Clone)]
+pub struct WorldState {
+    accounts: HashMap<Address, AccountRlp>,
+    code: HashMap<Address, Vec<u8>>,
+    storage: HashMap<Address, HashMap<H256, U256>>,
+}
+
+impl WorldState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the nonce and balance of `addr` to those of `account`.
+    pub fn set_account(&mut self, addr: Address, account: AccountRlp) {
+        self.accounts.insert(addr, account);
+    }
+
+    /// Sets the code of `addr`, creating an empty account if there's none.
+    pub fn set_code(&mut self, addr: Address, code: Vec<u8>) {
+        self.accounts
+            .entry(addr)
+            .or_insert_with(|| new_account(0.into()));
+        self.code.insert(addr, code);
+    }
+
+    /// Sets `slot` of `addr` to `value`, creating an empty account if there's none. Setting a
+    /// slot to zero removes it from the storage trie.
+    pub fn set_storage(&mut self, addr: Address, slot: H256, value: U256) {
+        self.accounts
+            .entry(addr)
+            .or_insert_with(|| new_account(0.into()));
+        self.storage.entry(addr).or_default().insert(slot, value);
+    }
+
+    /// Returns the account at `addr`, with its derived `code_hash` and `storage_root`.
+    pub fn account(&self, addr: Address) -> Option<AccountRlp> {
+        let account = self.accounts.get(&addr)?;
+        Some(AccountRlp {
+            code_hash: keccak(self.code.get(&addr).map_or(&[][..], Vec::as_slice)),
+            storage_root: self.storage_trie(addr).hash(),
+            ..*account
+        })
+    }
+
+    /// Returns the state trie of every account.
+    pub fn state_trie(&self) -> HashedPartialTrie {
+        let mut state = StateTrieBuilder::new();
+        for &addr in self.accounts.keys() {
+            state.insert(addr, self.account(addr).expect("the account exists"));
+        }
+        state.build()
+    }
+
+    /// Returns the tries of the world before a block, with empty transactions and receipts tries
+    /// and the storage trie of each account with storage.
+    pub fn to_trie_inputs(&self) -> TrieInputs {
+        let storage_tries = self
+            .storage
+            .keys()
+            .map(|&addr| (keccak(addr), self.storage_trie(addr)))
+            .collect();
+        TrieInputs {
+            state_trie: self.state_trie(),
+            transactions_trie: HashedPartialTrie::from(Node::Empty),
+            receipts_trie: HashedPartialTrie::from(Node::Empty),
+            storage_tries,
+        }
+    }
+
+    /// Returns the code of every account keyed by its hash, including the empty code, to be used
+    /// as `GenerationInputs::contract_code`.
+    pub fn contract_code_map(&self) -> HashMap<H256, Vec<u8>> {
+        let mut contract_code = HashMap::from([(keccak([]), vec![])]);
+        for code in self.code.values() {
+            contract_code.insert(keccak(code), code.clone());
+        }
+        contract_code
+    }
+
+    /// Applies `signed_txn`, a transfer included in a block with `block_metadata`, see
+    /// `apply_transfer_txn`, and returns the new state root.
+    ///
+    /// The transactions and receipts roots depend on the rest of the block, see `BlockBuilder`.
+    pub fn apply_transfer(
+        &mut self,
+        signed_txn: &SignedTxn,
+        block_metadata: &BlockMetadata,
+    ) -> Result<H256> {
+        let state_after = apply_transfer_txn(&self.state_trie(), signed_txn, block_metadata)?;
+        let to = Txn::decode(&signed_txn.bytes)?
+            .to()
+            .ok_or_else(|| ProveError::InvalidTransaction("it is a contract creation".into()))?;
+        // 送金で変わるのは送信者、受信者、beneficiaryの残高とnonceだけ
+        for addr in [signed_txn.sender, to, block_metadata.block_beneficiary] {
+            if let Some(account) = get_account(&state_after, addr)? {
+                let entry = self
+                    .accounts
+                    .entry(addr)
+                    .or_insert_with(|| new_account(0.into()));
+                entry.nonce = account.nonce;
+                entry.balance = account.balance;
+            }
+        }
+        Ok(state_after.hash())
+    }
+
+    fn storage_trie(&self, addr: Address) -> HashedPartialTrie {
+        let slots = self.storage.get(&addr).into_iter().flatten();
+        StorageTrieBuilder::with_slots(slots.map(|(&slot, &value)| (slot, value)))
+            .expect("the slots of a map are distinct")
+            .build()
+            .0
+    }
+}
@@ -0,0 +1,24 @@
+use std::str::FromStr;
+
+use eth_trie_utils::nibbles::Nibbles;
+use ethereum_types::Address;
+use hex_literal::hex;
+use plonky2_sample::address_to_state_key_nibbles;
+
+/// Test the state key nibbles of the sample's receiver.
+#[test]
+fn test_address_to_state_key_nibbles() {
+    let to = Address::from(hex!("a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0"));
+    let nibbles = address_to_state_key_nibbles(to);
+
+    // keccak(0xa0a0...a0) = 0xcea3717b...64982 の64ニブル
+    assert_eq!(
+        nibbles,
+        Nibbles::from_str("0xcea3717b23a29f65b207f6582dfe5ae3a6edfbc8187412789b115d6448464982")
+            .unwrap()
+    );
+    assert_eq!(nibbles.count, 64);
+    assert_eq!(nibbles.get_nibble(0), 0xc);
+    assert_eq!(nibbles.get_nibble(1), 0xe);
+    assert_eq!(nibbles.get_nibble(63), 0x2);
+}
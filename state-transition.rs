@@ -0,0 +1,59 @@
+use eth_trie_utils::partial_trie::{HashedPartialTrie, PartialTrie};
+use ethereum_types::{Address, H256, U256};
+use hex_literal::hex;
+use keccak_hash::keccak;
+use plonky2_evm::generation::mpt::AccountRlp;
+use plonky2_evm::Node;
+use plonky2_sample::{
+    apply_simple_transfer, eth_to_wei, prove_transfer, verify_state_transition,
+    BlockMetadataBuilder, ConfigPreset, HashConfig, StateTrieBuilder, SAMPLE_TXN,
+};
+
+/// Test that a valid proof is rejected when it doesn't commit to the expected state root.
+#[test]
+fn test_verify_state_transition() -> anyhow::Result<()> {
+    let sender = Address::from(hex!("2c7536e3605d9c16a7a3d7b1898e529396a65c23"));
+    let to = Address::from(hex!("a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0"));
+    let value = U256::from(100u32);
+    let sender_account_before = AccountRlp {
+        nonce: 5.into(),
+        balance: eth_to_wei(100_000.into()),
+        storage_root: HashedPartialTrie::from(Node::Empty).hash(),
+        code_hash: keccak([]),
+    };
+    let txn = SAMPLE_TXN;
+    let block_metadata = BlockMetadataBuilder::new()
+        .beneficiary(Address::from(hex!(
+            "deadbeefdeadbeefdeadbeefdeadbeefdeadbeef"
+        )))
+        .gas_used(21032.into())
+        .build()?;
+
+    let proof = prove_transfer(
+        sender,
+        to,
+        value,
+        sender_account_before,
+        &txn,
+        block_metadata,
+        HashConfig::Keccak,
+        ConfigPreset::Fast,
+    )?;
+
+    let mut state_before = StateTrieBuilder::new();
+    state_before.insert(sender, sender_account_before);
+    let expected_state_trie_after = apply_simple_transfer(
+        &state_before.build(),
+        sender,
+        to,
+        value,
+        21032.into(),
+        10.into(),
+    )?;
+    verify_state_transition(&proof, expected_state_trie_after.hash())?;
+
+    // 証明自体は正しくても、期待と違うルートならエラーになる
+    let err = verify_state_transition(&proof, H256::repeat_byte(0x42)).unwrap_err();
+    assert!(err.to_string().contains("differs from the expected"));
+    Ok(())
+}
@@ -0,0 +1,46 @@
+use eth_trie_utils::partial_trie::PartialTrie;
+use ethereum_types::{Address, U256};
+use keccak_hash::keccak;
+use plonky2_evm::generation::mpt::AccountRlp;
+use plonky2_evm::Node;
+use plonky2_sample::{address_to_state_key_nibbles, StateTrieBuilder};
+
+/// Test a state trie with three accounts whose state keys share the first nibble.
+#[test]
+fn test_state_trie_builder_shared_prefix() {
+    // 0x..01, 0x..20, 0x..2e のステートキーはどれも 0x1 から始まる
+    let addrs = [
+        Address::from_low_u64_be(0x01),
+        Address::from_low_u64_be(0x20),
+        Address::from_low_u64_be(0x2e),
+    ];
+    let accounts = [1u32, 2, 3].map(|i| AccountRlp {
+        nonce: i.into(),
+        balance: U256::from(i) * 1000,
+        ..AccountRlp::default()
+    });
+    for addr in addrs {
+        assert_eq!(keccak(addr).as_bytes()[0] >> 4, 0x1);
+    }
+
+    let mut builder = StateTrieBuilder::new();
+    for (addr, account) in addrs.into_iter().zip(accounts) {
+        builder.insert(addr, account);
+    }
+    let trie = builder.build();
+
+    // 先頭のニブルが共通なので、ルートはエクステンションノードになる
+    assert!(matches!(*trie, Node::Extension { .. }));
+
+    for (addr, account) in addrs.into_iter().zip(accounts) {
+        let nibbles = address_to_state_key_nibbles(addr);
+        assert_eq!(trie.get(nibbles), Some(rlp::encode(&account).as_ref()));
+    }
+
+    // 挿入順を変えてもルートは変わらない
+    let mut reversed = StateTrieBuilder::new();
+    for (addr, account) in addrs.into_iter().zip(accounts).rev() {
+        reversed.insert(addr, account);
+    }
+    assert_eq!(reversed.build().hash(), trie.hash());
+}
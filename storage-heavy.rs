@@ -0,0 +1,86 @@
+use eth_trie_utils::partial_trie::{HashedPartialTrie, PartialTrie};
+use ethereum_types::{Address, H256, U256};
+use hex_literal::hex;
+use keccak_hash::keccak;
+use plonky2_evm::generation::mpt::AccountRlp;
+use plonky2_evm::generation::TrieInputs;
+use plonky2_evm::Node;
+use plonky2_sample::{
+    apply_transfer_txn, block_inputs, eth_to_wei, get_account, prove_inputs, BlockMetadataBuilder,
+    HashConfig, SignedTxn, StateTrieBuilder, StorageTrieBuilder, SAMPLE_TXN,
+};
+
+/// Returns 16 slots 0..16, each set to a distinct non-zero value derived from `seed`.
+fn slots(seed: u64) -> Vec<(H256, U256)> {
+    (0..16u64)
+        .map(|i| (H256::from_low_u64_be(i), U256::from(seed * 0x100 + i + 1)))
+        .collect()
+}
+
+/// Test a transfer between two accounts with 16 storage slots each, which must keep both storage
+/// roots unchanged.
+#[test]
+fn test_transfer_between_storage_heavy_accounts() -> anyhow::Result<()> {
+    let sender = Address::from(hex!("2c7536e3605d9c16a7a3d7b1898e529396a65c23"));
+    let to = Address::from(hex!("a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0"));
+
+    let mut tries_before = TrieInputs {
+        state_trie: HashedPartialTrie::from(Node::Empty),
+        transactions_trie: HashedPartialTrie::from(Node::Empty),
+        receipts_trie: HashedPartialTrie::from(Node::Empty),
+        storage_tries: vec![],
+    };
+    let sender_storage_root =
+        StorageTrieBuilder::with_slots(slots(1))?.build_into(sender, &mut tries_before);
+    let to_storage_root =
+        StorageTrieBuilder::with_slots(slots(2))?.build_into(to, &mut tries_before);
+    assert_eq!(tries_before.storage_tries.len(), 2);
+    assert_ne!(sender_storage_root, to_storage_root);
+
+    let mut state_before = StateTrieBuilder::new();
+    state_before.insert(
+        sender,
+        AccountRlp {
+            nonce: 5.into(),
+            balance: eth_to_wei(100_000.into()),
+            storage_root: sender_storage_root,
+            code_hash: keccak([]),
+        },
+    );
+    state_before.insert(
+        to,
+        AccountRlp {
+            storage_root: to_storage_root,
+            ..AccountRlp::default()
+        },
+    );
+    tries_before.state_trie = state_before.build();
+
+    let txn = SAMPLE_TXN;
+    let block_metadata = BlockMetadataBuilder::new()
+        .beneficiary(Address::from(hex!(
+            "deadbeefdeadbeefdeadbeefdeadbeefdeadbeef"
+        )))
+        .gas_used(21032.into())
+        .build()?;
+    let signed_txn = SignedTxn {
+        sender,
+        bytes: txn.to_vec(),
+        gas_used: 21032.into(),
+    };
+
+    // 単純な送金はストレージに触れないので、どちらのstorage_rootも変わらない
+    let state_after = apply_transfer_txn(&tries_before.state_trie, &signed_txn, &block_metadata)?;
+    let sender_after = get_account(&state_after, sender)?.unwrap();
+    let to_after = get_account(&state_after, to)?.unwrap();
+    assert_eq!(sender_after.storage_root, sender_storage_root);
+    assert_eq!(to_after.storage_root, to_storage_root);
+    assert_eq!(to_after.balance, 100.into());
+
+    let inputs = block_inputs(vec![signed_txn], tries_before, block_metadata)?.remove(0);
+    let proof = prove_inputs(inputs, HashConfig::Keccak)?;
+    assert_eq!(proof.summary().state_root, state_after.hash());
+
+    proof.verify()?;
+    Ok(())
+}
@@ -0,0 +1,111 @@
+use eth_trie_utils::partial_trie::{HashedPartialTrie, PartialTrie};
+use ethereum_types::{Address, H256};
+use hex_literal::hex;
+use keccak_hash::keccak;
+use plonky2_evm::generation::mpt::AccountRlp;
+use plonky2_evm::generation::TrieInputs;
+use plonky2_evm::Node;
+use plonky2_sample::{
+    block_inputs, eth_to_wei, prove_inputs, BlockMetadataBuilder, HashConfig, ProveError,
+    SignedTxn, StateTrieBuilder, StorageTrieBuilder, SAMPLE_TXN,
+};
+
+/// Test a transfer to an account that already has a storage slot set.
+#[test]
+fn test_transfer_to_account_with_storage() -> anyhow::Result<()> {
+    let sender = Address::from(hex!("2c7536e3605d9c16a7a3d7b1898e529396a65c23"));
+    let to = Address::from(hex!("a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0"));
+
+    let mut tries_before = TrieInputs {
+        state_trie: HashedPartialTrie::from(Node::Empty),
+        transactions_trie: HashedPartialTrie::from(Node::Empty),
+        receipts_trie: HashedPartialTrie::from(Node::Empty),
+        storage_tries: vec![],
+    };
+
+    // 受信者のスロット0に0x2aが入っている
+    let mut storage = StorageTrieBuilder::new();
+    storage.insert(H256::zero(), 0x2a.into())?;
+    let storage_root = storage.build_into(to, &mut tries_before);
+    assert_eq!(
+        storage_root,
+        H256(hex!(
+            "81d1fa699f807735499cf6f7df860797cf66f6a66b565cfcda3fae3521eb6861"
+        ))
+    );
+    assert_eq!(tries_before.storage_tries.len(), 1);
+    assert_eq!(tries_before.storage_tries[0].0, keccak(to));
+
+    let sender_account_before = AccountRlp {
+        nonce: 5.into(),
+        balance: eth_to_wei(100_000.into()),
+        storage_root: HashedPartialTrie::from(Node::Empty).hash(),
+        code_hash: keccak([]),
+    };
+    let to_account_before = AccountRlp {
+        storage_root,
+        ..AccountRlp::default()
+    };
+    let mut state_before = StateTrieBuilder::new();
+    state_before.insert(sender, sender_account_before);
+    state_before.insert(to, to_account_before);
+    tries_before.state_trie = state_before.build();
+
+    let txn = SAMPLE_TXN;
+    let block_metadata = BlockMetadataBuilder::new()
+        .beneficiary(Address::from(hex!(
+            "deadbeefdeadbeefdeadbeefdeadbeefdeadbeef"
+        )))
+        .gas_used(21032.into())
+        .build()?;
+    let signed_txn = SignedTxn {
+        sender,
+        bytes: txn.to_vec(),
+        gas_used: 21032.into(),
+    };
+    let inputs = block_inputs(vec![signed_txn], tries_before, block_metadata)?.remove(0);
+    let proof = prove_inputs(inputs, HashConfig::Keccak)?;
+
+    // 送金してもストレージはそのまま残る
+    let expected_state_trie_after = {
+        let mut state_after = StateTrieBuilder::new();
+        state_after.insert(
+            sender,
+            AccountRlp {
+                balance: sender_account_before.balance - 100 - 21032 * 10,
+                nonce: 6.into(),
+                ..sender_account_before
+            },
+        );
+        state_after.insert(
+            to,
+            AccountRlp {
+                balance: 100.into(),
+                ..to_account_before
+            },
+        );
+        state_after.build()
+    };
+    assert_eq!(proof.summary().state_root, expected_state_trie_after.hash());
+
+    proof.verify()?;
+    Ok(())
+}
+
+/// Test that setting a storage slot twice is rejected.
+#[test]
+fn test_storage_trie_builder_duplicate_slot() -> anyhow::Result<()> {
+    let mut storage = StorageTrieBuilder::new();
+    storage.insert(H256::zero(), 0x2a.into())?;
+    assert!(matches!(
+        storage.insert(H256::zero(), 0x2b.into()),
+        Err(ProveError::InvalidInputs(_))
+    ));
+
+    let slots = [(H256::zero(), 1.into()), (H256::zero(), 2.into())];
+    assert!(matches!(
+        StorageTrieBuilder::with_slots(slots),
+        Err(ProveError::InvalidInputs(_))
+    ));
+    Ok(())
+}
@@ -0,0 +1,33 @@
+use plonky2_sample::{sample_summary, ProofSummary, ProveError};
+
+/// Test encoding a summary to bytes and decoding it back.
+#[test]
+fn test_summary_bytes_round_trip() -> anyhow::Result<()> {
+    let summary = sample_summary();
+    let bytes = summary.to_bytes();
+    assert_eq!(bytes.len(), 181);
+    // gas_usedはリトルエンディアンで、バージョンと3つのルートの後に入る
+    assert_eq!(&bytes[97..100], &[0x28, 0x52, 0x00]);
+
+    assert_eq!(ProofSummary::from_bytes(&bytes)?, summary);
+    Ok(())
+}
+
+/// Test that truncated or unknown input is rejected rather than panicking.
+#[test]
+fn test_summary_bytes_invalid() {
+    let bytes = sample_summary().to_bytes();
+    for len in [0, 1, 97, bytes.len() - 1] {
+        assert!(matches!(
+            ProofSummary::from_bytes(&bytes[..len]),
+            Err(ProveError::InvalidSummary(_))
+        ));
+    }
+
+    let mut unknown_version = bytes;
+    unknown_version[0] = 0xff;
+    assert!(matches!(
+        ProofSummary::from_bytes(&unknown_version),
+        Err(ProveError::InvalidSummary(_))
+    ));
+}
@@ -0,0 +1,22 @@
+use ethereum_types::{Address, H256};
+use plonky2_sample::ProofSummary;
+
+/// Test that a summary prints one field per line, with the roots in full hex.
+#[test]
+fn test_summary_display() {
+    let summary = ProofSummary {
+        state_root: H256::repeat_byte(0x01),
+        transactions_root: H256::repeat_byte(0x02),
+        receipts_root: H256::repeat_byte(0x03),
+        gas_used: 21032.into(),
+        block_number: 1.into(),
+        beneficiary: Address::repeat_byte(0xde),
+    };
+    let printed = summary.to_string();
+    assert_eq!(printed.lines().count(), 6);
+    assert!(printed.contains("block number: 1\n"));
+    assert!(printed.contains("gas used: 21032\n"));
+    assert!(printed.contains(&format!("beneficiary: 0x{}\n", "de".repeat(20))));
+    assert!(printed.contains(&format!("state root: 0x{}\n", "01".repeat(32))));
+    assert!(printed.ends_with(&format!("receipts root: 0x{}", "03".repeat(32))));
+}
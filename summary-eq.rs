@@ -0,0 +1,18 @@
+use ethereum_types::H256;
+use plonky2_sample::{sample_summary, ProofSummary};
+
+/// Test comparing summaries field by field.
+#[test]
+fn test_summary_assert_eq() {
+    let summary = sample_summary();
+    assert_eq!(summary.assert_eq(&summary), Ok(()));
+
+    // 最初に違うフィールドの名前がメッセージに入る
+    let other = ProofSummary {
+        receipts_root: H256::repeat_byte(0x42),
+        gas_used: 21000.into(),
+        ..summary
+    };
+    let err = summary.assert_eq(&other).unwrap_err();
+    assert!(err.starts_with("receipts_root differs"), "{err}");
+}
@@ -0,0 +1,28 @@
+use plonky2_sample::recursion::DEGREE_BITS_RANGES;
+use plonky2_sample::{sample_transfer_inputs, table_rows, TABLE_NAMES};
+
+/// Test reading the size of each STARK table of the sample transfer.
+#[test]
+fn test_sample_transfer_table_rows() -> anyhow::Result<()> {
+    let rows = table_rows(sample_transfer_inputs())?;
+    println!("{rows}");
+
+    // 再帰証明の回路は、単純な送金のテーブルのサイズに合わせてある
+    for (i, bits) in rows.degree_bits.iter().enumerate() {
+        assert!(
+            DEGREE_BITS_RANGES[i].contains(bits),
+            "{} has 2^{bits} rows",
+            TABLE_NAMES[i]
+        );
+    }
+
+    let json = rows.to_json();
+    for (name, bits) in TABLE_NAMES.iter().zip(rows.degree_bits) {
+        let table = format!(
+            "\"{name}\":{{\"degree_bits\":{bits},\"rows\":{}}}",
+            1 << bits
+        );
+        assert!(json.contains(&table), "{json}");
+    }
+    Ok(())
+}
@@ -0,0 +1,26 @@
+#![cfg(feature = "test-utils")]
+
+use plonky2::util::timing::TimingTree;
+use plonky2_evm::all_stark::AllStark;
+use plonky2_evm::config::StarkConfig;
+use plonky2_sample::test_utils::tamper_proof;
+use plonky2_sample::{recursive_circuits, sample_transfer_inputs, D, F};
+
+/// Test that a tampered proof is rejected while the original one is accepted.
+#[test]
+fn test_tampered_proof_is_rejected() -> anyhow::Result<()> {
+    let inputs = sample_transfer_inputs();
+
+    let all_stark = AllStark::<F, D>::default();
+    let config = StarkConfig::standard_fast_config();
+    let circuits = recursive_circuits(&all_stark, &config);
+    let mut timing = TimingTree::new("prove root", log::Level::Debug);
+    let (proof, _public_values) = circuits.prove_root(&all_stark, &config, inputs, &mut timing)?;
+
+    let mut tampered = proof.clone();
+    tamper_proof(&mut tampered);
+    assert_ne!(tampered, proof);
+    assert!(circuits.verify_root(tampered).is_err());
+
+    circuits.verify_root(proof)
+}
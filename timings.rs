@@ -0,0 +1,44 @@
+use std::thread;
+use std::time::Duration;
+
+use plonky2_evm::all_stark::AllStark;
+use plonky2_sample::{
+    prove_inputs_timed, sample_transfer_inputs, ConfigPreset, HashConfig, PhaseTimer,
+};
+
+/// Test collecting the timings of proving the sample transfer.
+#[test]
+fn test_prove_inputs_timed() -> anyhow::Result<()> {
+    let (proof, timings) = prove_inputs_timed(
+        &AllStark::default(),
+        ConfigPreset::Fast,
+        sample_transfer_inputs(),
+        HashConfig::Keccak,
+    )?;
+    proof.verify()?;
+
+    let names: Vec<_> = timings.iter().map(|entry| entry.name.as_str()).collect();
+    assert_eq!(names, ["validate inputs", "prove"]);
+    // 証明が一番時間がかかる
+    assert!(timings[1].duration > timings[0].duration);
+    Ok(())
+}
+
+/// Test that the timer records phases in order and filters them by duration.
+#[test]
+fn test_phase_timer_threshold() {
+    let mut timer = PhaseTimer::new();
+    let value = timer.time("fast", || 42);
+    assert_eq!(value, 42);
+    timer.time("slow", || thread::sleep(Duration::from_millis(20)));
+
+    let all = timer.phases(Duration::ZERO);
+    assert_eq!(all.len(), 2);
+    assert_eq!(all[0].name, "fast");
+    assert!(all[1].duration >= Duration::from_millis(20));
+
+    // しきい値を上げると、短いフェーズは落ちる
+    let slow = timer.phases(Duration::from_millis(20));
+    assert_eq!(slow.len(), 1);
+    assert_eq!(slow[0].name, "slow");
+}
@@ -0,0 +1,27 @@
+use ethereum_types::{Address, U256};
+use hex_literal::hex;
+use plonky2_sample::{TransferTxn, SAMPLE_TXN};
+
+/// Test that decoding and re-encoding the sample transaction gives back the same bytes.
+#[test]
+fn test_transfer_txn_round_trip() -> anyhow::Result<()> {
+    let txn = SAMPLE_TXN;
+
+    let decoded = TransferTxn::decode(&txn)?;
+    assert_eq!(decoded.nonce, 5.into());
+    assert_eq!(decoded.gas_price, 10.into());
+    assert_eq!(decoded.gas_limit, 22000.into());
+    assert_eq!(
+        decoded.to,
+        Some(Address::from(hex!(
+            "a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0"
+        )))
+    );
+    assert_eq!(decoded.value, U256::from(100u32));
+    assert_eq!(decoded.data, hex!("4242"));
+    // v = 27 なのでEIP-155以前の署名
+    assert_eq!(decoded.chain_id(), None);
+
+    assert_eq!(decoded.encode(), txn);
+    Ok(())
+}
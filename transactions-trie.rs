@@ -0,0 +1,104 @@
+use std::str::FromStr;
+
+use eth_trie_utils::nibbles::Nibbles;
+use eth_trie_utils::partial_trie::PartialTrie;
+use ethereum_types::H256;
+use hex_literal::hex;
+use plonky2_evm::generation::mpt::LegacyReceiptRlp;
+use plonky2_sample::{
+    build_receipts_trie, build_transactions_trie, compute_tx_and_receipt_roots, ProveError,
+    SAMPLE_TXN,
+};
+
+/// Test a transactions trie of the two transfers of `multi-transfer.rs`.
+#[test]
+fn test_build_transactions_trie() {
+    let txns = vec![
+        SAMPLE_TXN.to_vec(),
+        hex!("f869060a82520894a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a08a152d02c7e14af67995bc801ba00213ef373c5fd6736d9dafd04d45482b038438e5cfd0e9a7cc366454b18bcd8da042c663ec738e79b82dddb2a3819df510a461383b67da01a2928dcbc294de34a2").to_vec(),
+    ];
+    let trie = build_transactions_trie(&txns);
+
+    assert_eq!(
+        trie.get(Nibbles::from_str("0x80").unwrap()),
+        Some(txns[0].as_slice())
+    );
+    assert_eq!(
+        trie.get(Nibbles::from_str("0x01").unwrap()),
+        Some(txns[1].as_slice())
+    );
+    assert_eq!(
+        trie.hash(),
+        H256(hex!(
+            "e2cb483ad2ebe9691cb1804151af0f19e00edfbb482d07020bdf83e896553d2f"
+        ))
+    );
+    // 1つ目だけなら、サンプルのトランザクショントライと同じルートになる
+    assert_eq!(
+        build_transactions_trie(&txns[..1]).hash(),
+        H256(hex!(
+            "4eabe0d88efc66bd02d576cd69cddeb9356cf96137faa64588a1f629211f5ec9"
+        ))
+    );
+}
+
+/// Test that the transactions and receipts tries use the same key for index 128.
+#[test]
+fn test_transactions_and_receipts_keys_agree() {
+    let txns: Vec<Vec<u8>> = (0..130u8).map(|i| vec![i]).collect();
+    let receipts: Vec<_> = (0..130u64)
+        .map(|i| LegacyReceiptRlp {
+            status: true,
+            cum_gas_used: (21000 * (i + 1)).into(),
+            bloom: vec![0; 256].into(),
+            logs: vec![],
+        })
+        .collect();
+    let transactions_trie = build_transactions_trie(&txns);
+    let receipts_trie = build_receipts_trie(&receipts);
+
+    let key = Nibbles::from_str("0x8180").unwrap();
+    assert_eq!(transactions_trie.get(key), Some([128u8].as_slice()));
+    assert_eq!(
+        receipts_trie.get(key),
+        Some(rlp::encode(&receipts[128]).as_ref())
+    );
+}
+
+/// Test computing the transactions and receipts roots of the two transfers of `multi-transfer.rs`.
+#[test]
+fn test_compute_tx_and_receipt_roots() -> anyhow::Result<()> {
+    let txns = vec![
+        SAMPLE_TXN.to_vec(),
+        hex!("f869060a82520894a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a08a152d02c7e14af67995bc801ba00213ef373c5fd6736d9dafd04d45482b038438e5cfd0e9a7cc366454b18bcd8da042c663ec738e79b82dddb2a3819df510a461383b67da01a2928dcbc294de34a2").to_vec(),
+    ];
+    let receipts: Vec<_> = [21032u64, 42032]
+        .into_iter()
+        .map(|cum_gas_used| LegacyReceiptRlp {
+            status: true,
+            cum_gas_used: cum_gas_used.into(),
+            bloom: vec![0; 256].into(),
+            logs: vec![],
+        })
+        .collect();
+
+    let (transactions_root, receipts_root) = compute_tx_and_receipt_roots(&txns, &receipts)?;
+    assert_eq!(
+        transactions_root,
+        H256(hex!(
+            "e2cb483ad2ebe9691cb1804151af0f19e00edfbb482d07020bdf83e896553d2f"
+        ))
+    );
+    assert_eq!(receipts_root, build_receipts_trie(&receipts).hash());
+    assert_ne!(receipts_root, transactions_root);
+    Ok(())
+}
+
+/// Test that a transaction without a receipt is rejected.
+#[test]
+fn test_compute_tx_and_receipt_roots_count_mismatch() {
+    assert!(matches!(
+        compute_tx_and_receipt_roots(&[vec![0x01], vec![0x02]], &[]),
+        Err(ProveError::UnexpectedReceipt(_))
+    ));
+}
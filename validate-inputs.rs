@@ -0,0 +1,171 @@
+use eth_trie_utils::partial_trie::{HashedPartialTrie, PartialTrie};
+use ethereum_types::{H256, U256};
+use hex_literal::hex;
+use plonky2_evm::generation::GenerationInputs;
+use plonky2_evm::Node;
+use plonky2_sample::{
+    index_key_nibbles, sample_transfer_inputs, validate_inputs, validate_transaction_trie_keys,
+    ProveError,
+};
+
+/// Test that the inputs built by the block builder are valid.
+#[test]
+fn test_validate_sample_inputs() {
+    assert!(validate_inputs(&sample_transfer_inputs()).is_ok());
+}
+
+/// Test that an empty signed transaction is rejected.
+#[test]
+fn test_validate_empty_signed_txn() {
+    let inputs = GenerationInputs {
+        signed_txn: Some(vec![]),
+        ..sample_transfer_inputs()
+    };
+    assert!(matches!(
+        validate_inputs(&inputs),
+        Err(ProveError::InvalidInputs(_))
+    ));
+}
+
+/// Test that gas used decreasing over a transaction is rejected.
+#[test]
+fn test_validate_gas_used_decreasing() {
+    let inputs = GenerationInputs {
+        gas_used_before: 21033.into(),
+        ..sample_transfer_inputs()
+    };
+    assert!(matches!(
+        validate_inputs(&inputs),
+        Err(ProveError::InvalidInputs(_))
+    ));
+}
+
+/// Test that gas used beyond the block's gas used is rejected.
+#[test]
+fn test_validate_gas_used_exceeds_block() {
+    let inputs = GenerationInputs {
+        gas_used_after: 21033.into(),
+        ..sample_transfer_inputs()
+    };
+    assert!(matches!(
+        validate_inputs(&inputs),
+        Err(ProveError::InvalidInputs(_))
+    ));
+}
+
+/// Test that gas used without a transaction is rejected.
+#[test]
+fn test_validate_gas_used_without_txn() {
+    let inputs = GenerationInputs {
+        signed_txn: None,
+        ..sample_transfer_inputs()
+    };
+    assert!(matches!(
+        validate_inputs(&inputs),
+        Err(ProveError::InvalidInputs(_))
+    ));
+}
+
+/// Test that a transactions root not matching the signed transaction is rejected.
+#[test]
+fn test_validate_transactions_root_mismatch() {
+    let mut inputs = sample_transfer_inputs();
+    inputs.trie_roots_after.transactions_root = H256::repeat_byte(0x42);
+    assert!(matches!(
+        validate_inputs(&inputs),
+        Err(ProveError::TrieRootMismatch { .. })
+    ));
+}
+
+/// Test that a transaction at the wrong position in the transactions trie is rejected.
+#[test]
+fn test_validate_transaction_trie_key_gap() {
+    let mut inputs = sample_transfer_inputs();
+    // 1つ目のトランザクションなのに、2つ目の位置(0x01)にあるとしている
+    inputs.txn_number_before = 1.into();
+    assert!(matches!(
+        validate_inputs(&inputs),
+        Err(ProveError::TransactionTrieKeyGap(_))
+    ));
+
+    // 前のトランザクションが0x80ではなく0x01に入っている
+    let txn = inputs.signed_txn.clone().unwrap();
+    inputs
+        .tries
+        .transactions_trie
+        .insert(index_key_nibbles(1), txn.clone());
+    assert!(matches!(
+        validate_inputs(&inputs),
+        Err(ProveError::TransactionTrieKeyGap(_))
+    ));
+
+    // 0x80にあれば、キーの並びは正しい
+    let mut trie = HashedPartialTrie::from(Node::Empty);
+    trie.insert(index_key_nibbles(0), txn.clone());
+    assert!(validate_transaction_trie_keys(&trie, 1).is_ok());
+    // 余分なキーも弾かれる
+    trie.insert(index_key_nibbles(1), txn);
+    assert!(matches!(
+        validate_transaction_trie_keys(&trie, 1),
+        Err(ProveError::TransactionTrieKeyGap(_))
+    ));
+}
+
+/// Test that a changed state or receipts root without a transaction or withdrawal is rejected.
+#[test]
+fn test_validate_roots_changed_without_txn() {
+    let mut inputs = sample_transfer_inputs();
+    inputs.signed_txn = None;
+    inputs.gas_used_after = inputs.gas_used_before;
+    inputs.trie_roots_after.transactions_root = inputs.tries.transactions_trie.hash();
+    // レシートのルートが変わっている
+    assert!(matches!(
+        validate_inputs(&inputs),
+        Err(ProveError::TrieRootMismatch { .. })
+    ));
+
+    // ステートのルートが変わっている
+    inputs.trie_roots_after.receipts_root = inputs.tries.receipts_trie.hash();
+    inputs.trie_roots_after.state_root = H256::repeat_byte(0x42);
+    assert!(matches!(
+        validate_inputs(&inputs),
+        Err(ProveError::TrieRootMismatch { .. })
+    ));
+
+    inputs.trie_roots_after.state_root = inputs.tries.state_trie.hash();
+    assert!(validate_inputs(&inputs).is_ok());
+}
+
+/// Test that a transaction signed for chain 1 is rejected in a block of chain 5.
+#[test]
+fn test_validate_chain_id_mismatch() {
+    let mut inputs = sample_transfer_inputs();
+    // EIP-155でchain 1向けに署名した、サンプルと同じ送金 (v = 0x26)
+    inputs.signed_txn = Some(hex!("f85f050a82520894a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0648026a04c1cca5c79a570f2901528ce2bc9d56c233a07d7ccf0e114e335aafaf9fdd5e3a0646677e73f405bce4461cfeffcb11201e7e8d0bf7eb346c5da4cbbfed6647dba").to_vec());
+    inputs.block_metadata.block_chain_id = 5.into();
+    match validate_inputs(&inputs) {
+        Err(ProveError::ChainIdMismatch { txn, block }) => {
+            assert_eq!(txn, 1.into());
+            assert_eq!(block, 5.into());
+        }
+        res => panic!("unexpected result: {res:?}"),
+    }
+
+    // EIP-155以前のトランザクションはどのチェーンでも有効
+    let mut inputs = sample_transfer_inputs();
+    inputs.block_metadata.block_chain_id = 5.into();
+    assert!(validate_inputs(&inputs).is_ok());
+}
+
+/// Test that a transaction number beyond a usize is rejected rather than panicking.
+#[test]
+fn test_validate_huge_txn_number() {
+    let inputs = GenerationInputs {
+        txn_number_before: U256::MAX,
+        ..sample_transfer_inputs()
+    };
+    assert!(matches!(
+        validate_inputs(&inputs),
+        Err(ProveError::InvalidInputs(_))
+    ));
+}
@@ -0,0 +1,76 @@
+use ethereum_types::{Address, H256, U256};
+use hex_literal::hex;
+use plonky2_evm::all_stark::AllStark;
+use plonky2_evm::generation::mpt::AccountRlp;
+use plonky2_sample::{
+    eth_to_wei, prove_transfer, verify_many, BlockMetadataBuilder, ConfigPreset, EvmProof,
+    HashConfig, ProveError, SAMPLE_TXN,
+};
+
+/// Test that a batch with a tampered proof reports the index of that proof.
+#[test]
+fn test_verify_many() -> anyhow::Result<()> {
+    let sender = Address::from(hex!("2c7536e3605d9c16a7a3d7b1898e529396a65c23"));
+    let to = Address::from(hex!("a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0"));
+    let sender_account_before = AccountRlp {
+        nonce: 5.into(),
+        balance: eth_to_wei(100_000.into()),
+        ..AccountRlp::default()
+    };
+    let txn = SAMPLE_TXN;
+    let block_metadata = BlockMetadataBuilder::new()
+        .beneficiary(Address::from(hex!(
+            "deadbeefdeadbeefdeadbeefdeadbeefdeadbeef"
+        )))
+        .gas_used(21032.into())
+        .build()?;
+
+    let proof = prove_transfer(
+        sender,
+        to,
+        U256::from(100u32),
+        sender_account_before,
+        &txn,
+        block_metadata,
+        HashConfig::Keccak,
+        ConfigPreset::Fast,
+    )?;
+    let EvmProof::Keccak(valid) = proof.proof else {
+        unreachable!("proven with HashConfig::Keccak");
+    };
+
+    // 後の状態ルートを書き換えた証明は検証に失敗する
+    let mut tampered = valid.clone();
+    tampered.public_values.trie_roots_after.state_root = H256::repeat_byte(0x42);
+
+    let all_stark = AllStark::default();
+    let config = ConfigPreset::Fast.stark_config();
+    verify_many(&all_stark, vec![valid.clone()], &config)?;
+    let err = verify_many(&all_stark, vec![valid.clone(), tampered], &config).unwrap_err();
+    assert!(matches!(
+        err,
+        ProveError::BatchVerification { index: 1, .. }
+    ));
+
+    // 形の崩れた証明でも、バッチ全体が止まらずに番号が報告される
+    let mut missing_queries = valid.clone();
+    missing_queries.stark_proofs[0]
+        .proof
+        .opening_proof
+        .query_round_proofs
+        .clear();
+    let mut missing_openings = valid.clone();
+    missing_openings.stark_proofs[0]
+        .proof
+        .openings
+        .local_values
+        .clear();
+    for malformed in [missing_queries, missing_openings] {
+        let err = verify_many(&all_stark, vec![valid.clone(), malformed], &config).unwrap_err();
+        assert!(matches!(
+            err,
+            ProveError::BatchVerification { index: 1, .. }
+        ));
+    }
+    Ok(())
+}
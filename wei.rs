@@ -0,0 +1,20 @@
+use ethereum_types::U256;
+use plonky2_sample::{eth_to_wei, wei_to_eth_string};
+
+/// Test formatting wei amounts in ether.
+#[test]
+fn test_wei_to_eth_string() {
+    assert_eq!(wei_to_eth_string(eth_to_wei(1.into())), "1");
+    assert_eq!(wei_to_eth_string(1.into()), "0.000000000000000001");
+    assert_eq!(wei_to_eth_string(100.into()), "0.0000000000000001");
+    assert_eq!(wei_to_eth_string(U256::zero()), "0");
+
+    // サンプルの送信者の残高
+    assert_eq!(wei_to_eth_string(eth_to_wei(100_000.into())), "100000");
+    // サンプルの送金の後の残高
+    assert_eq!(
+        wei_to_eth_string(eth_to_wei(100_000.into()) - 100 - 21032 * 10),
+        "99999.99999999999978958"
+    );
+    assert_eq!(wei_to_eth_string(eth_to_wei(3.into()) / 2), "1.5");
+}
@@ -0,0 +1,70 @@
+use eth_trie_utils::partial_trie::{HashedPartialTrie, PartialTrie};
+use ethereum_types::{Address, U256};
+use hex_literal::hex;
+use keccak_hash::keccak;
+use plonky2_evm::generation::mpt::AccountRlp;
+use plonky2_evm::generation::TrieInputs;
+use plonky2_evm::Node;
+use plonky2_sample::{
+    eth_to_wei, prove_inputs, BlockBuilder, BlockMetadataBuilder, HashConfig, StateTrieBuilder,
+};
+
+/// Test a block without transactions that credits two withdrawals to new addresses.
+#[test]
+fn test_withdrawals_to_new_addresses() -> anyhow::Result<()> {
+    let account = Address::from(hex!("2c7536e3605d9c16a7a3d7b1898e529396a65c23"));
+    let account_before = AccountRlp {
+        nonce: 5.into(),
+        balance: eth_to_wei(100_000.into()),
+        storage_root: HashedPartialTrie::from(Node::Empty).hash(),
+        code_hash: keccak([]),
+    };
+    let mut state_before = StateTrieBuilder::new();
+    state_before.insert(account, account_before);
+    let tries_before = TrieInputs {
+        state_trie: state_before.build(),
+        transactions_trie: HashedPartialTrie::from(Node::Empty),
+        receipts_trie: HashedPartialTrie::from(Node::Empty),
+        storage_tries: vec![],
+    };
+
+    // バリデーターへの引き出し(金額はwei)
+    let withdrawals = vec![
+        (Address::from_low_u64_be(0x1111), eth_to_wei(1.into())),
+        (Address::from_low_u64_be(0x2222), U256::from(12345)),
+    ];
+
+    let block_metadata = BlockMetadataBuilder::new()
+        .beneficiary(Address::from(hex!(
+            "deadbeefdeadbeefdeadbeefdeadbeefdeadbeef"
+        )))
+        .build()?;
+    let mut builder = BlockBuilder::new(tries_before, block_metadata);
+    builder.with_withdrawals(withdrawals.clone());
+    let mut inputs = builder.build()?;
+    assert_eq!(inputs.len(), 1);
+    let inputs = inputs.remove(0);
+    assert_eq!(inputs.signed_txn, None);
+    assert_eq!(inputs.withdrawals, withdrawals);
+
+    let proof = prove_inputs(inputs, HashConfig::Keccak)?;
+
+    // 引き出し先のアカウントが作られ、金額が入っている
+    let expected_state_trie_after = {
+        let mut state_after = StateTrieBuilder::new();
+        state_after.insert(account, account_before);
+        for (addr, amount) in withdrawals {
+            state_after.insert(
+                addr,
+                AccountRlp {
+                    balance: amount,
+                    ..AccountRlp::default()
+                },
+            );
+        }
+        state_after.build()
+    };
+    assert_eq!(proof.summary().state_root, expected_state_trie_after.hash());
+
+    proof.verify()
+}
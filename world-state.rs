@@ -0,0 +1,97 @@
+use eth_trie_utils::partial_trie::PartialTrie;
+use ethereum_types::{Address, H256};
+use hex_literal::hex;
+use keccak_hash::keccak;
+use plonky2_evm::generation::mpt::AccountRlp;
+use plonky2_sample::{
+    block_inputs, eth_to_wei, prove_inputs, BlockMetadataBuilder, HashConfig, SignedTxn,
+    StorageTrieBuilder, WorldState, SAMPLE_TXN,
+};
+
+/// Test that the code and storage of a world are reflected in its account and tries.
+#[test]
+fn test_world_state_contract() -> anyhow::Result<()> {
+    let contract = Address::from(hex!("c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0"));
+    let code = hex!("60006000fd").to_vec();
+    let mut world = WorldState::new();
+    world.set_code(contract, code.clone());
+    world.set_storage(contract, H256::zero(), 1.into());
+    world.set_storage(contract, H256::from_low_u64_be(1), 2.into());
+    // 0にしたスロットはトライから消える
+    world.set_storage(contract, H256::from_low_u64_be(1), 0.into());
+
+    let (_, storage_root) = StorageTrieBuilder::with_slots([(H256::zero(), 1.into())])?.build();
+    let account = world.account(contract).expect("the contract exists");
+    assert_eq!(account.code_hash, keccak(&code));
+    assert_eq!(account.storage_root, storage_root);
+    assert_eq!(account.balance, 0.into());
+
+    let tries = world.to_trie_inputs();
+    assert_eq!(tries.storage_tries.len(), 1);
+    assert_eq!(tries.storage_tries[0].0, keccak(contract));
+    assert_eq!(tries.storage_tries[0].1.hash(), storage_root);
+    let contract_code = world.contract_code_map();
+    assert_eq!(contract_code.len(), 2);
+    assert_eq!(contract_code[&keccak(&code)], code);
+    Ok(())
+}
+
+/// Test proving a transfer in a world that also holds a contract the transfer doesn't touch.
+#[test]
+fn test_world_state_transfer() -> anyhow::Result<()> {
+    let sender = Address::from(hex!("2c7536e3605d9c16a7a3d7b1898e529396a65c23"));
+    let to = Address::from(hex!("a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0"));
+    let contract = Address::from(hex!("c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0"));
+    let mut world = WorldState::new();
+    world.set_account(
+        sender,
+        AccountRlp {
+            nonce: 5.into(),
+            balance: eth_to_wei(100_000.into()),
+            ..AccountRlp::default()
+        },
+    );
+    world.set_code(contract, hex!("60006000fd").to_vec());
+    world.set_storage(contract, H256::zero(), 1.into());
+    let world_before = world.clone();
+
+    let signed_txn = SignedTxn {
+        sender,
+        bytes: SAMPLE_TXN.to_vec(),
+        gas_used: 21032.into(),
+    };
+    let block_metadata = BlockMetadataBuilder::new()
+        .beneficiary(Address::from(hex!(
+            "deadbeefdeadbeefdeadbeefdeadbeefdeadbeef"
+        )))
+        .gas_used(21032.into())
+        .build()?;
+    let state_root = world.apply_transfer(&signed_txn, &block_metadata)?;
+    assert_eq!(state_root, world.state_trie().hash());
+
+    let sender_after = world.account(sender).expect("the sender exists");
+    assert_eq!(sender_after.nonce, 6.into());
+    assert_eq!(
+        sender_after.balance,
+        eth_to_wei(100_000.into()) - 100 - 21032 * 10
+    );
+    assert_eq!(
+        world.account(to).expect("the receiver exists").balance,
+        100.into()
+    );
+    assert_eq!(world.account(contract), world_before.account(contract));
+
+    // 変更前のワールドから作った入力で、同じルートが証明される
+    let mut inputs = block_inputs(
+        vec![signed_txn],
+        world_before.to_trie_inputs(),
+        block_metadata,
+    )?
+    .remove(0);
+    inputs.contract_code = world_before.contract_code_map();
+    assert_eq!(inputs.trie_roots_after.state_root, state_root);
+
+    let proof = prove_inputs(inputs, HashConfig::Keccak)?;
+    assert_eq!(proof.summary().state_root, state_root);
+    proof.verify()
+}
@@ -0,0 +1,59 @@
+use plonky2::fri::reduction_strategies::FriReductionStrategy;
+use plonky2::fri::FriConfig;
+use plonky2_evm::all_stark::AllStark;
+use plonky2_sample::{
+    fri_stark_config, prove_inputs, sample_transfer_inputs, verify_generic, ConfigPreset, EvmProof,
+    HashConfig, ProveError, D, F,
+};
+
+/// Test that a Keccak proof is rejected, not a panic, by a verifier set up for Poseidon.
+#[test]
+fn test_verify_with_wrong_hash_config() -> anyhow::Result<()> {
+    let proof = prove_inputs(sample_transfer_inputs(), HashConfig::Keccak)?;
+    let config = proof.preset.stark_config();
+    assert!(matches!(
+        proof.verify_as(HashConfig::Poseidon, &config),
+        Err(ProveError::Verification(_))
+    ));
+    proof.verify_as(HashConfig::Keccak, &config)?;
+    Ok(())
+}
+
+/// Test that a proof verified with another `StarkConfig` than it was proven with is rejected,
+/// even where plonky2 panics.
+#[test]
+fn test_verify_with_wrong_stark_config() -> anyhow::Result<()> {
+    let proof = prove_inputs(sample_transfer_inputs(), HashConfig::Keccak)?;
+    let EvmProof::Keccak(all_proof) = proof.proof else {
+        panic!("the proof was generated with Keccak");
+    };
+    // プリセットとはブローアップもクエリ回数も違う
+    let wrong_config = fri_stark_config(FriConfig {
+        rate_bits: 3,
+        cap_height: 4,
+        proof_of_work_bits: 16,
+        reduction_strategy: FriReductionStrategy::ConstantArityBits(4, 5),
+        num_query_rounds: 28,
+    })?;
+    let all_stark = AllStark::<F, D>::default();
+    assert!(matches!(
+        verify_generic(&all_stark, all_proof, &wrong_config),
+        Err(ProveError::Verification(_))
+    ));
+    Ok(())
+}
+
+/// Test that a proof is rejected, before verifying it, by a verifier set up for another preset.
+#[test]
+fn test_verify_as_with_wrong_preset() -> anyhow::Result<()> {
+    let proof = prove_inputs(sample_transfer_inputs(), HashConfig::Keccak)?;
+    assert_eq!(proof.preset, ConfigPreset::Fast);
+    match proof.verify_as(HashConfig::Keccak, &ConfigPreset::Secure.stark_config()) {
+        Err(ProveError::Verification(err)) => {
+            assert!(err.to_string().contains("wasn't generated with the config"))
+        }
+        Err(err) => panic!("unexpected error: {err}"),
+        Ok(()) => panic!("the proof was accepted under another preset"),
+    }
+    Ok(())
+}
@@ -0,0 +1,86 @@
+use eth_trie_utils::partial_trie::{HashedPartialTrie, PartialTrie};
+use ethereum_types::{Address, U256};
+use hex_literal::hex;
+use keccak_hash::keccak;
+use plonky2_evm::generation::mpt::AccountRlp;
+use plonky2_evm::Node;
+use plonky2_sample::{
+    apply_simple_transfer, eth_to_wei, prove_transfer, BlockMetadataBuilder, ConfigPreset,
+    HashConfig, StateTrieBuilder,
+};
+
+/// Test that a zero-value transfer still charges gas and doesn't create the receiver.
+#[test]
+fn test_zero_value_transfer() -> anyhow::Result<()> {
+    let sender = Address::from(hex!("2c7536e3605d9c16a7a3d7b1898e529396a65c23"));
+    let to = Address::from(hex!("a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0"));
+    let sender_account_before = AccountRlp {
+        nonce: 5.into(),
+        balance: eth_to_wei(100_000.into()),
+        storage_root: HashedPartialTrie::from(Node::Empty).hash(),
+        code_hash: keccak([]),
+    };
+
+    // nonce 5, gas_price 10, gas_limit 21000, value 0
+    let txn = hex!("f85f050a82520894a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a080801ca00cbe74491ff51c1d21d9b4ec63b7d2e8b25a6d52ad8153564a6fd8cd35891a6ba020798b82ba20919829532b2c52c9f7157270c36fa2a629de577ff73aaf910af4");
+    let block_metadata = BlockMetadataBuilder::new()
+        .beneficiary(Address::from(hex!(
+            "deadbeefdeadbeefdeadbeefdeadbeefdeadbeef"
+        )))
+        .gas_used(21000.into())
+        .build()?;
+    let proof = prove_transfer(
+        sender,
+        to,
+        U256::zero(),
+        sender_account_before,
+        &txn,
+        block_metadata,
+        HashConfig::Keccak,
+        ConfigPreset::Fast,
+    )?;
+
+    // ガス代は引かれ、nonceも増える。受信者は空のままなのでステートに現れない
+    let mut state_after = StateTrieBuilder::new();
+    state_after.insert(
+        sender,
+        AccountRlp {
+            balance: sender_account_before.balance - 21000 * 10,
+            nonce: 6.into(),
+            ..sender_account_before
+        },
+    );
+    let expected_state_trie_after = state_after.build();
+    assert_eq!(proof.summary().state_root, expected_state_trie_after.hash());
+
+    // 受信者が既にいれば、そのまま残る
+    let to_account = AccountRlp {
+        balance: 1.into(),
+        ..AccountRlp::default()
+    };
+    let mut state_before = StateTrieBuilder::new();
+    state_before.insert(sender, sender_account_before);
+    state_before.insert(to, to_account);
+    let applied = apply_simple_transfer(
+        &state_before.build(),
+        sender,
+        to,
+        U256::zero(),
+        21000.into(),
+        10.into(),
+    )?;
+    let mut state_after = StateTrieBuilder::new();
+    state_after.insert(
+        sender,
+        AccountRlp {
+            balance: sender_account_before.balance - 21000 * 10,
+            nonce: 6.into(),
+            ..sender_account_before
+        },
+    );
+    state_after.insert(to, to_account);
+    assert_eq!(applied.hash(), state_after.build().hash());
+
+    proof.verify()?;
+    Ok(())
+}